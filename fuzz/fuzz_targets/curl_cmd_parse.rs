@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nomcurl::curl::{curl_parsers::curl_cmd_parse, lazy, recover, request};
+
+fuzz_target!(|input: &str| {
+    let _ = curl_cmd_parse(input);
+    let _ = lazy::parse_curl_command_lazy(input);
+    let _ = recover::parse_curl_command_lenient(input);
+    if let Ok(request) = request::parse_curl_command(input) {
+        let _ = request.to_string();
+    }
+});
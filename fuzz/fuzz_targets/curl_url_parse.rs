@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nomcurl::curl::url_parser::{curl_url_parse, query_pairs, CurlURL};
+
+fuzz_target!(|input: &str| {
+    if let Ok((_, url)) = curl_url_parse(input) {
+        let _ = url.to_string();
+    }
+    let _ = CurlURL::try_from(input);
+    query_pairs(input).for_each(drop);
+});
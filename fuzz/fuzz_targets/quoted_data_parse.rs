@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nomcurl::curl::{
+    curl_parsers::{iter_quoted_data_parse, raw_url_parse},
+    request,
+};
+
+// The quoting lexers: the quoted data parsers and the quote tracking of the batch splitter
+fuzz_target!(|input: &str| {
+    let _ = iter_quoted_data_parse(input);
+    let _ = raw_url_parse(input);
+    let _ = request::find_curl_commands(input);
+});
@@ -0,0 +1,106 @@
+use nomcurl::curl::{
+    url_parser::{CurlURL, Protocol},
+    Curl, CurlStru,
+};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Parse a curl command at compile time, a malformed command is a compile error.
+///
+/// Expands into an expression building the parsed `ParsedRequest<'static>` of the command, its
+/// strings borrowed from the command, so nothing is parsed at runtime:
+///
+/// ```ignore
+/// let request = nomcurl_macros::curl!("curl 'https://github.com' -H 'Accept: */*'");
+/// assert_eq!("github.com", request.url().domain);
+/// ```
+#[proc_macro]
+pub fn curl(input: TokenStream) -> TokenStream {
+    let command = parse_macro_input!(input as LitStr);
+
+    match nomcurl::curl::request::parse_curl_command(&command.value()) {
+        Ok(request) => {
+            let curls = request.curls().iter().map(curl_tokens);
+            quote! {
+                ::nomcurl::curl::request::ParsedRequest::new_unchecked(::std::vec![#(#curls),*])
+            }
+            .into()
+        }
+        Err(e) => syn::Error::new(command.span(), format!("invalid curl command: {}", e))
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn curl_tokens(curl: &Curl) -> TokenStream2 {
+    let (variant, stru) = match curl {
+        Curl::URL(url) => {
+            let url = url_tokens(url);
+            return quote! { ::nomcurl::curl::Curl::URL(#url) };
+        }
+        Curl::Method(stru) => ("Method", stru),
+        Curl::Header(stru) => ("Header", stru),
+        Curl::Data(stru) => ("Data", stru),
+        Curl::Flag(stru) => ("Flag", stru),
+    };
+    let variant = Ident::new(variant, Span::call_site());
+    let CurlStru { identifier, data } = stru;
+    let identifier = cow_tokens(identifier);
+    let data = option_tokens(data.as_deref().map(cow_tokens));
+    quote! {
+        ::nomcurl::curl::Curl::#variant(::nomcurl::curl::CurlStru {
+            identifier: #identifier,
+            data: #data,
+        })
+    }
+}
+
+fn url_tokens(url: &CurlURL) -> TokenStream2 {
+    let protocol = match &url.protocol {
+        Protocol::Other(scheme) => quote! { Other(::std::string::String::from(#scheme)) },
+        protocol => {
+            let protocol = Ident::new(&format!("{:?}", protocol), Span::call_site());
+            quote! { #protocol }
+        }
+    };
+    let userinfo = match &url.userinfo {
+        Some(userinfo) => {
+            let userinfo = format!("{}:{}", userinfo.name(), userinfo.pwd());
+            quote! { ::nomcurl::curl::url_parser::UserInfo::new(#userinfo) }
+        }
+        None => quote! { ::core::option::Option::None },
+    };
+    let domain = cow_tokens(&url.domain);
+    let uri = option_tokens(url.uri.as_deref().map(cow_tokens));
+    let queries = option_tokens(url.queries.as_ref().map(|queries| {
+        let queries = queries.iter().map(|(name, value)| {
+            let (name, value) = (cow_tokens(name), cow_tokens(value));
+            quote! { (#name, #value) }
+        });
+        quote! { ::std::vec![#(#queries),*] }
+    }));
+    let fragment = option_tokens(url.fragment.as_deref().map(cow_tokens));
+    quote! {
+        ::nomcurl::curl::url_parser::CurlURL {
+            protocol: ::nomcurl::curl::url_parser::Protocol::#protocol,
+            userinfo: #userinfo,
+            domain: #domain,
+            uri: #uri,
+            queries: #queries,
+            fragment: #fragment,
+        }
+    }
+}
+
+fn cow_tokens(value: &str) -> TokenStream2 {
+    quote! { ::std::borrow::Cow::Borrowed(#value) }
+}
+
+fn option_tokens(value: Option<TokenStream2>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { ::core::option::Option::Some(#value) },
+        None => quote! { ::core::option::Option::None },
+    }
+}
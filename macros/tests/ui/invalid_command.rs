@@ -0,0 +1,6 @@
+use nomcurl_macros::curl;
+
+fn main() {
+    let _ = curl!("curl 'https://github.com' -H 'Accept: */*");
+    let _ = curl!("wget 'https://github.com'");
+}
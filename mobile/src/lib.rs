@@ -0,0 +1,192 @@
+//! The UniFFI bindings of nomcurl: parse, convert and redact curl commands from Swift and Kotlin.
+
+use std::{fmt, str::FromStr};
+
+use nomcurl::{
+    codegen::Target,
+    curl::{
+        document::RequestDocument,
+        error::ParseError,
+        redact::Redactor,
+        request::{self, ParsedRequest},
+    },
+};
+
+uniffi::setup_scaffolding!();
+
+/// The error thrown to Swift and Kotlin.
+#[derive(Debug, PartialEq, uniffi::Error)]
+pub enum NomcurlError {
+    /// The input can't be parsed as a curl command.
+    Parse { message: String },
+    /// The `convert` target isn't one of `targets()`.
+    UnknownTarget { message: String },
+    /// The request can't be written as the target code.
+    Convert { message: String },
+}
+
+impl fmt::Display for NomcurlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NomcurlError::Parse { message }
+            | NomcurlError::UnknownTarget { message }
+            | NomcurlError::Convert { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for NomcurlError {}
+
+impl From<ParseError> for NomcurlError {
+    fn from(err: ParseError) -> Self {
+        NomcurlError::Parse {
+            message: err.to_string(),
+        }
+    }
+}
+
+/// A `-H` header split into its name and value.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct Header {
+    pub name: String,
+    pub value: String,
+}
+
+/// A flag with its value, e.g. `--max-time 10`.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct Flag {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// A parsed curl command, see `parse_json` for the full document.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct Request {
+    pub url: String,
+    /// The effective method, implied by the params without `-X`.
+    pub method: String,
+    pub headers: Vec<Header>,
+    pub data: Vec<String>,
+    pub flags: Vec<Flag>,
+}
+
+impl From<&ParsedRequest<'_>> for Request {
+    fn from(request: &ParsedRequest<'_>) -> Self {
+        let document = RequestDocument::from(request);
+        Self {
+            url: document.url.to_string(),
+            method: request.effective_method().to_string(),
+            headers: document
+                .headers
+                .into_iter()
+                .map(|h| Header {
+                    name: h.name,
+                    value: h.value,
+                })
+                .collect(),
+            data: document.data,
+            flags: document
+                .flags
+                .into_iter()
+                .map(|f| Flag {
+                    name: f.name,
+                    value: f.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Parse a curl command, e.g. one pasted by the user.
+#[uniffi::export]
+pub fn parse(cmd: String) -> Result<Request, NomcurlError> {
+    let request = request::parse_curl_command(&cmd)?;
+    Ok(Request::from(&request))
+}
+
+/// Parse a curl command into the JSON of `nomcurl parse --json`.
+#[uniffi::export]
+pub fn parse_json(cmd: String) -> Result<String, NomcurlError> {
+    let request = request::parse_curl_command(&cmd)?;
+    Ok(serde_json::to_string(&request).expect("a request is always serializable"))
+}
+
+/// Convert a curl command to one of the `targets()`, e.g. `swift` or `kotlin`.
+#[uniffi::export]
+pub fn convert(cmd: String, target: String) -> Result<String, NomcurlError> {
+    let target =
+        Target::from_str(&target).map_err(|message| NomcurlError::UnknownTarget { message })?;
+    let request = request::parse_curl_command(&cmd)?;
+    let code = target
+        .generate(&request)
+        .map_err(|e| NomcurlError::Convert {
+            message: e.to_string(),
+        })?;
+    String::from_utf8(code).map_err(|e| NomcurlError::Convert {
+        message: e.to_string(),
+    })
+}
+
+/// The names of the `convert` targets.
+#[uniffi::export]
+pub fn targets() -> Vec<String> {
+    Target::ALL.iter().map(|t| t.name().to_string()).collect()
+}
+
+/// The curl command with its secrets replaced, the names containing one of the patterns being
+/// sensitive too, see `Redactor`.
+#[uniffi::export]
+pub fn redact(cmd: String, patterns: Vec<String>) -> Result<String, NomcurlError> {
+    let request = request::parse_curl_command(&cmd)?;
+    let redactor = patterns.iter().fold(Redactor::new(), |redactor, pattern| {
+        redactor.with_pattern(pattern)
+    });
+    Ok(redactor.redact(&request).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let request =
+            parse("curl 'https://github.com/a?b=1' -H 'Accept: */*' -d 'x=1' -k".into()).unwrap();
+        assert_eq!("https://github.com/a?b=1", request.url);
+        assert_eq!("POST", request.method);
+        assert_eq!(
+            vec![Header {
+                name: "Accept".into(),
+                value: "*/*".into()
+            }],
+            request.headers
+        );
+        assert_eq!(vec!["x=1".to_string()], request.data);
+        assert_eq!(None, request.flags[0].value);
+
+        let err = parse("curl -k".into()).unwrap_err();
+        assert_eq!("no target url found", err.to_string());
+    }
+
+    #[test]
+    fn test_convert() {
+        assert!(targets().contains(&"swift".to_string()));
+        assert_eq!(
+            "http https://github.com\n",
+            convert("curl 'https://github.com'".into(), "httpie".into()).unwrap()
+        );
+        assert!(matches!(
+            convert("curl 'https://github.com'".into(), "cobol".into()),
+            Err(NomcurlError::UnknownTarget { .. })
+        ));
+    }
+
+    #[test]
+    fn test_redact() {
+        let cmd = "curl 'https://github.com' -H 'Authorization: Bearer abc' -H 'X-Tenant: 42'";
+        assert_eq!(
+            "curl 'https://github.com' -H 'Authorization: Bearer REDACTED' -H 'X-Tenant: REDACTED'",
+            redact(cmd.into(), vec!["tenant".into()]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,32 @@
+const assert = require("node:assert");
+const test = require("node:test");
+
+const nomcurl = require("../nomcurl.node");
+
+test("parse", () => {
+  const request = nomcurl.parse("curl 'https://github.com/a?b=1' -H 'Accept: */*' -d 'x=1'");
+  assert.strictEqual(request.url.domain, "github.com");
+  assert.deepStrictEqual(request.url.queries, [["b", "1"]]);
+  assert.deepStrictEqual(request.headers, [{ name: "Accept", value: "*/*" }]);
+  assert.deepStrictEqual(request.data, ["x=1"]);
+});
+
+test("parse error", () => {
+  assert.throws(() => nomcurl.parse("wget 'https://github.com'"), {
+    code: "InvalidArg",
+    message: "input is not a curl command",
+  });
+});
+
+test("parseBatch", () => {
+  const entries = nomcurl.parseBatch("echo start\ncurl 'https://github.com'\ncurl -k\n");
+  assert.deepStrictEqual(entries.map((e) => e.line), [2, 3]);
+  assert.strictEqual(entries[0].request.url.domain, "github.com");
+  assert.strictEqual(entries[1].error, "no target url found");
+});
+
+test("convert", () => {
+  assert.ok(nomcurl.targets().includes("fetch"));
+  assert.strictEqual(nomcurl.convert("curl 'https://github.com'", "httpie"), "http https://github.com\n");
+  assert.throws(() => nomcurl.convert("curl 'https://github.com'", "cobol"), { code: "InvalidArg" });
+});
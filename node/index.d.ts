@@ -0,0 +1,14 @@
+/* auto-generated by NAPI-RS */
+/* eslint-disable */
+
+/** Parse a curl command into the object of `nomcurl parse --json`. */
+export declare function parse(cmd: string): any
+/**
+ * Parse every curl command found in the text, e.g. a shell script or a log extract, into the
+ * objects of `nomcurl batch`: the line, the command, and the request or the error.
+ */
+export declare function parseBatch(text: string): any
+/** Convert a curl command to one of the `targets()`, e.g. `fetch` or `httpie`. */
+export declare function convert(cmd: string, target: string): string
+/** The names of the `convert` targets. */
+export declare function targets(): Array<string>
@@ -0,0 +1,47 @@
+//! The `nomcurl` Node.js addon: parse curl commands into objects and convert them to code.
+
+use std::str::FromStr;
+
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+use nomcurl::{
+    codegen::Target,
+    curl::{batch, error::ParseError, request},
+};
+use serde_json::Value;
+
+/// Parse a curl command into the object of `nomcurl parse --json`.
+#[napi]
+pub fn parse(cmd: String) -> Result<Value> {
+    let request = request::parse_curl_command(&cmd).map_err(to_js_err)?;
+    Ok(serde_json::to_value(&request).expect("a request is always serializable"))
+}
+
+/// Parse every curl command found in the text, e.g. a shell script or a log extract, into the
+/// objects of `nomcurl batch`: the line, the command, and the request or the error.
+#[napi]
+pub fn parse_batch(text: String) -> Value {
+    let entries = batch::parse_batch(&text);
+    serde_json::to_value(&entries).expect("a batch is always serializable")
+}
+
+/// Convert a curl command to one of the `targets()`, e.g. `fetch` or `httpie`.
+#[napi]
+pub fn convert(cmd: String, target: String) -> Result<String> {
+    let target = Target::from_str(&target).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    let request = request::parse_curl_command(&cmd).map_err(to_js_err)?;
+    let code = target
+        .generate(&request)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+    String::from_utf8(code).map_err(|e| Error::from_reason(e.to_string()))
+}
+
+/// The names of the `convert` targets.
+#[napi]
+pub fn targets() -> Vec<String> {
+    Target::ALL.iter().map(|t| t.name().to_string()).collect()
+}
+
+fn to_js_err(err: ParseError) -> Error {
+    Error::new(Status::InvalidArg, err.to_string())
+}
@@ -0,0 +1,98 @@
+//! The `nomcurl` Python module: parse curl commands into dicts and convert them to code.
+
+use std::str::FromStr;
+
+use nomcurl::{
+    codegen::Target,
+    curl::{batch, error::ParseError as CurlParseError, request},
+};
+use pyo3::{
+    create_exception,
+    exceptions::{PyOSError, PyValueError},
+    prelude::*,
+    types::{PyDict, PyList},
+};
+use serde_json::Value;
+
+create_exception!(
+    nomcurl,
+    ParseError,
+    PyValueError,
+    "The input can't be parsed as a curl command."
+);
+
+/// Parse a curl command into the dict of `nomcurl parse --json`.
+#[pyfunction]
+fn parse(py: Python<'_>, cmd: &str) -> PyResult<PyObject> {
+    let request = request::parse_curl_command(cmd).map_err(to_py_err)?;
+    let value = serde_json::to_value(&request).expect("a request is always serializable");
+    to_py(py, &value)
+}
+
+/// Parse every curl command found in the text, e.g. a shell script or a log extract, into the
+/// dicts of `nomcurl batch`: the line, the command, and the request or the error.
+#[pyfunction]
+fn parse_batch(py: Python<'_>, text: &str) -> PyResult<PyObject> {
+    let entries = batch::parse_batch(text);
+    let value = serde_json::to_value(&entries).expect("a batch is always serializable");
+    to_py(py, &value)
+}
+
+/// Convert a curl command to one of the `targets()`, e.g. `fetch` or `httpie`.
+#[pyfunction]
+fn convert(cmd: &str, target: &str) -> PyResult<String> {
+    let target = Target::from_str(target).map_err(PyValueError::new_err)?;
+    let request = request::parse_curl_command(cmd).map_err(to_py_err)?;
+    let code = target
+        .generate(&request)
+        .map_err(|e| PyOSError::new_err(e.to_string()))?;
+    String::from_utf8(code).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// The names of the `convert` targets.
+#[pyfunction]
+fn targets() -> Vec<&'static str> {
+    Target::ALL.iter().map(|t| t.name()).collect()
+}
+
+fn to_py_err(err: CurlParseError) -> PyErr {
+    ParseError::new_err(err.to_string())
+}
+
+/// The Python object of a JSON value: `None`, `bool`, `int`, `float`, `str`, `list` or `dict`.
+fn to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    let object = match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_pyobject(py)?.into_any().unbind(),
+            None => n.as_f64().into_pyobject(py)?.into_any().unbind(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Array(values) => {
+            let list = PyList::empty(py);
+            for value in values {
+                list.append(to_py(py, value)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, to_py(py, value)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    };
+    Ok(object)
+}
+
+#[pymodule(name = "nomcurl")]
+fn nomcurl_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(convert, m)?)?;
+    m.add_function(wrap_pyfunction!(targets, m)?)?;
+    m.add("ParseError", m.py().get_type::<ParseError>())?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+import pytest
+
+import nomcurl
+
+
+def test_parse():
+    request = nomcurl.parse("curl 'https://github.com/a?b=1' -H 'Accept: */*' -d 'x=1'")
+    assert request["schemaVersion"] == 1
+    assert request["url"]["domain"] == "github.com"
+    assert request["url"]["queries"] == [["b", "1"]]
+    assert request["headers"] == [{"name": "Accept", "value": "*/*"}]
+    assert request["data"] == ["x=1"]
+
+
+def test_parse_error():
+    with pytest.raises(nomcurl.ParseError):
+        nomcurl.parse("wget 'https://github.com'")
+    with pytest.raises(ValueError):
+        nomcurl.parse("curl -k")
+
+
+def test_parse_batch():
+    entries = nomcurl.parse_batch("echo start\ncurl 'https://github.com'\ncurl -k\n")
+    assert [e["line"] for e in entries] == [2, 3]
+    assert entries[0]["request"]["url"]["domain"] == "github.com"
+    assert entries[1]["error"] == "no target url found"
+
+
+def test_convert():
+    assert "httpie" in nomcurl.targets()
+    assert nomcurl.convert("curl 'https://github.com'", "httpie") == "http https://github.com\n"
+    with pytest.raises(ValueError):
+        nomcurl.convert("curl 'https://github.com'", "cobol")
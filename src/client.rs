@@ -0,0 +1,214 @@
+//! Replay of a parsed request with an async reqwest `Client`.
+
+use std::{io, path::Path, time::Duration};
+
+use reqwest::{
+    multipart::{Form, Part},
+    redirect::Policy,
+    Client, ClientBuilder, NoProxy, Proxy, RequestBuilder,
+};
+
+use crate::{
+    codegen::{Body, FormField, RequestParts},
+    curl::request::ParsedRequest,
+};
+
+impl ParsedRequest<'_> {
+    /// The request as a builder of the client: the method, url, headers, `-u` / `--oauth2-bearer`
+    /// auth, body and `--max-time`.
+    ///
+    /// `-k`, `-L`, `--max-redirs`, `-x` and `--connect-timeout` are settings of the client, see
+    /// `client_builder`. Only reading a `-d @file` or `-F name=@file` payload can fail.
+    pub fn to_reqwest(&self, client: &Client) -> io::Result<RequestBuilder> {
+        let parts = RequestParts::new(self);
+
+        let mut builder = client.request(method(&parts), &parts.url);
+        for (name, value) in headers(&parts) {
+            builder = builder.header(name, value);
+        }
+        if let Some(timeout) = parts.timeout {
+            builder = builder.timeout(Duration::from_secs_f64(timeout));
+        }
+
+        Ok(match parts.body {
+            Some(Body::Raw(raw) | Body::Json(raw)) => builder.body(raw),
+            Some(Body::File(path)) => builder.body(std::fs::read(path)?),
+            Some(Body::Form(fields)) => {
+                let mut form = Form::new();
+                for field in fields {
+                    form = match field {
+                        FormField::Text(name, value) => form.text(name, value),
+                        FormField::File(name, path) => form.part(name, file_part(&path)?),
+                        FormField::Content(name, path) => {
+                            form.part(name, Part::bytes(std::fs::read(path)?))
+                        }
+                    };
+                }
+                builder.multipart(form)
+            }
+            None => builder,
+        })
+    }
+
+    /// A client builder with the settings of the request: `-k`, `-L`, `--max-redirs`, `-x` with
+    /// its `-U` user and `--noproxy` hosts, and `--connect-timeout`. Only an invalid proxy url is
+    /// an error.
+    pub fn client_builder(&self) -> reqwest::Result<ClientBuilder> {
+        let settings = ClientSettings::new(self)?;
+
+        let mut client = Client::builder()
+            .danger_accept_invalid_certs(settings.insecure)
+            .redirect(settings.redirect);
+        if let Some(timeout) = settings.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(proxy) = settings.proxy {
+            client = client.proxy(proxy);
+        }
+        Ok(client)
+    }
+
+    /// A blocking client builder with the settings of `client_builder`.
+    #[cfg(feature = "exec")]
+    pub fn blocking_client_builder(&self) -> reqwest::Result<reqwest::blocking::ClientBuilder> {
+        let settings = ClientSettings::new(self)?;
+
+        let mut client = reqwest::blocking::Client::builder()
+            .danger_accept_invalid_certs(settings.insecure)
+            .redirect(settings.redirect);
+        if let Some(timeout) = settings.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(proxy) = settings.proxy {
+            client = client.proxy(proxy);
+        }
+        Ok(client)
+    }
+}
+
+/// The settings of the async and the blocking client, see `ParsedRequest::client_builder`.
+struct ClientSettings {
+    insecure: bool,
+    connect_timeout: Option<Duration>,
+    redirect: Policy,
+    proxy: Option<Proxy>,
+}
+
+impl ClientSettings {
+    fn new(request: &ParsedRequest) -> reqwest::Result<Self> {
+        let parts = RequestParts::new(request);
+
+        let redirect = match (parts.follow_redirects, parts.max_redirects) {
+            (false, _) => Policy::none(),
+            (true, Some(max)) => Policy::limited(max as usize),
+            (true, None) => Policy::default(),
+        };
+        let proxy = match &parts.proxy {
+            Some(proxy) => {
+                let mut proxy = Proxy::all(proxy)?;
+                if let Some(user) = request.flag("--proxy-user").and_then(|f| f.data.as_deref()) {
+                    let (name, pwd) = user.split_once(':').unwrap_or((user, ""));
+                    proxy = proxy.basic_auth(name, pwd);
+                }
+                if let Some(hosts) = request.flag("--noproxy").and_then(|f| f.data.as_deref()) {
+                    proxy = proxy.no_proxy(NoProxy::from_string(hosts));
+                }
+                Some(proxy)
+            }
+            None => None,
+        };
+        Ok(Self {
+            insecure: parts.insecure,
+            connect_timeout: parts.connect_timeout.map(Duration::from_secs_f64),
+            redirect,
+            proxy,
+        })
+    }
+}
+
+/// The method of the request for reqwest, of the async or the blocking client.
+pub(crate) fn method(parts: &RequestParts) -> reqwest::Method {
+    reqwest::Method::from_bytes(parts.method.as_str().as_bytes())
+        .expect("a parsed method is a valid token")
+}
+
+/// The headers sent by reqwest, of the async or the blocking client: the `Authorization` one
+/// of the credentials is added, and the multipart `Content-Type` left to reqwest, which sets it
+/// with its boundary.
+pub(crate) fn headers(parts: &RequestParts) -> Vec<(&str, String)> {
+    let is_form = matches!(parts.body, Some(Body::Form(_)));
+    let mut headers: Vec<(&str, String)> = parts
+        .headers
+        .iter()
+        .filter(|(name, _)| !(is_form && name.eq_ignore_ascii_case("Content-Type")))
+        .map(|(name, value)| (name.as_str(), value.clone()))
+        .collect();
+    if let Some(authorization) = parts.authorization() {
+        headers.push(("Authorization", authorization));
+    }
+    headers
+}
+
+/// The uploaded file of a `-F name=@path`, under its file name.
+fn file_part(path: &str) -> io::Result<Part> {
+    let part = Part::bytes(std::fs::read(path)?);
+    Ok(match Path::new(path).file_name() {
+        Some(name) => part.file_name(name.to_string_lossy().into_owned()),
+        None => part,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_to_reqwest() {
+        let input = "curl 'https://github.com/a?b=1' -X 'PUT' -H 'X-A: 1' -d 'name=nomcurl' -u 'user:pwd' -m '2.5'";
+        let request = parse_curl_command(input).unwrap();
+        let builder = request.to_reqwest(&Client::new()).unwrap();
+        let built = builder.build().unwrap();
+
+        assert_eq!("PUT", built.method().as_str());
+        assert_eq!("https://github.com/a?b=1", built.url().as_str());
+        assert_eq!("1", built.headers()["x-a"]);
+        assert_eq!("Basic dXNlcjpwd2Q=", built.headers()["authorization"]);
+        assert_eq!(Some(Duration::from_millis(2500)), built.timeout().copied());
+        let body = built.body().and_then(|b| b.as_bytes());
+        assert_eq!(Some(&b"name=nomcurl"[..]), body);
+
+        let input = "curl 'https://github.com' --oauth2-bearer 'abc'";
+        let request = parse_curl_command(input).unwrap();
+        let built = request.to_reqwest(&Client::new()).unwrap().build().unwrap();
+        assert_eq!("Bearer abc", built.headers()["authorization"]);
+
+        let input = "curl 'https://github.com' --header 'X-A: 1' -H 'User-Agent:' -H 'X-Empty;' -G --data-urlencode 'q=a b' -A 'nomcurl'";
+        let request = parse_curl_command(input).unwrap();
+        let built = request.to_reqwest(&Client::new()).unwrap().build().unwrap();
+        assert_eq!("1", built.headers()["x-a"]);
+        assert_eq!("", built.headers()["x-empty"]);
+        assert!(!built.headers().contains_key("user-agent"));
+        assert_eq!("https://github.com/?q=a%20b", built.url().as_str());
+        assert!(built.body().is_none());
+    }
+
+    #[test]
+    fn test_client_builder() {
+        let input = "curl 'https://github.com' -k -x 'http://127.0.0.1:8080' --connect-timeout '1'";
+        let request = parse_curl_command(input).unwrap();
+        assert!(request.client_builder().unwrap().build().is_ok());
+
+        let input = "curl 'https://github.com' -x 'http://127.0.0.1:8080' -U 'user:pwd' --noproxy 'localhost,.local'";
+        let request = parse_curl_command(input).unwrap();
+        assert!(request.client_builder().unwrap().build().is_ok());
+
+        let request = parse_curl_command("curl 'https://github.com' -x 'http://[::1'").unwrap();
+        assert!(request.client_builder().is_err());
+        #[cfg(feature = "exec")]
+        assert!(request.blocking_client_builder().is_err());
+
+        let request = parse_curl_command("curl 'https://github.com' -F 'a=@/nonexistent'").unwrap();
+        assert!(request.to_reqwest(&Client::new()).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{escape_double_quoted, is_json, Body, FormField, RequestParts};
+
+/// Generate a browser / Node `fetch()` snippet of the request.
+///
+/// `-L` maps to `redirect: "follow"`, otherwise it's `redirect: "manual"` as curl doesn't follow
+/// redirects. A file body or form field is read with Node's `fs`. A JSON body is written as a
+/// `JSON.stringify()` literal when it's valid JSON, otherwise as an escaped string, e.g. a
+/// templated `{"id": {{id}}}` payload.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::new();
+    let mut options: Vec<String> = Vec::new();
+
+    let reads_file = match &parts.body {
+        Some(Body::File(_)) => true,
+        Some(Body::Form(fields)) => fields
+            .iter()
+            .any(|f| matches!(f, FormField::File(..) | FormField::Content(..))),
+        _ => false,
+    };
+    if reads_file {
+        code.push_str("const fs = require(\"fs\");\n\n");
+    }
+
+    if parts.method != Method::GET {
+        options.push(format!("method: {}", js_string(parts.method.as_str())));
+    }
+
+    let mut headers = parts.headers.clone();
+    if let Some(authorization) = parts.authorization() {
+        headers.push(("Authorization".into(), authorization));
+    }
+    if let Some(Body::Form(_)) = parts.body {
+        // The multipart boundary is set by fetch itself
+        headers.retain(|(n, _)| !n.eq_ignore_ascii_case("Content-Type"));
+    }
+    if !headers.is_empty() {
+        let headers: Vec<String> = headers
+            .iter()
+            .map(|(n, v)| format!("    {}: {}", js_string(n), js_string(v)))
+            .collect();
+        options.push(format!("headers: {{\n{}\n  }}", headers.join(",\n")));
+    }
+
+    match &parts.body {
+        Some(Body::Json(json)) if is_json(json) => {
+            options.push(format!("body: JSON.stringify({})", json))
+        }
+        Some(Body::Raw(raw) | Body::Json(raw)) => options.push(format!("body: {}", js_string(raw))),
+        Some(Body::File(path)) => {
+            options.push(format!("body: fs.readFileSync({})", js_string(path)));
+        }
+        Some(Body::Form(fields)) => {
+            code.push_str("const formData = new FormData();\n");
+            for field in fields {
+                match field {
+                    FormField::Text(name, value) => code.push_str(&format!(
+                        "formData.append({}, {});\n",
+                        js_string(name),
+                        js_string(value)
+                    )),
+                    FormField::File(name, path) => code.push_str(&format!(
+                        "formData.append({}, new Blob([fs.readFileSync({})]), {});\n",
+                        js_string(name),
+                        js_string(path),
+                        js_string(path)
+                    )),
+                    FormField::Content(name, path) => code.push_str(&format!(
+                        "formData.append({}, fs.readFileSync({}, \"utf8\"));\n",
+                        js_string(name),
+                        js_string(path)
+                    )),
+                }
+            }
+            code.push('\n');
+            options.push("body: formData".into());
+        }
+        None => {}
+    }
+
+    match parts.follow_redirects {
+        true => options.push("redirect: \"follow\"".into()),
+        false => options.push("redirect: \"manual\"".into()),
+    }
+
+    code.push_str(&format!("fetch({}", js_string(&parts.url)));
+    if !options.is_empty() {
+        code.push_str(&format!(", {{\n  {}\n}}", options.join(",\n  ")));
+    }
+    code.push_str(");\n");
+    code
+}
+
+fn js_string(input: &str) -> String {
+    format!("\"{}\"", escape_double_quoted(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn fetch(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_fetch() {
+        generic_parse(
+            fetch,
+            "curl 'https://github.com/a' -L",
+            "fetch(\"https://github.com/a\", {\n  redirect: \"follow\"\n});\n".to_string(),
+        );
+
+        let input = "curl 'https://github.com/a' -X 'POST' -H 'Content-Type: application/json' -d '{\"a\":\"b\"}' -b 'sid=1' -L";
+        let expect = r#"fetch("https://github.com/a", {
+  method: "POST",
+  headers: {
+    "Content-Type": "application/json",
+    "Cookie": "sid=1"
+  },
+  body: JSON.stringify({"a":"b"}),
+  redirect: "follow"
+});
+"#;
+        generic_parse(fetch, input, expect.to_string());
+
+        let input = "curl 'https://github.com/a' --json '{\"id\": {{id}}}' -u 'user:pwd' -H 'Authorization: Bearer abc'";
+        let expect = r#"fetch("https://github.com/a", {
+  method: "POST",
+  headers: {
+    "Authorization": "Bearer abc",
+    "Content-Type": "application/json",
+    "Accept": "application/json"
+  },
+  body: "{\"id\": {{id}}}",
+  redirect: "manual"
+});
+"#;
+        generic_parse(fetch, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_fetch_form() {
+        let input = "curl 'https://github.com/upload' -F 'name=nom\"curl' -F 'file=@a.png' -F 'note=<a.txt'";
+        let expect = r#"const fs = require("fs");
+
+const formData = new FormData();
+formData.append("name", "nom\"curl");
+formData.append("file", new Blob([fs.readFileSync("a.png")]), "a.png");
+formData.append("note", fs.readFileSync("a.txt", "utf8"));
+
+fetch("https://github.com/upload", {
+  method: "POST",
+  body: formData,
+  redirect: "manual"
+});
+"#;
+        generic_parse(fetch, input, expect.to_string());
+
+        let input = "curl 'https://github.com/upload' -d '@body.json' -L";
+        let expect = r#"const fs = require("fs");
+
+fetch("https://github.com/upload", {
+  method: "POST",
+  headers: {
+    "Content-Type": "application/x-www-form-urlencoded"
+  },
+  body: fs.readFileSync("body.json"),
+  redirect: "follow"
+});
+"#;
+        generic_parse(fetch, input, expect.to_string());
+    }
+}
@@ -0,0 +1,199 @@
+use std::io;
+
+use serde_json::{json, Value};
+
+use crate::curl::request::ParsedRequest;
+
+use super::{Body, FormField, RequestParts};
+
+/// The `startedDateTime` of the entries, the requests aren't sent.
+const STARTED: &str = "1970-01-01T00:00:00.000Z";
+
+/// Generate a HAR 1.2 document of the request, an entry with an empty response, e.g. to import
+/// it in the network panel of a browser or a proxy.
+///
+/// The `Cookie` header is listed in `cookies` too, and the query of the url in `queryString`.
+/// `@file` payloads and form files given with `<` are read from disk.
+pub fn generate(request: &ParsedRequest) -> io::Result<String> {
+    generate_batch(core::slice::from_ref(request))
+}
+
+/// Generate a HAR document with an entry per request, in order.
+pub fn generate_batch(requests: &[ParsedRequest]) -> io::Result<String> {
+    let entries = requests.iter().map(entry).collect::<io::Result<Vec<_>>>()?;
+    let har = json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "nomcurl", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries,
+        }
+    });
+    Ok(serde_json::to_string_pretty(&har)? + "\n")
+}
+
+fn entry(request: &ParsedRequest) -> io::Result<Value> {
+    let parts = RequestParts::new(request);
+
+    let mut headers = parts.headers.clone();
+    if let Some(authorization) = parts.authorization() {
+        headers.push(("Authorization".into(), authorization));
+    }
+    let cookies: Vec<Value> = parts
+        .header("Cookie")
+        .into_iter()
+        .flat_map(|cookie| cookie.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect();
+    let query: Vec<Value> = parts
+        .url
+        .split('#')
+        .next()
+        .and_then(|url| url.split_once('?'))
+        .into_iter()
+        .flat_map(|(_, query)| query.split('&'))
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            json!({ "name": name, "value": value })
+        })
+        .collect();
+
+    let mime_type = parts.header("Content-Type").unwrap_or_default();
+    let post_data = match &parts.body {
+        Some(Body::Raw(text) | Body::Json(text)) => Some(json!({
+            "mimeType": mime_type,
+            "text": text,
+        })),
+        Some(Body::File(path)) => Some(json!({
+            "mimeType": mime_type,
+            "text": String::from_utf8_lossy(&std::fs::read(path)?),
+        })),
+        Some(Body::Form(fields)) => {
+            let mut params = Vec::new();
+            for field in fields {
+                params.push(match field {
+                    FormField::Text(name, value) => json!({ "name": name, "value": value }),
+                    FormField::File(name, path) => json!({ "name": name, "fileName": path }),
+                    FormField::Content(name, path) => json!({
+                        "name": name,
+                        "value": String::from_utf8_lossy(&std::fs::read(path)?),
+                    }),
+                });
+            }
+            Some(json!({ "mimeType": "multipart/form-data", "params": params }))
+        }
+        None => None,
+    };
+    let body_size = match &post_data {
+        Some(post_data) => post_data["text"].as_str().map_or(-1, |t| t.len() as i64),
+        None => 0,
+    };
+
+    let http_version = if request.has_flag("--http1.0") {
+        "HTTP/1.0"
+    } else if request.has_flag("--http2") {
+        "HTTP/2"
+    } else {
+        "HTTP/1.1"
+    };
+    let mut har_request = json!({
+        "method": parts.method.as_str(),
+        "url": parts.url,
+        "httpVersion": http_version,
+        "cookies": cookies,
+        "headers": headers
+            .iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect::<Vec<_>>(),
+        "queryString": query,
+        "headersSize": -1,
+        "bodySize": body_size,
+    });
+    if let Some(post_data) = post_data {
+        har_request["postData"] = post_data;
+    }
+
+    Ok(json!({
+        "startedDateTime": STARTED,
+        "time": 0,
+        "request": har_request,
+        "response": {
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+        },
+        "cache": {},
+        "timings": { "send": 0, "wait": 0, "receive": 0 },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curl::request::parse_curl_command, import::har::parse_har, test_util::generic_parse,
+    };
+
+    fn to_har(input: &str) -> Value {
+        let request = parse_curl_command(input).unwrap();
+        serde_json::from_str(&generate(&request).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_generate_har() {
+        let har = to_har("curl 'https://github.com/a?b=1&c' -X 'PUT' -H 'X-A: 1' -b 'k=v; l=w' --data-raw 'x=1' -u 'user:pwd'");
+        assert_eq!("1.2", har["log"]["version"]);
+        let request = &har["log"]["entries"][0]["request"];
+        let expect = json!({
+            "method": "PUT",
+            "url": "https://github.com/a?b=1&c",
+            "httpVersion": "HTTP/1.1",
+            "cookies": [{ "name": "k", "value": "v" }, { "name": "l", "value": "w" }],
+            "headers": [
+                { "name": "X-A", "value": "1" },
+                { "name": "Cookie", "value": "k=v; l=w" },
+                { "name": "Content-Type", "value": "application/x-www-form-urlencoded" },
+                { "name": "Authorization", "value": "Basic dXNlcjpwd2Q=" },
+            ],
+            "queryString": [{ "name": "b", "value": "1" }, { "name": "c", "value": "" }],
+            "headersSize": -1,
+            "bodySize": 3,
+            "postData": { "mimeType": "application/x-www-form-urlencoded", "text": "x=1" },
+        });
+        assert_eq!(&expect, request);
+
+        let har = to_har("curl 'https://github.com/upload' -F 'a=1' -F 'f=@a.png' --http2");
+        let request = &har["log"]["entries"][0]["request"];
+        assert_eq!("HTTP/2", request["httpVersion"]);
+        let expect = json!({
+            "mimeType": "multipart/form-data",
+            "params": [{ "name": "a", "value": "1" }, { "name": "f", "fileName": "a.png" }],
+        });
+        assert_eq!(expect, request["postData"]);
+    }
+
+    #[test]
+    fn test_generate_har_import() {
+        let roundtrip = |input: &str| {
+            let request = parse_curl_command(input).unwrap();
+            let har = generate(&request).unwrap();
+            parse_har(&har).unwrap().remove(0).unwrap().to_string()
+        };
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' --data-raw '{\"a\":1}'";
+        generic_parse(roundtrip, input, input.to_string());
+
+        let requests = [
+            parse_curl_command("curl 'https://a.com'").unwrap(),
+            parse_curl_command("curl 'https://b.com'").unwrap(),
+        ];
+        let har = generate_batch(&requests).unwrap();
+        assert_eq!(2, parse_har(&har).unwrap().len());
+    }
+}
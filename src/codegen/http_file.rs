@@ -0,0 +1,140 @@
+use crate::curl::request::ParsedRequest;
+
+use super::{Body, FormField, RequestParts};
+
+const BOUNDARY: &str = "nomcurl-boundary";
+
+/// Generate the JetBrains / VS Code REST Client `.http` request of the request.
+///
+/// curl not following redirects without `-L` maps to `# @no-redirect`, and `-m` /
+/// `--connect-timeout` to the `# @timeout` / `# @connection-timeout` tags.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::new();
+
+    if !parts.follow_redirects {
+        code.push_str("# @no-redirect\n");
+    }
+    if let Some(timeout) = parts.timeout {
+        code.push_str(&format!("# @timeout {}\n", timeout.ceil()));
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        code.push_str(&format!("# @connection-timeout {}\n", timeout.ceil()));
+    }
+    code.push_str(&format!("{} {}\n", parts.method, parts.url));
+
+    let is_form = matches!(parts.body, Some(Body::Form(_)));
+    for (name, value) in &parts.headers {
+        if !(is_form && name.eq_ignore_ascii_case("Content-Type")) {
+            code.push_str(&format!("{}: {}\n", name, value));
+        }
+    }
+    if is_form {
+        code.push_str(&format!(
+            "Content-Type: multipart/form-data; boundary={}\n",
+            BOUNDARY
+        ));
+    }
+    if let Some(authorization) = parts.authorization() {
+        code.push_str(&format!("Authorization: {}\n", authorization));
+    }
+
+    match &parts.body {
+        Some(Body::Raw(body) | Body::Json(body)) => code.push_str(&format!("\n{}\n", body)),
+        Some(Body::File(path)) => code.push_str(&format!("\n< {}\n", path)),
+        Some(Body::Form(fields)) => {
+            code.push('\n');
+            for field in fields {
+                code.push_str(&format!("--{}\n", BOUNDARY));
+                match field {
+                    FormField::Text(name, value) => code.push_str(&format!(
+                        "Content-Disposition: form-data; name=\"{}\"\n\n{}\n",
+                        name, value
+                    )),
+                    FormField::File(name, path) => code.push_str(&format!(
+                        "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\n\n< {}\n",
+                        name, path, path
+                    )),
+                    FormField::Content(name, path) => code.push_str(&format!(
+                        "Content-Disposition: form-data; name=\"{}\"\n\n< {}\n",
+                        name, path
+                    )),
+                }
+            }
+            code.push_str(&format!("--{}--\n", BOUNDARY));
+        }
+        None => {}
+    }
+    code
+}
+
+/// Generate a `.http` file of all the requests, separated by `###`.
+pub fn generate_batch(requests: &[ParsedRequest]) -> String {
+    requests
+        .iter()
+        .map(generate)
+        .collect::<Vec<String>>()
+        .join("\n###\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn http_file(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_http_file() {
+        let input = "curl 'https://github.com/a' -X 'POST' -H 'Content-Type: application/json' -d '{\"a\":1}' -u 'user:pwd' -m '2.5'";
+        let expect = r#"# @no-redirect
+# @timeout 3
+POST https://github.com/a
+Content-Type: application/json
+Authorization: Basic dXNlcjpwd2Q=
+
+{"a":1}
+"#;
+        generic_parse(http_file, input, expect.to_string());
+
+        let input = "curl 'https://github.com/upload' -F 'name=nomcurl' -F 'file=@a.png' -L";
+        let expect = r#"POST https://github.com/upload
+Content-Type: multipart/form-data; boundary=nomcurl-boundary
+
+--nomcurl-boundary
+Content-Disposition: form-data; name="name"
+
+nomcurl
+--nomcurl-boundary
+Content-Disposition: form-data; name="file"; filename="a.png"
+
+< a.png
+--nomcurl-boundary--
+"#;
+        generic_parse(http_file, input, expect.to_string());
+
+        let input = "curl 'https://github.com/a' -u 'user:pwd' -H 'Authorization: Bearer abc' -L";
+        let expect = "GET https://github.com/a\nAuthorization: Bearer abc\n";
+        generic_parse(http_file, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_http_file_batch() {
+        let requests = vec![
+            parse_curl_command("curl 'https://github.com/a' -L").unwrap(),
+            parse_curl_command("curl 'https://github.com/b' -L -d '@body.txt'").unwrap(),
+        ];
+        let expect = r#"GET https://github.com/a
+
+###
+
+POST https://github.com/b
+Content-Type: application/x-www-form-urlencoded
+
+< body.txt
+"#;
+        generic_parse(generate_batch, requests.as_slice(), expect.to_string());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{shell_quote, Body, FormField, RequestParts};
+
+/// Generate an HTTPie command line of the request.
+///
+/// Queries become `name==value` items, headers `Name:value`, and bodies `name=value` /
+/// `name:=json` items when HTTPie can rebuild them, otherwise `--raw` is used.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut args: Vec<String> = vec!["http".into()];
+    let mut items: Vec<String> = Vec::new();
+
+    let (url, query) = match parts.url.split_once('?') {
+        Some((url, query)) if !query.contains(['%', '+', '#']) => (url, Some(query)),
+        _ => (parts.url.as_str(), None),
+    };
+    for pair in query.into_iter().flat_map(|q| q.split('&')) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        items.push(format!("{}=={}", item_key(name), value));
+    }
+
+    let mut skip_content_type = false;
+    match &parts.body {
+        Some(Body::Form(fields)) => {
+            args.push("--multipart".into());
+            skip_content_type = true;
+            items.extend(fields.iter().map(|field| match field {
+                FormField::Text(name, value) => format!("{}={}", item_key(name), value),
+                FormField::File(name, path) => format!("{}@{}", item_key(name), path),
+                FormField::Content(name, path) => format!("{}=@{}", item_key(name), path),
+            }));
+        }
+        Some(Body::Raw(raw)) => match form_items(raw) {
+            Some(form) if parts.header("Content-Type") == Some(FORM_CONTENT_TYPE) => {
+                args.push("--form".into());
+                skip_content_type = true;
+                items.extend(form);
+            }
+            _ => args.push(format!("--raw={}", raw)),
+        },
+        Some(Body::Json(json)) => match json_items(json) {
+            Some(json) => items.extend(json),
+            None => args.push(format!("--raw={}", json)),
+        },
+        Some(Body::File(_)) | None => {}
+    }
+
+    let headers = parts
+        .headers
+        .iter()
+        .filter(|(n, _)| !(skip_content_type && n.eq_ignore_ascii_case("Content-Type")));
+    for (name, value) in headers {
+        match value.is_empty() {
+            // `Name:` would remove the header, `Name;` sends it empty
+            true => items.push(format!("{};", name)),
+            false => items.push(format!("{}:{}", name, value)),
+        }
+    }
+
+    if let Some((name, pwd)) = &parts.basic_auth {
+        match pwd {
+            Some(pwd) => args.push(format!("--auth={}:{}", name, pwd)),
+            None => args.push(format!("--auth={}", name)),
+        }
+    } else if let Some(token) = &parts.bearer_token {
+        args.push("--auth-type=bearer".into());
+        args.push(format!("--auth={}", token));
+    }
+    if parts.insecure {
+        args.push("--verify=no".into());
+    }
+    if parts.follow_redirects {
+        args.push("--follow".into());
+        if let Some(max) = parts.max_redirects {
+            args.push(format!("--max-redirects={}", max));
+        }
+    }
+    if let Some(timeout) = parts.timeout {
+        args.push(format!("--timeout={}", timeout));
+    }
+    if let Some(proxy) = &parts.proxy {
+        let protocol = url.split("://").next().unwrap_or("http");
+        args.push(format!("--proxy={}:{}", protocol, proxy));
+    }
+    if let Some(output) = &parts.output {
+        args.push(format!("--output={}", output));
+    }
+
+    if parts.method != Method::GET {
+        args.push(parts.method.as_str().into());
+    }
+    args.push(url.into());
+    args.extend(items);
+
+    let mut code: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+    if let Some(Body::File(path)) = &parts.body {
+        code.push(format!("< {}", shell_quote(path)));
+    }
+    code.join(" ") + "\n"
+}
+
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Escape the item separators HTTPie would otherwise find in the key.
+fn item_key(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    for c in key.chars() {
+        if matches!(c, '\\' | ':' | '=' | '@' | ';') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Split a `a=1&b=2` payload into `--form` items, `None` if HTTPie would encode it differently.
+fn form_items(raw: &str) -> Option<Vec<String>> {
+    if raw.contains(['%', '+']) {
+        return None;
+    }
+    raw.split('&')
+        .map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            Some(format!("{}={}", item_key(name), value))
+        })
+        .collect()
+}
+
+/// Split a JSON object into `name=string` / `name:=json` items, `None` if it isn't one.
+fn json_items(json: &str) -> Option<Vec<String>> {
+    let inner = json.trim().strip_prefix('{')?.strip_suffix('}')?;
+    if inner.trim().is_empty() {
+        return None;
+    }
+
+    let mut members = Vec::new();
+    let (mut depth, mut in_string, mut escaped, mut start) = (0, false, false, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' | '[' if !in_string => depth += 1,
+            '}' | ']' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                members.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    members.push(&inner[start..]);
+
+    members
+        .iter()
+        .map(|member| {
+            let (key, value) = member.trim().strip_prefix('"')?.split_once('"')?;
+            let value = value.trim_start().strip_prefix(':')?.trim();
+            if key.contains('\\') || value.is_empty() {
+                return None;
+            }
+            match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(string) if !string.contains(['\\', '"']) => {
+                    Some(format!("{}={}", item_key(key), string))
+                }
+                _ => Some(format!("{}:={}", item_key(key), value)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn httpie(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_httpie() {
+        generic_parse(
+            httpie,
+            "curl 'https://example.com/api?page=2&q=nom' -H 'X-Token: abc'",
+            "http https://example.com/api page==2 q==nom X-Token:abc\n".to_string(),
+        );
+
+        let input = "curl 'https://example.com/api' -H 'X-Token: abc' -d 'name=value' -d 'a b=1'";
+        let expect = "http --form POST https://example.com/api name=value 'a b=1' X-Token:abc\n";
+        generic_parse(httpie, input, expect.to_string());
+
+        let input = "curl 'https://example.com/api' -X 'PUT' -H 'Content-Type: application/json' -d '{\"name\": \"nomcurl\", \"tags\": [\"a\", \"b\"], \"n\": 1}' -u 'user:pwd' -k -L";
+        let expect = "http --auth=user:pwd --verify=no --follow PUT https://example.com/api name=nomcurl 'tags:=[\"a\", \"b\"]' n:=1 Content-Type:application/json\n";
+        generic_parse(httpie, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_httpie_bodies() {
+        let input = "curl 'https://example.com/upload' -F 'name=nomcurl' -F 'file=@a.png'";
+        let expect = "http --multipart POST https://example.com/upload name=nomcurl file@a.png\n";
+        generic_parse(httpie, input, expect.to_string());
+
+        let input = "curl 'https://example.com/api' -d 'a%20b'";
+        let expect = "http --raw=a%20b POST https://example.com/api Content-Type:application/x-www-form-urlencoded\n";
+        generic_parse(httpie, input, expect.to_string());
+
+        let input = "curl 'https://example.com/api' -d '@body.json' --oauth2-bearer 'token'";
+        let expect = "http --auth-type=bearer --auth=token POST https://example.com/api Content-Type:application/x-www-form-urlencoded < body.json\n";
+        generic_parse(httpie, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_json_items() {
+        generic_parse(json_items, "[1, 2]", None);
+        generic_parse(json_items, "{\"a\":1,}", None);
+        generic_parse(
+            json_items,
+            r#"{"a:b": "x", "c": "say \"hi\"", "d": {"e": null}}"#,
+            Some(vec![
+                r"a\:b=x".to_string(),
+                r#"c:="say \"hi\"""#.to_string(),
+                r#"d:={"e": null}"#.to_string(),
+            ]),
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{escape_double_quoted, Body, FormField, RequestParts};
+
+/// The headers `java.net.http` refuses to set.
+const RESTRICTED_HEADERS: [&str; 5] = ["Connection", "Content-Length", "Expect", "Host", "Upgrade"];
+
+const BOUNDARY: &str = "nomcurl-boundary";
+
+/// Generate a Java 11+ `HttpClient` snippet of the request.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::new();
+
+    if parts.insecure {
+        code.push_str(
+            "// --insecure: build the client with an SSLContext trusting every certificate\n",
+        );
+    }
+
+    let body = match &parts.body {
+        Some(Body::Raw(raw) | Body::Json(raw)) => {
+            format!("HttpRequest.BodyPublishers.ofString({})", java_string(raw))
+        }
+        Some(Body::File(path)) => format!(
+            "HttpRequest.BodyPublishers.ofFile(Path.of({}))",
+            java_string(path)
+        ),
+        Some(Body::Form(fields)) => {
+            code.push_str(&multipart(fields));
+            "HttpRequest.BodyPublishers.ofByteArray(multipart.toByteArray())".into()
+        }
+        None => "HttpRequest.BodyPublishers.noBody()".into(),
+    };
+
+    code.push_str("HttpClient client = HttpClient.newBuilder()");
+    if parts.follow_redirects {
+        code.push_str("\n    .followRedirects(HttpClient.Redirect.NORMAL)");
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        code.push_str(&format!("\n    .connectTimeout({})", duration(timeout)));
+    }
+    if let Some(proxy) = &parts.proxy {
+        let address = proxy.split("://").last().unwrap_or(proxy);
+        match address.rsplit_once(':') {
+            Some((host, port)) if port.parse::<u16>().is_ok() => code.push_str(&format!(
+                "\n    .proxy(ProxySelector.of(new InetSocketAddress({}, {})))",
+                java_string(host),
+                port
+            )),
+            _ => code.push_str(&format!(
+                "\n    .proxy(ProxySelector.of(new InetSocketAddress({}, 1080)))",
+                java_string(address)
+            )),
+        }
+    }
+    code.push_str("\n    .build();\n\n");
+
+    code.push_str("HttpRequest request = HttpRequest.newBuilder()");
+    code.push_str(&format!(
+        "\n    .uri(URI.create({}))",
+        java_string(&parts.url)
+    ));
+    if let Some(timeout) = parts.timeout {
+        code.push_str(&format!("\n    .timeout({})", duration(timeout)));
+    }
+
+    let is_form = matches!(parts.body, Some(Body::Form(_)));
+    for (name, value) in &parts.headers {
+        if RESTRICTED_HEADERS
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name))
+            || (is_form && name.eq_ignore_ascii_case("Content-Type"))
+        {
+            continue;
+        }
+        code.push_str(&format!(
+            "\n    .header({}, {})",
+            java_string(name),
+            java_string(value)
+        ));
+    }
+    if is_form {
+        let content_type = format!("multipart/form-data; boundary={}", BOUNDARY);
+        code.push_str(&format!(
+            "\n    .header(\"Content-Type\", {})",
+            java_string(&content_type)
+        ));
+    }
+    if let Some(authorization) = parts.authorization() {
+        code.push_str(&format!(
+            "\n    .header(\"Authorization\", {})",
+            java_string(&authorization)
+        ));
+    }
+
+    match (&parts.method, &parts.body) {
+        (Method::GET, None) => code.push_str("\n    .GET()"),
+        (Method::DELETE, None) => code.push_str("\n    .DELETE()"),
+        (method, _) => code.push_str(&format!(
+            "\n    .method({}, {})",
+            java_string(method.as_str()),
+            body
+        )),
+    }
+    code.push_str("\n    .build();\n\n");
+
+    match &parts.output {
+        Some(output) => code.push_str(&format!(
+            "HttpResponse<Path> response = client.send(request, HttpResponse.BodyHandlers.ofFile(Path.of({})));\n",
+            java_string(output)
+        )),
+        None => code.push_str(
+            "HttpResponse<String> response = client.send(request, HttpResponse.BodyHandlers.ofString());\nSystem.out.println(response.body());\n",
+        ),
+    }
+    code
+}
+
+/// Assemble the multipart body by hand, `java.net.http` has no multipart publisher.
+fn multipart(fields: &[FormField]) -> String {
+    let mut code = String::from("ByteArrayOutputStream multipart = new ByteArrayOutputStream();\n");
+    for field in fields {
+        match field {
+            FormField::Text(name, value) => {
+                let part = format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    BOUNDARY, name, value
+                );
+                code.push_str(&format!(
+                    "multipart.write({}.getBytes());\n",
+                    java_string(&part)
+                ));
+            }
+            FormField::File(name, path) => {
+                let part = format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\r\n",
+                    BOUNDARY, name, path
+                );
+                code.push_str(&format!(
+                    "multipart.write({}.getBytes());\n",
+                    java_string(&part)
+                ));
+                code.push_str(&format!(
+                    "multipart.write(Files.readAllBytes(Path.of({})));\n",
+                    java_string(path)
+                ));
+                code.push_str("multipart.write(\"\\r\\n\".getBytes());\n");
+            }
+            FormField::Content(name, path) => {
+                let part = format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                    BOUNDARY, name
+                );
+                code.push_str(&format!(
+                    "multipart.write({}.getBytes());\n",
+                    java_string(&part)
+                ));
+                code.push_str(&format!(
+                    "multipart.write(Files.readAllBytes(Path.of({})));\n",
+                    java_string(path)
+                ));
+                code.push_str("multipart.write(\"\\r\\n\".getBytes());\n");
+            }
+        }
+    }
+    let end = format!("--{}--\r\n", BOUNDARY);
+    code.push_str(&format!(
+        "multipart.write({}.getBytes());\n\n",
+        java_string(&end)
+    ));
+    code
+}
+
+fn duration(seconds: f64) -> String {
+    format!("Duration.ofMillis({})", (seconds * 1000.0).round())
+}
+
+fn java_string(input: &str) -> String {
+    format!("\"{}\"", escape_double_quoted(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn java(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_java() {
+        let input = "curl 'https://github.com/a' -H 'Content-Type: application/json' -H 'Host: a.com' -d '{\"a\":1}' -L -m '2.5' --connect-timeout '1' -x 'http://proxy:8080'";
+        let expect = r#"HttpClient client = HttpClient.newBuilder()
+    .followRedirects(HttpClient.Redirect.NORMAL)
+    .connectTimeout(Duration.ofMillis(1000))
+    .proxy(ProxySelector.of(new InetSocketAddress("proxy", 8080)))
+    .build();
+
+HttpRequest request = HttpRequest.newBuilder()
+    .uri(URI.create("https://github.com/a"))
+    .timeout(Duration.ofMillis(2500))
+    .header("Content-Type", "application/json")
+    .method("POST", HttpRequest.BodyPublishers.ofString("{\"a\":1}"))
+    .build();
+
+HttpResponse<String> response = client.send(request, HttpResponse.BodyHandlers.ofString());
+System.out.println(response.body());
+"#;
+        generic_parse(java, input, expect.to_string());
+
+        let input = "curl 'https://github.com/a' -u 'user:pwd' -k -o 'out.html'";
+        let expect = r#"// --insecure: build the client with an SSLContext trusting every certificate
+HttpClient client = HttpClient.newBuilder()
+    .build();
+
+HttpRequest request = HttpRequest.newBuilder()
+    .uri(URI.create("https://github.com/a"))
+    .header("Authorization", "Basic dXNlcjpwd2Q=")
+    .GET()
+    .build();
+
+HttpResponse<Path> response = client.send(request, HttpResponse.BodyHandlers.ofFile(Path.of("out.html")));
+"#;
+        generic_parse(java, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_java_explicit_authorization() {
+        let code = java("curl 'https://github.com/a' -u 'user:pwd' -H 'Authorization: Bearer abc'");
+        assert_eq!(
+            1,
+            code.matches(".header(\"Authorization\"").count(),
+            "{}",
+            code
+        );
+        assert!(code.contains(".header(\"Authorization\", \"Bearer abc\")"));
+    }
+
+    #[test]
+    fn test_generate_java_multipart() {
+        let input = "curl 'https://github.com/upload' -F 'name=nomcurl' -F 'file=@a.png'";
+        let code = java(input);
+
+        assert!(code.contains("multipart.write(Files.readAllBytes(Path.of(\"a.png\")));\n"));
+        assert!(code.contains("name=\\\"name\\\"\\r\\n\\r\\nnomcurl\\r\\n\".getBytes());\n"));
+        assert!(code.contains(
+            ".header(\"Content-Type\", \"multipart/form-data; boundary=nomcurl-boundary\")"
+        ));
+        assert!(code.contains(
+            ".method(\"POST\", HttpRequest.BodyPublishers.ofByteArray(multipart.toByteArray()))"
+        ));
+    }
+}
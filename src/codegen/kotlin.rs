@@ -0,0 +1,293 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{Body, FormField, RequestParts};
+
+/// Generate a Kotlin OkHttp snippet of the request.
+///
+/// OkHttp follows redirects unless told otherwise, so curl without `-L` maps to
+/// `.followRedirects(false)`. The `Content-Type` header becomes the media type of the body.
+/// OkHttp refuses a body with GET and HEAD, it's left out, and requires one with e.g. POST, an
+/// empty one is sent.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::new();
+
+    let media_type = parts
+        .header("Content-Type")
+        .map(|t| format!("{}.toMediaType()", kotlin_string(t)));
+    let body = match &parts.body {
+        Some(Body::Raw(raw) | Body::Json(raw)) => Some(format!(
+            "{}.toRequestBody({})",
+            kotlin_string(raw),
+            media_type.unwrap_or_default()
+        )),
+        Some(Body::File(path)) => Some(format!(
+            "File({}).asRequestBody({})",
+            kotlin_string(path),
+            media_type.unwrap_or_default()
+        )),
+        Some(Body::Form(fields)) => {
+            let mut form =
+                String::from("MultipartBody.Builder()\n    .setType(MultipartBody.FORM)");
+            for field in fields {
+                match field {
+                    FormField::Text(name, value) => form.push_str(&format!(
+                        "\n    .addFormDataPart({}, {})",
+                        kotlin_string(name),
+                        kotlin_string(value)
+                    )),
+                    FormField::File(name, path) => form.push_str(&format!(
+                        "\n    .addFormDataPart({}, {}, File({}).asRequestBody())",
+                        kotlin_string(name),
+                        kotlin_string(path),
+                        kotlin_string(path)
+                    )),
+                    FormField::Content(name, path) => form.push_str(&format!(
+                        "\n    .addFormDataPart({}, File({}).readText())",
+                        kotlin_string(name),
+                        kotlin_string(path)
+                    )),
+                }
+            }
+            form.push_str("\n    .build()");
+            Some(form)
+        }
+        None => None,
+    };
+    let body = match parts.method {
+        Method::GET | Method::HEAD if body.is_some() => {
+            code.push_str("// OkHttp sends no body with a GET or HEAD request\n");
+            None
+        }
+        _ => body,
+    };
+    if let Some(body) = &body {
+        code.push_str(&format!("val body = {}\n\n", body));
+    }
+
+    if parts.insecure {
+        code.push_str("// --insecure: set an sslSocketFactory with a trust-all X509TrustManager\n");
+    }
+    code.push_str("val client = OkHttpClient.Builder()");
+    if let Some(timeout) = parts.timeout {
+        code.push_str(&format!(
+            "\n    .callTimeout({}, TimeUnit.MILLISECONDS)",
+            (timeout * 1000.0).round()
+        ));
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        code.push_str(&format!(
+            "\n    .connectTimeout({}, TimeUnit.MILLISECONDS)",
+            (timeout * 1000.0).round()
+        ));
+    }
+    if !parts.follow_redirects {
+        code.push_str("\n    .followRedirects(false)");
+    }
+    if parts.insecure {
+        code.push_str("\n    .hostnameVerifier { _, _ -> true }");
+    }
+    if let Some(proxy) = &parts.proxy {
+        let address = proxy.split("://").last().unwrap_or(proxy);
+        let (host, port) = address
+            .rsplit_once(':')
+            .filter(|(_, port)| port.parse::<u16>().is_ok())
+            .unwrap_or((address, "1080"));
+        let kind = match proxy.starts_with("socks") {
+            true => "SOCKS",
+            false => "HTTP",
+        };
+        code.push_str(&format!(
+            "\n    .proxy(Proxy(Proxy.Type.{}, InetSocketAddress({}, {})))",
+            kind,
+            kotlin_string(host),
+            port
+        ));
+    }
+    code.push_str("\n    .build()\n\n");
+
+    code.push_str("val request = Request.Builder()");
+    code.push_str(&format!("\n    .url({})", kotlin_string(&parts.url)));
+    for (name, value) in &parts.headers {
+        // The media type of the body is sent as Content-Type
+        if body.is_some() && name.eq_ignore_ascii_case("Content-Type") {
+            continue;
+        }
+        code.push_str(&format!(
+            "\n    .addHeader({}, {})",
+            kotlin_string(name),
+            kotlin_string(value)
+        ));
+    }
+    match (&parts.basic_auth, &parts.bearer_token) {
+        (Some((name, pwd)), _) => code.push_str(&format!(
+            "\n    .addHeader(\"Authorization\", Credentials.basic({}, {}))",
+            kotlin_string(name),
+            kotlin_string(pwd.as_deref().unwrap_or(""))
+        )),
+        (None, Some(token)) => {
+            let bearer = format!("Bearer {}", token);
+            code.push_str(&format!(
+                "\n    .addHeader(\"Authorization\", {})",
+                kotlin_string(&bearer)
+            ));
+        }
+        (None, None) => {}
+    }
+    // See OkHttp's HttpMethod.requiresRequestBody
+    let requires_body = matches!(
+        parts.method.as_str(),
+        "POST" | "PUT" | "PATCH" | "PROPPATCH" | "REPORT"
+    );
+    let body = match (body.is_some(), requires_body) {
+        (true, _) => Some("body"),
+        (false, true) => Some("ByteArray(0).toRequestBody()"),
+        (false, false) => None,
+    };
+    match (&parts.method, body) {
+        (Method::GET, None) => {}
+        (Method::HEAD, None) => code.push_str("\n    .head()"),
+        (Method::DELETE, None) => code.push_str("\n    .delete()"),
+        (Method::POST, Some(body)) => code.push_str(&format!("\n    .post({})", body)),
+        (Method::PUT, Some(body)) => code.push_str(&format!("\n    .put({})", body)),
+        (Method::PATCH, Some(body)) => code.push_str(&format!("\n    .patch({})", body)),
+        (method, body) => code.push_str(&format!(
+            "\n    .method({}, {})",
+            kotlin_string(method.as_str()),
+            body.unwrap_or("null")
+        )),
+    }
+    code.push_str("\n    .build()\n\n");
+
+    code.push_str("client.newCall(request).execute().use { response ->\n");
+    match &parts.output {
+        Some(output) => code.push_str(&format!(
+            "    File({}).writeBytes(response.body!!.bytes())\n",
+            kotlin_string(output)
+        )),
+        None => code.push_str("    println(response.body!!.string())\n"),
+    }
+    code.push_str("}\n");
+    code
+}
+
+/// A Kotlin string literal, `$` is escaped to avoid string templates.
+fn kotlin_string(input: &str) -> String {
+    let mut result = String::with_capacity(input.len() + 2);
+    result.push('"');
+    for c in input.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '$' => result.push_str("\\$"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn kotlin(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_kotlin() {
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' -H 'X-Price: $5' -d '{\"a\":1}' -u 'user:pwd' -m '10' --connect-timeout '2.5' -L";
+        let expect = r#"val body = "{\"a\":1}".toRequestBody("application/json".toMediaType())
+
+val client = OkHttpClient.Builder()
+    .callTimeout(10000, TimeUnit.MILLISECONDS)
+    .connectTimeout(2500, TimeUnit.MILLISECONDS)
+    .build()
+
+val request = Request.Builder()
+    .url("https://github.com/a")
+    .addHeader("X-Price", "\$5")
+    .addHeader("Authorization", Credentials.basic("user", "pwd"))
+    .put(body)
+    .build()
+
+client.newCall(request).execute().use { response ->
+    println(response.body!!.string())
+}
+"#;
+        generic_parse(kotlin, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_kotlin_body_rules() {
+        let code = kotlin("curl -X POST https://a.com -L");
+        assert!(
+            code.contains("\n    .post(ByteArray(0).toRequestBody())\n"),
+            "{}",
+            code
+        );
+        assert!(!code.contains("val body"));
+
+        let code = kotlin("curl -X REPORT https://a.com -L");
+        assert!(code.contains(".method(\"REPORT\", ByteArray(0).toRequestBody())"));
+
+        let code = kotlin("curl -X GET https://a.com -d x -L");
+        assert!(code.starts_with("// OkHttp sends no body with a GET or HEAD request\n"));
+        assert!(
+            !code.contains("body)") && !code.contains(".method("),
+            "{}",
+            code
+        );
+    }
+
+    #[test]
+    fn test_generate_kotlin_explicit_authorization() {
+        let code =
+            kotlin("curl 'https://github.com/a' -u 'user:pwd' -H 'Authorization: Bearer abc'");
+        assert_eq!(
+            1,
+            code.matches(".addHeader(\"Authorization\"").count(),
+            "{}",
+            code
+        );
+        assert!(code.contains(".addHeader(\"Authorization\", \"Bearer abc\")"));
+        assert!(!code.contains("Credentials.basic"));
+    }
+
+    #[test]
+    fn test_generate_kotlin_multipart() {
+        let input = "curl 'https://github.com/upload' -F 'name=nomcurl' -F 'file=@a.png' -k -x 'socks5://proxy:1080' -o 'out.txt'";
+        let expect = r#"val body = MultipartBody.Builder()
+    .setType(MultipartBody.FORM)
+    .addFormDataPart("name", "nomcurl")
+    .addFormDataPart("file", "a.png", File("a.png").asRequestBody())
+    .build()
+
+// --insecure: set an sslSocketFactory with a trust-all X509TrustManager
+val client = OkHttpClient.Builder()
+    .followRedirects(false)
+    .hostnameVerifier { _, _ -> true }
+    .proxy(Proxy(Proxy.Type.SOCKS, InetSocketAddress("proxy", 1080)))
+    .build()
+
+val request = Request.Builder()
+    .url("https://github.com/upload")
+    .post(body)
+    .build()
+
+client.newCall(request).execute().use { response ->
+    File("out.txt").writeBytes(response.body!!.bytes())
+}
+"#;
+        generic_parse(kotlin, input, expect.to_string());
+
+        let input = "curl 'https://github.com/a' -X 'PURGE'";
+        assert!(kotlin(input).contains("\n    .method(\"PURGE\", null)\n"));
+    }
+}
@@ -0,0 +1,499 @@
+pub mod fetch;
+pub mod har;
+pub mod http_file;
+pub mod httpie;
+pub mod java;
+pub mod kotlin;
+pub mod php;
+pub mod powershell;
+pub mod python;
+pub mod raw_http;
+pub mod reqwest;
+pub mod swift;
+pub mod wget;
+
+use std::{io, str::FromStr, time::Duration};
+
+use crate::curl::{
+    method::Method,
+    request::{split_header, ParsedRequest},
+};
+pub use crate::shell_quote::posix as shell_quote;
+
+/// An export format of a request: a code generator or a serialization of the request.
+///
+/// The targets take no options of their own, the output follows the curl flags, e.g. `-o`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Fetch,
+    Har,
+    HttpFile,
+    Httpie,
+    Java,
+    Kotlin,
+    Php,
+    PhpGuzzle,
+    PowerShell,
+    Python,
+    RawHttp,
+    Reqwest,
+    ReqwestFn,
+    Swift,
+    Wget,
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl Target {
+    pub const ALL: &'static [Target] = &[
+        Target::Fetch,
+        Target::Har,
+        Target::HttpFile,
+        Target::Httpie,
+        Target::Java,
+        Target::Kotlin,
+        Target::Php,
+        Target::PhpGuzzle,
+        Target::PowerShell,
+        Target::Python,
+        Target::RawHttp,
+        Target::Reqwest,
+        Target::ReqwestFn,
+        Target::Swift,
+        Target::Wget,
+        Target::Json,
+        Target::Yaml,
+        Target::Toml,
+    ];
+
+    /// The name given to `convert --to`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Target::Fetch => "fetch",
+            Target::Har => "har",
+            Target::HttpFile => "http-file",
+            Target::Httpie => "httpie",
+            Target::Java => "java",
+            Target::Kotlin => "kotlin",
+            Target::Php => "php",
+            Target::PhpGuzzle => "php-guzzle",
+            Target::PowerShell => "powershell",
+            Target::Python => "python",
+            Target::RawHttp => "raw-http",
+            Target::Reqwest => "reqwest",
+            Target::ReqwestFn => "reqwest-fn",
+            Target::Swift => "swift",
+            Target::Wget => "wget",
+            Target::Json => "json",
+            Target::Yaml => "yaml",
+            Target::Toml => "toml",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Target::Fetch => "JavaScript fetch() snippet",
+            Target::Har => "HAR 1.2 document with the request as its entry",
+            Target::HttpFile => "JetBrains / VS Code REST Client .http file",
+            Target::Httpie => "HTTPie command line",
+            Target::Java => "Java 11+ HttpClient snippet",
+            Target::Kotlin => "Kotlin OkHttp snippet",
+            Target::Php => "PHP snippet with the curl extension",
+            Target::PhpGuzzle => "PHP snippet with a Guzzle client",
+            Target::PowerShell => "PowerShell 7 Invoke-RestMethod call",
+            Target::Python => "Python requests snippet",
+            Target::RawHttp => "The raw HTTP/1.1 request curl would send",
+            Target::Reqwest => "Rust async reqwest snippet",
+            Target::ReqwestFn => "Rust async function sending the request with reqwest",
+            Target::Swift => "Swift URLSession snippet",
+            Target::Wget => "wget command line",
+            Target::Json => "The JSON document of `parse --json`",
+            Target::Yaml => "The YAML document of the request",
+            Target::Toml => "The TOML document of the request",
+        }
+    }
+
+    /// Export the request, only `raw-http` and `har` read files, the `@file` payloads. A request
+    /// the target can't be written in is an `InvalidData` error.
+    pub fn generate(self, request: &ParsedRequest) -> io::Result<Vec<u8>> {
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+        let code = match self {
+            Target::Fetch => fetch::generate(request),
+            Target::Har => har::generate(request)?,
+            Target::HttpFile => http_file::generate(request),
+            Target::Httpie => httpie::generate(request),
+            Target::Java => java::generate(request),
+            Target::Kotlin => kotlin::generate(request),
+            Target::Php => php::generate_curl(request),
+            Target::PhpGuzzle => php::generate_guzzle(request),
+            Target::PowerShell => powershell::generate(request),
+            Target::Python => python::generate(request),
+            Target::RawHttp => return raw_http::generate(request),
+            Target::Reqwest => reqwest::generate(request),
+            Target::ReqwestFn => reqwest::generate_fn(request),
+            Target::Swift => swift::generate(request),
+            Target::Wget => wget::generate(request),
+            Target::Json => request.to_json()? + "\n",
+            Target::Yaml => request.to_yaml().map_err(|e| invalid(e.to_string()))?,
+            Target::Toml => request.to_toml().map_err(|e| invalid(e.to_string()))?,
+        };
+        Ok(code.into_bytes())
+    }
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Target::ALL
+            .iter()
+            .copied()
+            .find(|t| t.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown target {:?}, see --list-targets", s))
+    }
+}
+
+/// A field of a `-F` multipart form.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormField {
+    Text(String, String),
+    /// `name=@path`, the file is uploaded.
+    File(String, String),
+    /// `name=<path`, the file content is sent as a text field, with no file name.
+    Content(String, String),
+}
+
+impl FormField {
+    pub fn new(form: &str) -> Self {
+        let (name, value) = form.split_once('=').unwrap_or((form, ""));
+        // Drop the `;type=...` / `;filename=...` attributes of the file
+        let path = |path: &str| path.split(';').next().unwrap_or("").to_string();
+        if let Some(file) = value.strip_prefix('@') {
+            Self::File(name.into(), path(file))
+        } else if let Some(file) = value.strip_prefix('<') {
+            Self::Content(name.into(), path(file))
+        } else {
+            Self::Text(name.into(), value.into())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body {
+    /// The `-d` payloads, joined by `&` as curl does.
+    Raw(String),
+    /// A payload sent with a JSON `Content-Type` or by `--json`.
+    Json(String),
+    /// `-d @path`, the file content is sent.
+    File(String),
+    Form(Vec<FormField>),
+}
+
+/// The HTTP view of a `ParsedRequest` shared by the code generators.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestParts {
+    pub method: Method,
+    pub url: String,
+    /// The `-H` headers plus the ones implied by `-A`, `-e`, `-b`, `--json` and the body, a
+    /// `Name;` one has an empty value.
+    pub headers: Vec<(String, String)>,
+    /// The names of the `-H 'Name:'` headers, which remove the ones curl would add.
+    pub removed_headers: Vec<String>,
+    pub body: Option<Body>,
+    /// `-u user:passwd`, the password may be omitted. Like `bearer_token`, it's `None` next to an
+    /// explicit `-H 'Authorization: ...'`, which replaces the header of the credentials.
+    pub basic_auth: Option<(String, Option<String>)>,
+    pub bearer_token: Option<String>,
+    pub insecure: bool,
+    pub follow_redirects: bool,
+    pub max_redirects: Option<u32>,
+    /// `--max-time` in seconds, a valid `Duration`: not negative, NaN or infinite.
+    pub timeout: Option<f64>,
+    /// `--connect-timeout` in seconds, a valid `Duration` too.
+    pub connect_timeout: Option<f64>,
+    pub proxy: Option<String>,
+    pub output: Option<String>,
+}
+
+impl RequestParts {
+    pub fn new(request: &ParsedRequest) -> Self {
+        let flag_value = |name: &str| {
+            request
+                .flag(name)
+                .and_then(|f| f.data.as_deref().map(String::from))
+        };
+        let seconds = |name: &str| {
+            flag_value(name)
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|s| Duration::try_from_secs_f64(*s).is_ok())
+        };
+
+        let mut headers: Vec<(String, String)> = Vec::new();
+        let mut removed_headers: Vec<String> = Vec::new();
+        for header in request.headers() {
+            let Some((name, value)) = split_header(header) else {
+                continue;
+            };
+            let (name, value) = (name.trim().to_string(), value.trim().to_string());
+            match header.contains(':') && value.is_empty() {
+                true => removed_headers.push(name),
+                false => headers.push((name, value)),
+            }
+        }
+
+        let mut implied_header = |name: &str, value: Option<String>| {
+            let exists = headers
+                .iter()
+                .map(|(n, _)| n)
+                .chain(&removed_headers)
+                .any(|n| n.eq_ignore_ascii_case(name));
+            if let (false, Some(value)) = (exists, value) {
+                headers.push((name.into(), value));
+            }
+        };
+
+        implied_header("User-Agent", flag_value("--user-agent"));
+        implied_header("Referer", flag_value("--referer"));
+        // `-b name=value` is a cookie string, otherwise it's a cookie file to read
+        implied_header("Cookie", flag_value("--cookie").filter(|c| c.contains('=')));
+
+        let forms: Vec<FormField> = request
+            .flags()
+            .filter(|f| crate::curl::options::is_option(&f.identifier, "--form"))
+            .filter_map(|f| f.data.as_deref().map(FormField::new))
+            .collect();
+        let data = request.payloads();
+
+        let body = if !forms.is_empty() {
+            Some(Body::Form(forms))
+        } else if data.is_empty() || request.has_flag("--get") {
+            None
+        } else if request.has_flag("--json") {
+            implied_header("Content-Type", Some("application/json".into()));
+            implied_header("Accept", Some("application/json".into()));
+            Some(Body::Json(data.join("")))
+        } else {
+            implied_header(
+                "Content-Type",
+                Some("application/x-www-form-urlencoded".into()),
+            );
+            match data.as_slice() {
+                [file] if file.starts_with('@') && request.flag("--data-raw").is_none() => {
+                    Some(Body::File(file[1..].into()))
+                }
+                _ => Some(Body::Raw(data.join("&"))),
+            }
+        };
+
+        let is_json = headers
+            .iter()
+            .any(|(n, v)| n.eq_ignore_ascii_case("Content-Type") && v.contains("json"));
+        let body = match body {
+            Some(Body::Raw(raw)) if is_json => Some(Body::Json(raw)),
+            body => body,
+        };
+
+        let explicit_authorization = headers
+            .iter()
+            .any(|(n, _)| n.eq_ignore_ascii_case("Authorization"));
+        let credential = |name| flag_value(name).filter(|_| !explicit_authorization);
+        let basic_auth = credential("--user").map(|user| match user.split_once(':') {
+            Some((name, pwd)) => (name.to_string(), Some(pwd.to_string())),
+            None => (user, None),
+        });
+
+        Self {
+            method: request.effective_method(),
+            url: request.effective_url(),
+            headers,
+            removed_headers,
+            body,
+            basic_auth,
+            bearer_token: credential("--oauth2-bearer"),
+            insecure: request.has_flag("--insecure"),
+            follow_redirects: request.has_flag("--location"),
+            max_redirects: flag_value("--max-redirs").and_then(|v| v.parse().ok()),
+            timeout: seconds("--max-time"),
+            connect_timeout: seconds("--connect-timeout"),
+            proxy: flag_value("--proxy"),
+            output: flag_value("--output"),
+        }
+    }
+
+    /// The value of the first header with the given name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The `Authorization` value of `-u` / `--oauth2-bearer`, `None` next to an explicit header.
+    pub fn authorization(&self) -> Option<String> {
+        if let Some((name, pwd)) = &self.basic_auth {
+            let credentials = format!("{}:{}", name, pwd.as_deref().unwrap_or(""));
+            return Some(format!("Basic {}", base64_encode(credentials.as_bytes())));
+        }
+        self.bearer_token.as_ref().map(|t| format!("Bearer {}", t))
+    }
+}
+
+/// Whether the payload is valid JSON, e.g. not a templated `{"id": {{id}}}` one, and so can be
+/// written as a JSON literal of the target.
+pub fn is_json(input: &str) -> bool {
+    serde_json::from_str::<serde::de::IgnoredAny>(input).is_ok()
+}
+
+/// Escape the string the way C-like languages (and JSON) expect inside double quotes.
+pub fn escape_double_quoted(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Standard base64 with padding.
+pub fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    #[test]
+    fn test_target() {
+        let request = parse_curl_command("curl 'https://github.com' -d 'a=1'").unwrap();
+        for target in Target::ALL {
+            assert_eq!(Ok(*target), target.name().parse());
+            assert!(!target.generate(&request).unwrap().is_empty());
+        }
+
+        let wget = Target::Wget.generate(&request).unwrap();
+        assert_eq!(wget::generate(&request).into_bytes(), wget);
+        assert!("python2".parse::<Target>().is_err());
+    }
+
+    #[test]
+    fn test_request_parts() {
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' -d '{\"a\":1}' -u 'user:pwd' -A 'nomcurl' -b 'a=1' -k -L -m '2.5'";
+        let parts = RequestParts::new(&parse_curl_command(input).unwrap());
+
+        assert_eq!(Method::PUT, parts.method);
+        assert_eq!("https://github.com/a", parts.url);
+        assert_eq!(Some("nomcurl"), parts.header("user-agent"));
+        assert_eq!(Some("a=1"), parts.header("Cookie"));
+        assert_eq!(Some(Body::Json("{\"a\":1}".into())), parts.body);
+        assert_eq!(Some(("user".into(), Some("pwd".into()))), parts.basic_auth);
+        assert_eq!(Some("Basic dXNlcjpwd2Q=".into()), parts.authorization());
+        assert!(parts.insecure && parts.follow_redirects);
+        assert_eq!(Some(2.5), parts.timeout);
+
+        let input =
+            "curl 'https://a.com' -u 'user:pwd' --oauth2-bearer 't' -H 'authorization: Token x'";
+        let parts = RequestParts::new(&parse_curl_command(input).unwrap());
+        assert!(parts.basic_auth.is_none() && parts.bearer_token.is_none());
+        assert_eq!(None, parts.authorization());
+
+        let input = "curl 'https://github.com' -F 'a=1' -F 'f=@a.png;type=image/png' -F 't=<a.txt'";
+        let parts = RequestParts::new(&parse_curl_command(input).unwrap());
+        let expect = Body::Form(vec![
+            FormField::Text("a".into(), "1".into()),
+            FormField::File("f".into(), "a.png".into()),
+            FormField::Content("t".into(), "a.txt".into()),
+        ]);
+        assert_eq!(Some(expect), parts.body);
+        assert_eq!(Method::POST, parts.method);
+
+        let input = "curl 'https://github.com' -d 'a=1' -d 'b=2'";
+        let parts = RequestParts::new(&parse_curl_command(input).unwrap());
+        assert_eq!(Some(Body::Raw("a=1&b=2".into())), parts.body);
+        assert_eq!(
+            Some("application/x-www-form-urlencoded"),
+            parts.header("Content-Type")
+        );
+
+        let input =
+            "curl 'https://github.com' -H 'Content-Type:' -H 'X-Empty;' --data-urlencode 'a=b c'";
+        let parts = RequestParts::new(&parse_curl_command(input).unwrap());
+        assert_eq!(vec!["Content-Type".to_string()], parts.removed_headers);
+        assert_eq!(None, parts.header("Content-Type"));
+        assert_eq!(Some(""), parts.header("X-Empty"));
+        assert_eq!(Some(Body::Raw("a=b%20c".into())), parts.body);
+
+        // curl rejects them, a Duration can't hold them
+        for timeout in ["-1", "nan", "inf", "1e300"] {
+            let input = format!(
+                "curl 'https://github.com' -m '{0}' --connect-timeout '{0}'",
+                timeout
+            );
+            let parts = RequestParts::new(&parse_curl_command(&input).unwrap());
+            assert_eq!(
+                (None, None),
+                (parts.timeout, parts.connect_timeout),
+                "{}",
+                timeout
+            );
+        }
+    }
+
+    #[test]
+    fn test_escape_double_quoted() {
+        generic_parse(
+            escape_double_quoted,
+            "a\"b\\c\nd\u{1}",
+            "a\\\"b\\\\c\\nd\\u0001".to_string(),
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        generic_parse(
+            shell_quote,
+            "https://a.com/b",
+            "https://a.com/b".to_string(),
+        );
+        generic_parse(shell_quote, "", "''".to_string());
+        generic_parse(shell_quote, "a b", "'a b'".to_string());
+        generic_parse(shell_quote, "it's", r"'it'\''s'".to_string());
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        generic_parse(base64_encode, b"".as_slice(), "".to_string());
+        generic_parse(base64_encode, b"f".as_slice(), "Zg==".to_string());
+        generic_parse(base64_encode, b"fo".as_slice(), "Zm8=".to_string());
+        generic_parse(base64_encode, b"foo".as_slice(), "Zm9v".to_string());
+        generic_parse(base64_encode, b"foobar".as_slice(), "Zm9vYmFy".to_string());
+    }
+}
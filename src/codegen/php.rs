@@ -0,0 +1,290 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{Body, FormField, RequestParts};
+
+/// Generate a PHP snippet of the request with the `curl_setopt` calls of the curl extension.
+pub fn generate_curl(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::from("<?php\n$ch = curl_init();\n");
+    let mut setopt = |option: &str, value: String| {
+        code.push_str(&format!("curl_setopt($ch, {}, {});\n", option, value));
+    };
+
+    setopt("CURLOPT_URL", php_string(&parts.url));
+    setopt("CURLOPT_RETURNTRANSFER", "true".into());
+    if parts.method != Method::GET {
+        setopt("CURLOPT_CUSTOMREQUEST", php_string(parts.method.as_str()));
+    }
+
+    let mut headers: Vec<String> = headers(&parts)
+        .iter()
+        .map(|(n, v)| format!("    {},\n", php_string(&format!("{}: {}", n, v))))
+        .collect();
+    if let Some(token) = &parts.bearer_token {
+        let authorization = format!("Authorization: Bearer {}", token);
+        headers.push(format!("    {},\n", php_string(&authorization)));
+    }
+    if !headers.is_empty() {
+        setopt("CURLOPT_HTTPHEADER", format!("[\n{}]", headers.concat()));
+    }
+
+    match &parts.body {
+        Some(Body::Raw(raw) | Body::Json(raw)) => setopt("CURLOPT_POSTFIELDS", php_string(raw)),
+        Some(Body::File(path)) => setopt(
+            "CURLOPT_POSTFIELDS",
+            format!("file_get_contents({})", php_string(path)),
+        ),
+        Some(Body::Form(fields)) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| match field {
+                    FormField::Text(name, value) => {
+                        format!("    {} => {},\n", php_string(name), php_string(value))
+                    }
+                    FormField::File(name, path) => format!(
+                        "    {} => new CURLFile({}),\n",
+                        php_string(name),
+                        php_string(path)
+                    ),
+                    FormField::Content(name, path) => format!(
+                        "    {} => file_get_contents({}),\n",
+                        php_string(name),
+                        php_string(path)
+                    ),
+                })
+                .collect();
+            setopt("CURLOPT_POSTFIELDS", format!("[\n{}]", fields.concat()));
+        }
+        None => {}
+    }
+
+    if let Some((name, pwd)) = &parts.basic_auth {
+        let userpwd = format!("{}:{}", name, pwd.as_deref().unwrap_or(""));
+        setopt("CURLOPT_USERPWD", php_string(&userpwd));
+    }
+    if parts.insecure {
+        setopt("CURLOPT_SSL_VERIFYPEER", "false".into());
+        setopt("CURLOPT_SSL_VERIFYHOST", "0".into());
+    }
+    if parts.follow_redirects {
+        setopt("CURLOPT_FOLLOWLOCATION", "true".into());
+        if let Some(max) = parts.max_redirects {
+            setopt("CURLOPT_MAXREDIRS", max.to_string());
+        }
+    }
+    if let Some(timeout) = parts.timeout {
+        match timeout.fract() == 0.0 {
+            true => setopt("CURLOPT_TIMEOUT", timeout.to_string()),
+            false => setopt("CURLOPT_TIMEOUT_MS", (timeout * 1000.0).round().to_string()),
+        }
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        match timeout.fract() == 0.0 {
+            true => setopt("CURLOPT_CONNECTTIMEOUT", timeout.to_string()),
+            false => setopt(
+                "CURLOPT_CONNECTTIMEOUT_MS",
+                (timeout * 1000.0).round().to_string(),
+            ),
+        }
+    }
+    if let Some(proxy) = &parts.proxy {
+        setopt("CURLOPT_PROXY", php_string(proxy));
+    }
+
+    code.push_str("\n$response = curl_exec($ch);\ncurl_close($ch);\n");
+    match &parts.output {
+        Some(output) => code.push_str(&format!(
+            "file_put_contents({}, $response);\n",
+            php_string(output)
+        )),
+        None => code.push_str("echo $response;\n"),
+    }
+    code
+}
+
+/// Generate a PHP snippet of the request with a Guzzle client.
+pub fn generate_guzzle(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut options: Vec<String> = Vec::new();
+
+    let mut headers: Vec<String> = headers(&parts)
+        .iter()
+        .map(|(n, v)| format!("        {} => {},\n", php_string(n), php_string(v)))
+        .collect();
+    if let Some(token) = &parts.bearer_token {
+        let bearer = format!("Bearer {}", token);
+        headers.push(format!(
+            "        'Authorization' => {},\n",
+            php_string(&bearer)
+        ));
+    }
+    if !headers.is_empty() {
+        options.push(format!("'headers' => [\n{}    ]", headers.concat()));
+    }
+
+    match &parts.body {
+        Some(Body::Raw(raw) | Body::Json(raw)) => {
+            options.push(format!("'body' => {}", php_string(raw)))
+        }
+        Some(Body::File(path)) => {
+            options.push(format!("'body' => fopen({}, 'r')", php_string(path)))
+        }
+        Some(Body::Form(fields)) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| match field {
+                    FormField::Text(name, value) => format!(
+                        "        ['name' => {}, 'contents' => {}],\n",
+                        php_string(name),
+                        php_string(value)
+                    ),
+                    FormField::File(name, path) => format!(
+                        "        ['name' => {}, 'contents' => fopen({}, 'r'), 'filename' => {}],\n",
+                        php_string(name),
+                        php_string(path),
+                        php_string(path)
+                    ),
+                    FormField::Content(name, path) => format!(
+                        "        ['name' => {}, 'contents' => file_get_contents({})],\n",
+                        php_string(name),
+                        php_string(path)
+                    ),
+                })
+                .collect();
+            options.push(format!("'multipart' => [\n{}    ]", fields.concat()));
+        }
+        None => {}
+    }
+
+    if let Some((name, pwd)) = &parts.basic_auth {
+        options.push(format!(
+            "'auth' => [{}, {}]",
+            php_string(name),
+            php_string(pwd.as_deref().unwrap_or(""))
+        ));
+    }
+    if parts.insecure {
+        options.push("'verify' => false".into());
+    }
+    // Guzzle follows redirects by default, curl only does with -L
+    match (parts.follow_redirects, parts.max_redirects) {
+        (false, _) => options.push("'allow_redirects' => false".into()),
+        (true, Some(max)) => options.push(format!("'allow_redirects' => ['max' => {}]", max)),
+        (true, None) => {}
+    }
+    if let Some(timeout) = parts.timeout {
+        options.push(format!("'timeout' => {}", timeout));
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        options.push(format!("'connect_timeout' => {}", timeout));
+    }
+    if let Some(proxy) = &parts.proxy {
+        options.push(format!("'proxy' => {}", php_string(proxy)));
+    }
+    if let Some(output) = &parts.output {
+        options.push(format!("'sink' => {}", php_string(output)));
+    }
+
+    let mut code = String::from("<?php\n$client = new \\GuzzleHttp\\Client();\n");
+    code.push_str(&format!(
+        "$response = $client->request({}, {}",
+        php_string(parts.method.as_str()),
+        php_string(&parts.url)
+    ));
+    if !options.is_empty() {
+        let options: Vec<String> = options.iter().map(|o| format!("    {},\n", o)).collect();
+        code.push_str(&format!(", [\n{}]", options.concat()));
+    }
+    code.push_str(");\n");
+    if parts.output.is_none() {
+        code.push_str("echo $response->getBody();\n");
+    }
+    code
+}
+
+/// The headers to send, the multipart boundary is set by PHP itself.
+fn headers(parts: &RequestParts) -> Vec<(String, String)> {
+    let is_form = matches!(parts.body, Some(Body::Form(_)));
+    parts
+        .headers
+        .iter()
+        .filter(|(n, _)| !(is_form && n.eq_ignore_ascii_case("Content-Type")))
+        .cloned()
+        .collect()
+}
+
+/// A single-quoted PHP string, only `\` and `'` need escaping.
+fn php_string(input: &str) -> String {
+    format!("'{}'", input.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    const TEST_CURL_CMD: &str = "curl 'https://github.com/a' -X 'POST' -H 'Accept: */*' -d 'name=O\"Brien' -u 'user:pwd' -k -m '2.5'";
+
+    #[test]
+    fn test_generate_curl() {
+        let expect = r#"<?php
+$ch = curl_init();
+curl_setopt($ch, CURLOPT_URL, 'https://github.com/a');
+curl_setopt($ch, CURLOPT_RETURNTRANSFER, true);
+curl_setopt($ch, CURLOPT_CUSTOMREQUEST, 'POST');
+curl_setopt($ch, CURLOPT_HTTPHEADER, [
+    'Accept: */*',
+    'Content-Type: application/x-www-form-urlencoded',
+]);
+curl_setopt($ch, CURLOPT_POSTFIELDS, 'name=O"Brien');
+curl_setopt($ch, CURLOPT_USERPWD, 'user:pwd');
+curl_setopt($ch, CURLOPT_SSL_VERIFYPEER, false);
+curl_setopt($ch, CURLOPT_SSL_VERIFYHOST, 0);
+curl_setopt($ch, CURLOPT_TIMEOUT_MS, 2500);
+
+$response = curl_exec($ch);
+curl_close($ch);
+echo $response;
+"#;
+        let generate = |input| generate_curl(&parse_curl_command(input).unwrap());
+        generic_parse(generate, TEST_CURL_CMD, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_guzzle() {
+        let expect = r#"<?php
+$client = new \GuzzleHttp\Client();
+$response = $client->request('POST', 'https://github.com/a', [
+    'headers' => [
+        'Accept' => '*/*',
+        'Content-Type' => 'application/x-www-form-urlencoded',
+    ],
+    'body' => 'name=O"Brien',
+    'auth' => ['user', 'pwd'],
+    'verify' => false,
+    'allow_redirects' => false,
+    'timeout' => 2.5,
+]);
+echo $response->getBody();
+"#;
+        let generate = |input| generate_guzzle(&parse_curl_command(input).unwrap());
+        generic_parse(generate, TEST_CURL_CMD, expect.to_string());
+
+        let input = "curl 'https://github.com/upload' -F 'file=@a.png' -L -o 'out.txt'";
+        let expect = r#"<?php
+$client = new \GuzzleHttp\Client();
+$response = $client->request('POST', 'https://github.com/upload', [
+    'multipart' => [
+        ['name' => 'file', 'contents' => fopen('a.png', 'r'), 'filename' => 'a.png'],
+    ],
+    'sink' => 'out.txt',
+]);
+"#;
+        generic_parse(generate, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_php_string() {
+        generic_parse(php_string, r"it's C:\tmp", r"'it\'s C:\\tmp'".to_string());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+use crate::shell_quote::powershell_quoted as quote;
+
+use super::{Body, FormField, RequestParts};
+
+/// Generate a PowerShell 7 `Invoke-RestMethod` call of the request.
+///
+/// `Content-Type` and `User-Agent` go to `-ContentType` and `-UserAgent`, Windows PowerShell
+/// rejects them in `-Headers`. The credentials of `-u` and `--oauth2-bearer` are sent as an
+/// `Authorization` header, up front as curl does.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::new();
+    let mut args: Vec<String> = vec![format!("-Uri {}", quote(&parts.url))];
+
+    match &parts.method {
+        Method::GET => {}
+        Method::CONNECT | Method::Custom(_) => {
+            args.push(format!("-CustomMethod {}", quote(parts.method.as_str())))
+        }
+        method => {
+            let name = method.as_str();
+            args.push(format!(
+                "-Method {}{}",
+                &name[..1],
+                name[1..].to_lowercase()
+            ));
+        }
+    }
+
+    let mut headers: Vec<(&str, String)> = Vec::new();
+    for (name, value) in &parts.headers {
+        if name.eq_ignore_ascii_case("Content-Type") {
+            args.push(format!("-ContentType {}", quote(value)));
+        } else if name.eq_ignore_ascii_case("User-Agent") {
+            args.push(format!("-UserAgent {}", quote(value)));
+        } else {
+            headers.push((name, value.clone()));
+        }
+    }
+    if let Some(authorization) = parts.authorization() {
+        headers.push(("Authorization", authorization));
+    }
+    if !headers.is_empty() {
+        code.push_str("$headers = @{\n");
+        for (name, value) in &headers {
+            code.push_str(&format!("    {} = {}\n", quote(name), quote(value)));
+        }
+        code.push_str("}\n");
+        args.push("-Headers $headers".into());
+    }
+
+    match &parts.body {
+        Some(Body::Raw(body) | Body::Json(body)) => {
+            code.push_str(&format!("$body = {}\n", quote(body)));
+            args.push("-Body $body".into());
+        }
+        Some(Body::File(path)) => args.push(format!("-InFile {}", quote(path))),
+        Some(Body::Form(fields)) => {
+            code.push_str("$form = @{\n");
+            for field in fields {
+                match field {
+                    FormField::Text(name, value) => {
+                        code.push_str(&format!("    {} = {}\n", quote(name), quote(value)))
+                    }
+                    FormField::File(name, path) => code.push_str(&format!(
+                        "    {} = Get-Item -Path {}\n",
+                        quote(name),
+                        quote(path)
+                    )),
+                    FormField::Content(name, path) => code.push_str(&format!(
+                        "    {} = Get-Content -Raw -Path {}\n",
+                        quote(name),
+                        quote(path)
+                    )),
+                }
+            }
+            code.push_str("}\n");
+            args.push("-Form $form".into());
+        }
+        None => {}
+    }
+
+    if parts.insecure {
+        args.push("-SkipCertificateCheck".into());
+    }
+    // Invoke-RestMethod follows up to 5 redirects by default, curl none without -L
+    match (parts.follow_redirects, parts.max_redirects) {
+        (false, _) => args.push("-MaximumRedirection 0".into()),
+        (true, Some(max)) => args.push(format!("-MaximumRedirection {}", max)),
+        (true, None) => {}
+    }
+    if let Some(timeout) = parts.timeout {
+        // Whole seconds, 0 would mean no timeout
+        args.push(format!("-TimeoutSec {}", timeout.ceil().max(1.0)));
+    }
+    if let Some(proxy) = &parts.proxy {
+        args.push(format!("-Proxy {}", quote(proxy)));
+    }
+    if let Some(output) = &parts.output {
+        args.push(format!("-OutFile {}", quote(output)));
+    }
+
+    if !code.is_empty() {
+        code.push('\n');
+    }
+    code.push_str("Invoke-RestMethod ");
+    code.push_str(&args.join(" "));
+    code.push('\n');
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn powershell(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_powershell() {
+        generic_parse(
+            powershell,
+            "curl 'https://github.com/a' -L",
+            "Invoke-RestMethod -Uri 'https://github.com/a'\n".to_string(),
+        );
+
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' -H 'X-Note: it'\\''s' -A 'nomcurl' -d '{\"a\":1}' -u 'user:pwd' -k -m '2.5' -o 'a.json'";
+        let expect = r#"$headers = @{
+    'X-Note' = 'it''s'
+    'Authorization' = 'Basic dXNlcjpwd2Q='
+}
+$body = '{"a":1}'
+
+Invoke-RestMethod -Uri 'https://github.com/a' -Method Put -ContentType 'application/json' -UserAgent 'nomcurl' -Headers $headers -Body $body -SkipCertificateCheck -MaximumRedirection 0 -TimeoutSec 3 -OutFile 'a.json'
+"#;
+        generic_parse(powershell, input, expect.to_string());
+
+        let input = "curl 'https://github.com/a' -X 'PROPFIND' -d '@body.xml' -L --max-redirs '3' -x 'http://proxy:8080'";
+        let expect = "Invoke-RestMethod -Uri 'https://github.com/a' -CustomMethod 'PROPFIND' -ContentType 'application/x-www-form-urlencoded' -InFile 'body.xml' -MaximumRedirection 3 -Proxy 'http://proxy:8080'\n";
+        generic_parse(powershell, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_powershell_form() {
+        let input = "curl 'https://github.com/upload' -F 'name=nomcurl' -F 'file=@a.png' -L";
+        let expect = r#"$form = @{
+    'name' = 'nomcurl'
+    'file' = Get-Item -Path 'a.png'
+}
+
+Invoke-RestMethod -Uri 'https://github.com/upload' -Method Post -Form $form
+"#;
+        generic_parse(powershell, input, expect.to_string());
+    }
+}
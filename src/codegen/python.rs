@@ -0,0 +1,206 @@
+use crate::curl::request::ParsedRequest;
+
+use super::{escape_double_quoted, Body, FormField, RequestParts};
+
+/// Generate a Python `requests` snippet of the request.
+///
+/// `-F` fields become `files=`, text and `<file` ones as `(None, ...)` tuples so they stay
+/// multipart fields with no file name.
+/// requests follows redirects unless told otherwise, curl only with `-L`.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::from("import requests\n\n");
+    let mut args: Vec<String> = vec![py_string(parts.method.as_str()), py_string(&parts.url)];
+
+    let is_form = matches!(parts.body, Some(Body::Form(_)));
+    let headers: Vec<String> = parts
+        .headers
+        .iter()
+        // The multipart boundary is set by requests itself
+        .filter(|(n, _)| !(is_form && n.eq_ignore_ascii_case("Content-Type")))
+        .map(|(n, v)| format!("    {}: {},\n", py_string(n), py_string(v)))
+        .collect();
+    let bearer = parts.bearer_token.as_ref().map(|token| {
+        format!(
+            "    \"Authorization\": \"Bearer {}\",\n",
+            escape_double_quoted(token)
+        )
+    });
+    if !headers.is_empty() || bearer.is_some() {
+        code.push_str("headers = {\n");
+        code.extend(headers);
+        code.extend(bearer);
+        code.push_str("}\n");
+        args.push("headers=headers".into());
+    }
+
+    match &parts.body {
+        Some(Body::Raw(data) | Body::Json(data)) => {
+            code.push_str(&format!("data = {}\n", py_string(data)));
+            args.push("data=data".into());
+        }
+        Some(Body::File(path)) => {
+            code.push_str(&format!("data = open({}, \"rb\")\n", py_string(path)));
+            args.push("data=data".into());
+        }
+        Some(Body::Form(fields)) => {
+            code.push_str("files = {\n");
+            for field in fields {
+                let (name, value) = match field {
+                    FormField::Text(name, value) => (name, format!("(None, {})", py_string(value))),
+                    FormField::File(name, path) => {
+                        (name, format!("open({}, \"rb\")", py_string(path)))
+                    }
+                    FormField::Content(name, path) => {
+                        (name, format!("(None, open({}, \"rb\"))", py_string(path)))
+                    }
+                };
+                code.push_str(&format!("    {}: {},\n", py_string(name), value));
+            }
+            code.push_str("}\n");
+            args.push("files=files".into());
+        }
+        None => {}
+    }
+
+    if let Some((name, pwd)) = &parts.basic_auth {
+        let pwd = pwd.as_deref().unwrap_or_default();
+        args.push(format!("auth=({}, {})", py_string(name), py_string(pwd)));
+    }
+    if parts.insecure {
+        args.push("verify=False".into());
+    }
+    if !parts.follow_redirects {
+        args.push("allow_redirects=False".into());
+    }
+    // requests has no total timeout, a tuple is the connect and read ones
+    match (parts.connect_timeout, parts.timeout) {
+        (Some(connect), timeout) => args.push(format!(
+            "timeout=({}, {})",
+            connect,
+            timeout.map_or("None".into(), |t| t.to_string())
+        )),
+        (None, Some(timeout)) => args.push(format!("timeout={}", timeout)),
+        (None, None) => {}
+    }
+    if let Some(proxy) = &parts.proxy {
+        let proxy = py_string(proxy);
+        args.push(format!(
+            "proxies={{\"http\": {}, \"https\": {}}}",
+            proxy, proxy
+        ));
+    }
+
+    if !code.ends_with("\n\n") {
+        code.push('\n');
+    }
+    code.push_str(&format!(
+        "response = requests.request(\n    {},\n)\n",
+        args.join(",\n    ")
+    ));
+    match &parts.output {
+        Some(output) => code.push_str(&format!(
+            "with open({}, \"wb\") as f:\n    f.write(response.content)\n",
+            py_string(output)
+        )),
+        None => code.push_str("print(response.text)\n"),
+    }
+    code
+}
+
+fn py_string(input: &str) -> String {
+    format!("\"{}\"", escape_double_quoted(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn python(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_python() {
+        let expect = r#"import requests
+
+response = requests.request(
+    "GET",
+    "https://github.com/a",
+)
+print(response.text)
+"#;
+        generic_parse(python, "curl 'https://github.com/a' -L", expect.to_string());
+
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' -d '{\"a\":\"b\"}' -u 'user:pwd' -k -m '2.5' -x 'http://proxy:8080' -o 'a.json'";
+        let expect = r#"import requests
+
+headers = {
+    "Content-Type": "application/json",
+}
+data = "{\"a\":\"b\"}"
+
+response = requests.request(
+    "PUT",
+    "https://github.com/a",
+    headers=headers,
+    data=data,
+    auth=("user", "pwd"),
+    verify=False,
+    allow_redirects=False,
+    timeout=2.5,
+    proxies={"http": "http://proxy:8080", "https": "http://proxy:8080"},
+)
+with open("a.json", "wb") as f:
+    f.write(response.content)
+"#;
+        generic_parse(python, input, expect.to_string());
+
+        let input = "curl 'https://github.com/a' --oauth2-bearer 'abc' --connect-timeout '3' -L";
+        let expect = r#"import requests
+
+headers = {
+    "Authorization": "Bearer abc",
+}
+
+response = requests.request(
+    "GET",
+    "https://github.com/a",
+    headers=headers,
+    timeout=(3, None),
+)
+print(response.text)
+"#;
+        generic_parse(python, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_python_explicit_authorization() {
+        let code =
+            python("curl 'https://github.com/a' -u 'user:pwd' -H 'Authorization: Bearer abc'");
+        assert!(code.contains("    \"Authorization\": \"Bearer abc\",\n"));
+        assert!(!code.contains("auth=("), "{}", code);
+    }
+
+    #[test]
+    fn test_generate_python_form() {
+        let input = "curl 'https://github.com/upload' -F 'name=nom\"curl' -F 'file=@a.png' -F 'note=<a.txt' -L";
+        let expect = r#"import requests
+
+files = {
+    "name": (None, "nom\"curl"),
+    "file": open("a.png", "rb"),
+    "note": (None, open("a.txt", "rb")),
+}
+
+response = requests.request(
+    "POST",
+    "https://github.com/upload",
+    files=files,
+)
+print(response.text)
+"#;
+        generic_parse(python, input, expect.to_string());
+    }
+}
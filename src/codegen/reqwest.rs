@@ -0,0 +1,311 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{is_json, Body, FormField, RequestParts};
+
+/// The `reqwest::header` constants of the standard request headers.
+const HEADER_CONSTANTS: [&str; 22] = [
+    "ACCEPT",
+    "ACCEPT_CHARSET",
+    "ACCEPT_ENCODING",
+    "ACCEPT_LANGUAGE",
+    "AUTHORIZATION",
+    "CACHE_CONTROL",
+    "CONNECTION",
+    "CONTENT_ENCODING",
+    "CONTENT_LENGTH",
+    "CONTENT_TYPE",
+    "COOKIE",
+    "DNT",
+    "HOST",
+    "IF_MATCH",
+    "IF_MODIFIED_SINCE",
+    "IF_NONE_MATCH",
+    "ORIGIN",
+    "PRAGMA",
+    "RANGE",
+    "REFERER",
+    "UPGRADE_INSECURE_REQUESTS",
+    "USER_AGENT",
+];
+
+/// Generate an async `reqwest::Client` snippet of the request.
+///
+/// `-k` maps to `.danger_accept_invalid_certs`, `-m` / `--connect-timeout` to the client
+/// timeouts, and curl not following redirects without `-L` to `Policy::none()`. A JSON body
+/// is a `serde_json::json!` literal when it's valid JSON, otherwise it's sent as is.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = statements(&parts, false);
+
+    match &parts.output {
+        Some(output) => code.push_str(&format!(
+            "\nstd::fs::write({:?}, response.bytes().await?)?;\n",
+            output
+        )),
+        None => code.push_str("\nprintln!(\"{}\", response.text().await?);\n"),
+    }
+    code
+}
+
+/// Generate a ready-to-compile async function sending the request and returning the response.
+///
+/// Unlike [`generate`], the standard headers are the `reqwest::header` constants and the body
+/// is a `BODY` literal sent as is, without a `serde_json` dependency. `-o` is left to the
+/// caller.
+pub fn generate_fn(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::new();
+
+    if let Some(Body::Raw(body) | Body::Json(body)) = &parts.body {
+        code.push_str(&format!("const BODY: &str = {};\n\n", str_literal(body)));
+    }
+    code.push_str(
+        "pub async fn send_request() -> Result<reqwest::Response, Box<dyn std::error::Error>> {\n",
+    );
+    for line in statements(&parts, true).lines() {
+        if !line.is_empty() {
+            code.push_str("    ");
+        }
+        code.push_str(line);
+        code.push('\n');
+    }
+    code.push_str("    Ok(response)\n}\n");
+    code
+}
+
+/// The statements building the client and sending the request into `response`, the body and
+/// header names of a function are `BODY` and constants.
+fn statements(parts: &RequestParts, function: bool) -> String {
+    let mut code = String::new();
+
+    if let Some(Body::Form(fields)) = &parts.body {
+        code.push_str("let form = reqwest::multipart::Form::new()");
+        for field in fields {
+            match field {
+                FormField::Text(name, value) => {
+                    code.push_str(&format!("\n    .text({:?}, {:?})", name, value))
+                }
+                FormField::File(name, path) => code.push_str(&format!(
+                    "\n    .part({:?}, reqwest::multipart::Part::bytes(std::fs::read({:?})?).file_name({:?}))",
+                    name, path, path
+                )),
+                FormField::Content(name, path) => code.push_str(&format!(
+                    "\n    .text({:?}, std::fs::read_to_string({:?})?)",
+                    name, path
+                )),
+            }
+        }
+        code.push_str(";\n\n");
+    }
+
+    code.push_str("let client = reqwest::Client::builder()");
+    if parts.insecure {
+        code.push_str("\n    .danger_accept_invalid_certs(true)");
+    }
+    if let Some(timeout) = parts.timeout {
+        code.push_str(&format!(
+            "\n    .timeout(std::time::Duration::from_secs_f64({:?}))",
+            timeout
+        ));
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        code.push_str(&format!(
+            "\n    .connect_timeout(std::time::Duration::from_secs_f64({:?}))",
+            timeout
+        ));
+    }
+    match (parts.follow_redirects, parts.max_redirects) {
+        (false, _) => code.push_str("\n    .redirect(reqwest::redirect::Policy::none())"),
+        (true, Some(max)) => code.push_str(&format!(
+            "\n    .redirect(reqwest::redirect::Policy::limited({}))",
+            max
+        )),
+        (true, None) => {}
+    }
+    if let Some(proxy) = &parts.proxy {
+        code.push_str(&format!("\n    .proxy(reqwest::Proxy::all({:?})?)", proxy));
+    }
+    code.push_str("\n    .build()?;\n\n");
+
+    code.push_str("let response = client");
+    match &parts.method {
+        Method::GET => code.push_str(&format!("\n    .get({:?})", parts.url)),
+        Method::POST => code.push_str(&format!("\n    .post({:?})", parts.url)),
+        Method::PUT => code.push_str(&format!("\n    .put({:?})", parts.url)),
+        Method::PATCH => code.push_str(&format!("\n    .patch({:?})", parts.url)),
+        Method::DELETE => code.push_str(&format!("\n    .delete({:?})", parts.url)),
+        Method::HEAD => code.push_str(&format!("\n    .head({:?})", parts.url)),
+        method => code.push_str(&format!(
+            "\n    .request(reqwest::Method::from_bytes(b{:?})?, {:?})",
+            method.as_str(),
+            parts.url
+        )),
+    }
+
+    for (name, value) in &parts.headers {
+        let is_content_type = name.eq_ignore_ascii_case("Content-Type");
+        if is_content_type && matches!(parts.body, Some(Body::Form(_))) {
+            // The multipart boundary is set by reqwest itself
+            continue;
+        }
+        let name = match function {
+            true => header_name(name),
+            false => format!("{:?}", name),
+        };
+        code.push_str(&format!("\n    .header({}, {:?})", name, value));
+    }
+
+    match (&parts.basic_auth, &parts.bearer_token) {
+        (Some((name, Some(pwd))), _) => {
+            code.push_str(&format!("\n    .basic_auth({:?}, Some({:?}))", name, pwd))
+        }
+        (Some((name, None)), _) => {
+            code.push_str(&format!("\n    .basic_auth({:?}, None::<&str>)", name))
+        }
+        (None, Some(token)) => code.push_str(&format!("\n    .bearer_auth({:?})", token)),
+        (None, None) => {}
+    }
+
+    match &parts.body {
+        Some(Body::Raw(_) | Body::Json(_)) if function => code.push_str("\n    .body(BODY)"),
+        Some(Body::Json(json)) if is_json(json) => {
+            code.push_str(&format!("\n    .json(&serde_json::json!({}))", json))
+        }
+        Some(Body::Json(json)) => code.push_str(&format!("\n    .body({})", str_literal(json))),
+        Some(Body::Raw(raw)) => code.push_str(&format!("\n    .body({:?})", raw)),
+        Some(Body::File(path)) => {
+            code.push_str(&format!("\n    .body(std::fs::read({:?})?)", path))
+        }
+        Some(Body::Form(_)) => code.push_str("\n    .multipart(form)"),
+        None => {}
+    }
+    code.push_str("\n    .send()\n    .await?;\n");
+    code
+}
+
+/// The `reqwest::header` constant of a standard header, the string literal of the others.
+fn header_name(name: &str) -> String {
+    let constant = name.to_ascii_uppercase().replace('-', "_");
+    match HEADER_CONSTANTS.contains(&constant.as_str()) {
+        true => format!("reqwest::header::{}", constant),
+        false => format!("{:?}", name),
+    }
+}
+
+/// A raw string literal of the text, with enough `#` to hold its quotes. A raw string can't
+/// hold a carriage return, the escaped literal is used then.
+fn str_literal(text: &str) -> String {
+    if text.contains('\r') {
+        return format!("{:?}", text);
+    }
+    let mut hashes = 1;
+    while text.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+    let hashes = "#".repeat(hashes);
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn reqwest(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_reqwest() {
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' -d '{\"a\":\"b\"}' -u 'user:pwd' -k -L -m '10' -o 'out.json'";
+        let expect = r#"let client = reqwest::Client::builder()
+    .danger_accept_invalid_certs(true)
+    .timeout(std::time::Duration::from_secs_f64(10.0))
+    .build()?;
+
+let response = client
+    .put("https://github.com/a")
+    .header("Content-Type", "application/json")
+    .basic_auth("user", Some("pwd"))
+    .json(&serde_json::json!({"a":"b"}))
+    .send()
+    .await?;
+
+std::fs::write("out.json", response.bytes().await?)?;
+"#;
+        generic_parse(reqwest, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_reqwest_invalid_json() {
+        let input = "curl 'https://github.com/a' -H 'Content-Type: application/json' -d '{\"id\": {{id}}}' -L";
+        let code = reqwest(input);
+        assert!(
+            code.contains("\n    .body(r#\"{\"id\": {{id}}}\"#)\n"),
+            "{}",
+            code
+        );
+        assert!(!code.contains("json!"));
+    }
+
+    #[test]
+    fn test_generate_reqwest_explicit_authorization() {
+        let code =
+            reqwest("curl 'https://github.com/a' -u 'user:pwd' -H 'Authorization: Bearer abc'");
+        assert!(code.contains(".header(\"Authorization\", \"Bearer abc\")"));
+        assert!(!code.contains("basic_auth"), "{}", code);
+    }
+
+    #[test]
+    fn test_generate_reqwest_fn() {
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' -H 'X-Tag: \"#1\"' -d '{\"a\":\"b\"}' -k -m '2.5' -x 'http://proxy:8080'";
+        let expect = r###"const BODY: &str = r#"{"a":"b"}"#;
+
+pub async fn send_request() -> Result<reqwest::Response, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .timeout(std::time::Duration::from_secs_f64(2.5))
+        .redirect(reqwest::redirect::Policy::none())
+        .proxy(reqwest::Proxy::all("http://proxy:8080")?)
+        .build()?;
+
+    let response = client
+        .put("https://github.com/a")
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Tag", "\"#1\"")
+        .body(BODY)
+        .send()
+        .await?;
+    Ok(response)
+}
+"###;
+        let generate = |input| generate_fn(&parse_curl_command(input).unwrap());
+        generic_parse(generate, input, expect.to_string());
+
+        generic_parse(str_literal, "a\"#b", "r##\"a\"#b\"##".to_string());
+        generic_parse(str_literal, "a\r\nb", "\"a\\r\\nb\"".to_string());
+    }
+
+    #[test]
+    fn test_generate_reqwest_multipart() {
+        let input =
+            "curl 'https://github.com/upload' -X 'PURGE' -F 'name=nomcurl' -F 'file=@a.png'";
+        let expect = r#"let form = reqwest::multipart::Form::new()
+    .text("name", "nomcurl")
+    .part("file", reqwest::multipart::Part::bytes(std::fs::read("a.png")?).file_name("a.png"));
+
+let client = reqwest::Client::builder()
+    .redirect(reqwest::redirect::Policy::none())
+    .build()?;
+
+let response = client
+    .request(reqwest::Method::from_bytes(b"PURGE")?, "https://github.com/upload")
+    .multipart(form)
+    .send()
+    .await?;
+
+println!("{}", response.text().await?);
+"#;
+        generic_parse(reqwest, input, expect.to_string());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{Body, FormField, RequestParts};
+
+const BOUNDARY: &str = "nomcurl-boundary";
+
+/// Generate a Swift `URLRequest` / `URLSession` snippet of the request.
+///
+/// `-m` maps to `timeoutInterval`, `-k` and `-x` need a session delegate and configuration
+/// and are only noted in comments.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let mut code = String::from("import Foundation\n\n");
+
+    if parts.insecure {
+        code.push_str("// --insecure: accept the server trust in urlSession(_:didReceive:completionHandler:)\n");
+    }
+    if let Some(proxy) = &parts.proxy {
+        code.push_str(&format!(
+            "// --proxy {}: set URLSessionConfiguration.connectionProxyDictionary\n",
+            proxy
+        ));
+    }
+
+    code.push_str(&format!(
+        "var request = URLRequest(url: URL(string: {})!)\n",
+        swift_string(&parts.url)
+    ));
+    if parts.method != Method::GET {
+        code.push_str(&format!(
+            "request.httpMethod = {}\n",
+            swift_string(parts.method.as_str())
+        ));
+    }
+    if let Some(timeout) = parts.timeout {
+        code.push_str(&format!("request.timeoutInterval = {}\n", timeout));
+    }
+
+    let is_form = matches!(parts.body, Some(Body::Form(_)));
+    let mut headers: Vec<(String, String)> = parts
+        .headers
+        .iter()
+        .filter(|(n, _)| !(is_form && n.eq_ignore_ascii_case("Content-Type")))
+        .cloned()
+        .collect();
+    if is_form {
+        let content_type = format!("multipart/form-data; boundary={}", BOUNDARY);
+        headers.push(("Content-Type".into(), content_type));
+    }
+    if let Some(authorization) = parts.authorization() {
+        headers.push(("Authorization".into(), authorization));
+    }
+    for (name, value) in &headers {
+        code.push_str(&format!(
+            "request.setValue({}, forHTTPHeaderField: {})\n",
+            swift_string(value),
+            swift_string(name)
+        ));
+    }
+
+    match &parts.body {
+        Some(Body::Raw(raw) | Body::Json(raw)) => code.push_str(&format!(
+            "request.httpBody = Data({}.utf8)\n",
+            swift_string(raw)
+        )),
+        Some(Body::File(path)) => code.push_str(&format!(
+            "request.httpBody = try Data(contentsOf: URL(fileURLWithPath: {}))\n",
+            swift_string(path)
+        )),
+        Some(Body::Form(fields)) => code.push_str(&multipart(fields)),
+        None => {}
+    }
+
+    code.push_str("\nlet (data, response) = try await URLSession.shared.data(for: request)\n");
+    match &parts.output {
+        Some(output) => code.push_str(&format!(
+            "try data.write(to: URL(fileURLWithPath: {}))\n",
+            swift_string(output)
+        )),
+        None => code.push_str("print(String(decoding: data, as: UTF8.self))\n"),
+    }
+    code
+}
+
+/// Assemble the multipart body by hand, URLSession has no multipart API.
+fn multipart(fields: &[FormField]) -> String {
+    let mut code = String::from("\nvar body = Data()\n");
+    for field in fields {
+        match field {
+            FormField::Text(name, value) => {
+                let part = format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    BOUNDARY, name, value
+                );
+                code.push_str(&format!(
+                    "body.append(Data({}.utf8))\n",
+                    swift_string(&part)
+                ));
+            }
+            FormField::File(name, path) => {
+                let part = format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\r\n",
+                    BOUNDARY, name, path
+                );
+                code.push_str(&format!(
+                    "body.append(Data({}.utf8))\n",
+                    swift_string(&part)
+                ));
+                code.push_str(&format!(
+                    "body.append(try Data(contentsOf: URL(fileURLWithPath: {})))\n",
+                    swift_string(path)
+                ));
+                code.push_str("body.append(Data(\"\\r\\n\".utf8))\n");
+            }
+            FormField::Content(name, path) => {
+                let part = format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                    BOUNDARY, name
+                );
+                code.push_str(&format!(
+                    "body.append(Data({}.utf8))\n",
+                    swift_string(&part)
+                ));
+                code.push_str(&format!(
+                    "body.append(try Data(contentsOf: URL(fileURLWithPath: {})))\n",
+                    swift_string(path)
+                ));
+                code.push_str("body.append(Data(\"\\r\\n\".utf8))\n");
+            }
+        }
+    }
+    let end = format!("--{}--\r\n", BOUNDARY);
+    code.push_str(&format!("body.append(Data({}.utf8))\n", swift_string(&end)));
+    code.push_str("request.httpBody = body\n");
+    code
+}
+
+/// A Swift string literal, control characters use the `\u{..}` escape.
+fn swift_string(input: &str) -> String {
+    let mut result = String::with_capacity(input.len() + 2);
+    result.push('"');
+    for c in input.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn swift(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_swift() {
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'Content-Type: application/json' -d '{\"a\":1}' -u 'user:pwd' -m '2.5' -o 'out.json'";
+        let expect = r#"import Foundation
+
+var request = URLRequest(url: URL(string: "https://github.com/a")!)
+request.httpMethod = "PUT"
+request.timeoutInterval = 2.5
+request.setValue("application/json", forHTTPHeaderField: "Content-Type")
+request.setValue("Basic dXNlcjpwd2Q=", forHTTPHeaderField: "Authorization")
+request.httpBody = Data("{\"a\":1}".utf8)
+
+let (data, response) = try await URLSession.shared.data(for: request)
+try data.write(to: URL(fileURLWithPath: "out.json"))
+"#;
+        generic_parse(swift, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_generate_swift_explicit_authorization() {
+        let code =
+            swift("curl 'https://github.com/a' -u 'user:pwd' -H 'Authorization: Bearer abc'");
+        assert_eq!(1, code.matches("\"Authorization\")").count(), "{}", code);
+        assert!(code
+            .contains("request.setValue(\"Bearer abc\", forHTTPHeaderField: \"Authorization\")"));
+    }
+
+    #[test]
+    fn test_generate_swift_multipart() {
+        let input = "curl 'https://github.com/upload' -F 'name=nomcurl' -F 'file=@a.png' -k";
+        let expect = r#"import Foundation
+
+// --insecure: accept the server trust in urlSession(_:didReceive:completionHandler:)
+var request = URLRequest(url: URL(string: "https://github.com/upload")!)
+request.httpMethod = "POST"
+request.setValue("multipart/form-data; boundary=nomcurl-boundary", forHTTPHeaderField: "Content-Type")
+
+var body = Data()
+body.append(Data("--nomcurl-boundary\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nnomcurl\r\n".utf8))
+body.append(Data("--nomcurl-boundary\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.png\"\r\n\r\n".utf8))
+body.append(try Data(contentsOf: URL(fileURLWithPath: "a.png")))
+body.append(Data("\r\n".utf8))
+body.append(Data("--nomcurl-boundary--\r\n".utf8))
+request.httpBody = body
+
+let (data, response) = try await URLSession.shared.data(for: request)
+print(String(decoding: data, as: UTF8.self))
+"#;
+        generic_parse(swift, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_swift_string() {
+        generic_parse(
+            swift_string,
+            "a\"b\\(c)\u{1}",
+            "\"a\\\"b\\\\(c)\\u{1}\"".to_string(),
+        );
+    }
+}
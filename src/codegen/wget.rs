@@ -0,0 +1,212 @@
+use crate::curl::{method::Method, request::ParsedRequest};
+
+use super::{shell_quote, Body, RequestParts};
+
+/// The curl options wget has no equivalent for, with the reason.
+const UNSUPPORTED: [(&str, &str); 9] = [
+    ("--form", "wget can't send multipart forms"),
+    ("--form-string", "wget can't send multipart forms"),
+    ("--socks5", "wget doesn't support SOCKS proxies"),
+    ("--http2", "wget only speaks HTTP/1.x"),
+    ("--resolve", "wget can't pin a host to an address"),
+    ("--ntlm", "wget doesn't support NTLM authentication"),
+    ("--negotiate", "wget doesn't support SPNEGO authentication"),
+    ("--write-out", "wget has no output format"),
+    ("--retry-max-time", "wget can't limit the total retry time"),
+];
+
+/// Generate the closest wget command of the request.
+///
+/// The curl features wget can't express are reported by [`diagnostics`] and prepended as
+/// shell comments.
+pub fn generate(request: &ParsedRequest) -> String {
+    let parts = RequestParts::new(request);
+    let flag_value = |name: &str| request.flag(name).and_then(|f| f.data.clone());
+    let mut args: Vec<String> = vec!["wget".into()];
+
+    match (&parts.method, &parts.body) {
+        (Method::POST, Some(Body::Raw(data) | Body::Json(data))) => {
+            args.push(format!("--post-data={}", data))
+        }
+        (Method::POST, Some(Body::File(path))) => args.push(format!("--post-file={}", path)),
+        (Method::GET, None) => {}
+        (method, body) => {
+            args.push(format!("--method={}", method));
+            match body {
+                Some(Body::Raw(data) | Body::Json(data)) => {
+                    args.push(format!("--body-data={}", data))
+                }
+                Some(Body::File(path)) => args.push(format!("--body-file={}", path)),
+                _ => {}
+            }
+        }
+    }
+
+    for (name, value) in &parts.headers {
+        args.push(format!("--header={}: {}", name, value));
+    }
+    if let Some((name, pwd)) = &parts.basic_auth {
+        args.push(format!("--user={}", name));
+        if let Some(pwd) = pwd {
+            args.push(format!("--password={}", pwd));
+        }
+        // curl sends the credentials up front, wget waits for the challenge otherwise
+        args.push("--auth-no-challenge".into());
+    } else if let Some(token) = &parts.bearer_token {
+        args.push(format!("--header=Authorization: Bearer {}", token));
+    }
+    if let Some(cookies) = flag_value("--cookie").filter(|c| !c.contains('=')) {
+        args.push(format!("--load-cookies={}", cookies));
+    }
+    if let Some(jar) = flag_value("--cookie-jar") {
+        args.push(format!("--save-cookies={}", jar));
+        args.push("--keep-session-cookies".into());
+    }
+    if request.has_flag("--compressed") {
+        args.push("--compression=auto".into());
+    }
+
+    if parts.insecure {
+        args.push("--no-check-certificate".into());
+    }
+    for (curl, wget) in [
+        ("--cacert", "--ca-certificate"),
+        ("--capath", "--ca-directory"),
+        ("--cert", "--certificate"),
+        ("--key", "--private-key"),
+    ] {
+        if let Some(value) = flag_value(curl) {
+            args.push(format!("{}={}", wget, value));
+        }
+    }
+    if request.has_flag("--tlsv1.2") {
+        args.push("--secure-protocol=TLSv1_2".into());
+    } else if request.has_flag("--tlsv1.3") {
+        args.push("--secure-protocol=TLSv1_3".into());
+    }
+
+    // wget follows up to 20 redirects by default, curl none without -L
+    match (parts.follow_redirects, parts.max_redirects) {
+        (false, _) => args.push("--max-redirect=0".into()),
+        (true, Some(max)) => args.push(format!("--max-redirect={}", max)),
+        (true, None) => {}
+    }
+    if let Some(timeout) = parts.timeout {
+        args.push(format!("--timeout={}", timeout));
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        args.push(format!("--connect-timeout={}", timeout));
+    }
+    if let Some(retry) = flag_value("--retry").and_then(|r| r.parse::<u32>().ok()) {
+        args.push(format!("--tries={}", retry + 1));
+    }
+    if let Some(delay) = flag_value("--retry-delay") {
+        args.push(format!("--waitretry={}", delay));
+    }
+    if request.has_flag("--ipv4") {
+        args.push("--inet4-only".into());
+    } else if request.has_flag("--ipv6") {
+        args.push("--inet6-only".into());
+    }
+
+    if let Some(proxy) = parts.proxy.as_ref().filter(|p| !p.starts_with("socks")) {
+        args.push("--execute=use_proxy=yes".into());
+        args.push(format!("--execute=http_proxy={}", proxy));
+        args.push(format!("--execute=https_proxy={}", proxy));
+    }
+    if let Some(noproxy) = flag_value("--noproxy") {
+        args.push(format!("--execute=no_proxy={}", noproxy));
+    }
+    if let Some((name, pwd)) = flag_value("--proxy-user")
+        .as_deref()
+        .map(|u| u.split_once(':').unwrap_or((u, "")))
+    {
+        args.push(format!("--proxy-user={}", name));
+        args.push(format!("--proxy-password={}", pwd));
+    }
+
+    if request.has_flag("--silent") {
+        args.push("--quiet".into());
+    }
+    // curl writes to stdout, `-O` keeps the remote name as wget does by default
+    match &parts.output {
+        Some(output) => args.push(format!("--output-document={}", output)),
+        None if !request.has_flag("--remote-name") => args.push("--output-document=-".into()),
+        None => {}
+    }
+    args.push(parts.url.clone());
+
+    let mut code: String = diagnostics(request)
+        .iter()
+        .map(|d| format!("# {}\n", d))
+        .collect();
+    let args: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+    code.push_str(&args.join(" "));
+    code.push('\n');
+    code
+}
+
+/// The curl features used by the request which the wget command can't reproduce.
+pub fn diagnostics(request: &ParsedRequest) -> Vec<String> {
+    let mut diagnostics: Vec<String> = UNSUPPORTED
+        .iter()
+        .filter(|(name, _)| request.has_flag(name))
+        .map(|(name, reason)| format!("{}: {}", name, reason))
+        .collect();
+
+    let proxy = request.flag("--proxy").and_then(|f| f.data.as_deref());
+    if proxy.is_some_and(|p| p.starts_with("socks")) {
+        diagnostics.push("--proxy: wget doesn't support SOCKS proxies".into());
+    }
+    if request.has_flag("--max-time") {
+        diagnostics.push(
+            "--max-time: wget has no total timeout, --timeout applies to each network operation"
+                .into(),
+        );
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    fn wget(input: &str) -> String {
+        generate(&parse_curl_command(input).unwrap())
+    }
+
+    #[test]
+    fn test_generate_wget() {
+        generic_parse(
+            wget,
+            "curl 'https://github.com/a' -L -o 'a.html'",
+            "wget --output-document=a.html https://github.com/a\n".to_string(),
+        );
+
+        let input =
+            "curl 'https://github.com/a' -X 'PUT' -H 'Accept: */*' -d 'a=1' -u 'user:pwd' -k";
+        let expect = "wget --method=PUT --body-data=a=1 '--header=Accept: */*' '--header=Content-Type: application/x-www-form-urlencoded' --user=user --password=pwd --auth-no-challenge --no-check-certificate --max-redirect=0 --output-document=- https://github.com/a\n";
+        generic_parse(wget, input, expect.to_string());
+
+        let input = "curl 'https://github.com/a' -d '@body.txt' -x 'http://proxy:8080' -O -L";
+        let expect = "wget --post-file=body.txt '--header=Content-Type: application/x-www-form-urlencoded' --execute=use_proxy=yes --execute=http_proxy=http://proxy:8080 --execute=https_proxy=http://proxy:8080 https://github.com/a\n";
+        generic_parse(wget, input, expect.to_string());
+    }
+
+    #[test]
+    fn test_wget_diagnostics() {
+        let input =
+            "curl 'https://github.com/upload' -F 'file=@a.png' -m '10' -x 'socks5://proxy:1080' -L";
+        let expect = vec![
+            "--form: wget can't send multipart forms".to_string(),
+            "--proxy: wget doesn't support SOCKS proxies".to_string(),
+            "--max-time: wget has no total timeout, --timeout applies to each network operation"
+                .to_string(),
+        ];
+        let request = parse_curl_command(input).unwrap();
+        generic_parse(diagnostics, &request, expect);
+
+        assert!(generate(&request).starts_with("# --form: wget can't send multipart forms\n"));
+    }
+}
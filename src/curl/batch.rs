@@ -0,0 +1,174 @@
+use serde::Serialize;
+
+#[cfg(feature = "arena")]
+use bumpalo::{collections::Vec as BumpVec, Bump};
+
+#[cfg(feature = "arena")]
+use super::request::ParsedRequest;
+use super::{document::RequestDocument, error::line_col, request};
+
+/// The outcome of one curl command of a batch input.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BatchEntry {
+    /// The position of the command in the input, from 0.
+    pub index: usize,
+    /// The line the command starts on, from 1.
+    pub line: usize,
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<RequestDocument>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Parse every curl command found in the input, see `request::find_curl_commands`.
+pub fn parse_batch(input: &str) -> Vec<BatchEntry> {
+    request::find_curl_commands(input)
+        .into_iter()
+        .enumerate()
+        .map(|(index, command)| {
+            let result = request::parse_curl_command(command);
+            BatchEntry {
+                index,
+                line: line_of(input, command),
+                command: command.into(),
+                request: result.as_ref().ok().map(RequestDocument::from),
+                error: result.err().map(|e| e.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// The outcome of one curl command of `parse_batch_in`, borrowing from the input and the arena.
+#[cfg(feature = "arena")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaEntry<'b> {
+    /// The position of the command in the input, from 0.
+    pub index: usize,
+    /// The line the command starts on, from 1.
+    pub line: usize,
+    pub command: &'b str,
+    /// The parsed request or the error message.
+    pub request: Result<ParsedRequest<'b>, &'b str>,
+}
+
+/// Parse every curl command found in the input like `parse_batch`, but borrow the commands and
+/// the values of the requests from the input rather than copying them into owned strings.
+///
+/// Only the entries and the error messages are allocated in the arena, resetting it frees them
+/// at once. The params of each request, and the strings of a command read once normalised (see
+/// `request::parse_curl_command`), are allocated globally.
+#[cfg(feature = "arena")]
+pub fn parse_batch_in<'b>(input: &'b str, bump: &'b Bump) -> BumpVec<'b, ArenaEntry<'b>> {
+    let commands = request::find_curl_commands(input);
+    let mut entries = BumpVec::with_capacity_in(commands.len(), bump);
+    entries.extend(commands.into_iter().enumerate().map(|(index, command)| {
+        let request = request::parse_curl_command(command)
+            .map_err(|e| bumpalo::format!(in bump, "{}", e).into_bump_str());
+        ArenaEntry {
+            index,
+            line: line_of(input, command),
+            command,
+            request,
+        }
+    }));
+    entries
+}
+
+/// The line the command starts on, from 1, the command being a slice of the input.
+fn line_of(input: &str, command: &str) -> usize {
+    let offset = command.as_ptr() as usize - input.as_ptr() as usize;
+    line_col(input, offset).0
+}
+
+/// The number of entries which parsed and which failed.
+pub fn counts(entries: &[BatchEntry]) -> (usize, usize) {
+    let parsed = entries.iter().filter(|e| e.request.is_some()).count();
+    (parsed, entries.len() - parsed)
+}
+
+/// The pretty printed JSON array of the entries.
+pub fn to_json(entries: &[BatchEntry]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(entries)
+}
+
+/// Newline-delimited JSON of the entries, one object per line.
+pub fn to_ndjson(entries: &[BatchEntry]) -> serde_json::Result<String> {
+    let mut ndjson = String::new();
+    for entry in entries {
+        ndjson.push_str(&serde_json::to_string(entry)?);
+        ndjson.push('\n');
+    }
+    Ok(ndjson)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch() {
+        let input = "curl 'https://github.com/a' -H 'A: 1'\necho done\ncurl -H 'A: 1'\n";
+        let entries = parse_batch(input);
+
+        assert_eq!(2, entries.len());
+        assert_eq!(1, entries[1].index);
+        assert_eq!((1, 3), (entries[0].line, entries[1].line));
+        assert_eq!((1, 1), counts(&entries));
+        assert!(entries[0].request.is_some() && entries[0].error.is_none());
+        assert_eq!(Some("no target url found".to_string()), entries[1].error);
+
+        let entries = parse_batch("curl 'https://a.com'\r\necho done\rcurl 'https://b.com'\n");
+        assert_eq!((1, 3), (entries[0].line, entries[1].line));
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_parse_batch_in() {
+        let input = "curl 'https://github.com/a' -H 'A: 1'\necho done\ncurl -H 'A: 1'\n";
+        let mut bump = Bump::new();
+        let entries = parse_batch_in(input, &bump);
+
+        assert_eq!(2, entries.len());
+        assert_eq!((1, 3), (entries[0].line, entries[1].line));
+        assert_eq!("curl -H 'A: 1'", entries[1].command);
+        let request = entries[0].request.as_ref().unwrap();
+        assert_eq!(Some("1"), request.header("a"));
+        assert_eq!(Err("no target url found"), entries[1].request);
+
+        drop(entries);
+        bump.reset();
+        assert_eq!(
+            1,
+            parse_batch_in("curl 'https://github.com/b'", &bump).len()
+        );
+    }
+
+    #[test]
+    fn test_to_ndjson() {
+        let input = "curl 'https://github.com/a'\ncurl -k";
+        let ndjson = to_ndjson(&parse_batch(input)).unwrap();
+        let lines: Vec<&str> = ndjson.lines().collect();
+
+        assert_eq!(2, lines.len());
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!("github.com", first["request"]["url"]["domain"]);
+        let second: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!("curl -k", second["command"]);
+        assert_eq!("no target url found", second["error"]);
+        assert!(second.get("request").is_none());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let input = "# setup\ncurl 'https://github.com/a' \\\n  -k\n\ncurl 'https://github.com/b'";
+        let json = to_json(&parse_batch(input)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(2, value.as_array().unwrap().len());
+        assert_eq!(2, value[0]["line"]);
+        assert_eq!(5, value[1]["line"]);
+        assert_eq!("-k", value[0]["request"]["flags"][0]["name"]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use super::{options, request::ParsedRequest, url_parser::Protocol, Curl};
+
+/// Where a cookie of the request comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum CookieSource {
+    /// A `-b name=value` string, a `-b` without `=` is a cookie file and is ignored.
+    Flag,
+    /// A `Cookie:` header.
+    Header,
+}
+
+/// A `name=value` cookie sent by the request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub source: CookieSource,
+}
+
+/// Every cookie of the `-b` strings and `Cookie` headers, in input order.
+pub fn cookies(request: &ParsedRequest) -> Vec<Cookie> {
+    let mut cookies = Vec::new();
+    for curl in request.curls() {
+        let (source, line) = match curl {
+            Curl::Flag(f) if options::is_option(&f.identifier, "--cookie") => {
+                match f.data.as_deref() {
+                    Some(data) if data.contains('=') => (CookieSource::Flag, data),
+                    _ => continue,
+                }
+            }
+            Curl::Header(h) => match h.data.as_deref().and_then(|h| h.split_once(':')) {
+                Some((name, value)) if name.trim().eq_ignore_ascii_case("cookie") => {
+                    (CookieSource::Header, value)
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+        for cookie in line.split(';').filter(|c| !c.trim().is_empty()) {
+            let (name, value) = cookie.split_once('=').unwrap_or((cookie, ""));
+            cookies.push(Cookie {
+                name: name.trim().into(),
+                value: value.trim().into(),
+                source,
+            });
+        }
+    }
+    cookies
+}
+
+/// A `NAME  VALUE  SOURCE` table with aligned columns.
+pub fn to_table(cookies: &[Cookie]) -> String {
+    let name_width = cookies
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let value_width = cookies
+        .iter()
+        .map(|c| c.value.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+
+    let mut table = String::new();
+    let mut row = |name: &str, value: &str, source: &str| {
+        let line = format!("{:<name_width$}  {:<value_width$}  {}", name, value, source);
+        writeln!(table, "{}", line.trim_end()).unwrap();
+    };
+    row("NAME", "VALUE", "SOURCE");
+    for cookie in cookies {
+        let source = match cookie.source {
+            CookieSource::Flag => "-b",
+            CookieSource::Header => "header",
+        };
+        row(&cookie.name, &cookie.value, source);
+    }
+    table
+}
+
+/// The cookies as a Netscape cookie-jar file, as read by `curl -b` and written by `curl -c`.
+///
+/// They're scoped to the host of the request url, on every path, with no expiry.
+pub fn to_netscape(request: &ParsedRequest, cookies: &[Cookie]) -> String {
+    let url = request.url();
+    let host = url.domain.split(':').next().unwrap_or_default();
+    let secure = match url.protocol {
+        Protocol::HTTPS => "TRUE",
+        _ => "FALSE",
+    };
+
+    let mut jar = String::from("# Netscape HTTP Cookie File\n");
+    for cookie in cookies {
+        writeln!(
+            jar,
+            "{}\tFALSE\t/\t{}\t0\t{}\t{}",
+            host, secure, cookie.name, cookie.value
+        )
+        .unwrap();
+    }
+    jar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    const TEST_CURL_CMD: &str = "curl 'https://github.com:8443/a' -b 'sid=abc; theme=dark' -H 'cookie: lang=en' -b 'jar.txt' -H 'Accept: */*'";
+
+    #[test]
+    fn test_cookies() {
+        let request = parse_curl_command(TEST_CURL_CMD).unwrap();
+        let cookies = cookies(&request);
+
+        let names: Vec<_> = cookies.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(vec!["sid", "theme", "lang"], names);
+        assert_eq!("dark", cookies[1].value);
+        assert_eq!(CookieSource::Header, cookies[2].source);
+
+        let expect =
+            "NAME   VALUE  SOURCE\nsid    abc    -b\ntheme  dark   -b\nlang   en     header\n";
+        assert_eq!(expect, to_table(&cookies));
+
+        let expect = "# Netscape HTTP Cookie File\ngithub.com\tFALSE\t/\tTRUE\t0\tsid\tabc\n";
+        assert_eq!(expect, to_netscape(&request, &cookies[..1]));
+    }
+}
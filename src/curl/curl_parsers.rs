@@ -1,115 +1,244 @@
+use alloc::borrow::Cow;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until},
+    bytes::complete::{tag, take_while, take_while1},
     character::{
         self,
-        complete::{alphanumeric0, anychar, char, multispace0, multispace1},
+        complete::{anychar, char, multispace0, multispace1},
     },
-    combinator::{map, map_res, opt, peek, recognize, rest},
+    combinator::{map, map_opt, map_res, opt, recognize},
     error::{context, Error, ErrorKind},
-    multi::fold_many0,
+    multi::{fold_many0, many1},
     sequence::{delimited, preceded, tuple},
     IResult,
 };
 
-use crate::curl::Curl;
+use crate::curl::{Curl, CurlStru};
+use crate::prelude::*;
 
-use super::url_parser;
+use super::{method::Method, options, url_parser};
 
 const CURL_CMD: &str = "curl";
+pub const NO_URL_FOUND: &str = "No target url found!";
+
 pub fn is_curl(input: &str) -> bool {
-    input.trim_start().to_lowercase().starts_with(CURL_CMD)
+    let input = input.trim_start();
+    curl_program_len(input).is_some()
+        || input
+            .get(..CURL_CMD.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(CURL_CMD))
 }
 
+/// The params of a command checked by `is_curl`, after its curl program.
 pub fn remove_curl_cmd_header(input: &str) -> &str {
-    &input[4..]
+    &input[curl_program_len(input).unwrap_or(CURL_CMD.len())..]
 }
 
-pub fn url_parse(input: &str) -> IResult<&str, Curl> {
+/// The length of the curl program a command starts with, `curl` or `curl.exe`, maybe under a path
+/// and quoted, e.g. `/usr/bin/curl` or `'C:\tools\curl.exe'`, when a whitespace or nothing
+/// follows it.
+pub fn curl_program_len(input: &str) -> Option<usize> {
+    let (program, len) = match input.chars().next()? {
+        q @ ('\'' | '"') => {
+            let end = input[1..].find(q)? + 1;
+            (&input[1..end], end + 1)
+        }
+        _ => {
+            let len = input.find(char::is_whitespace).unwrap_or(input.len());
+            (&input[..len], len)
+        }
+    };
+    let name = program.rsplit(['/', '\\']).next()?;
+    let is_curl = name.eq_ignore_ascii_case(CURL_CMD) || name.eq_ignore_ascii_case("curl.exe");
+    let rest = &input[len..];
+    (is_curl && (rest.is_empty() || rest.starts_with(char::is_whitespace))).then_some(len)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument(level = "trace", skip_all))]
+pub fn url_parse(input: &str) -> IResult<&str, Curl<'_>> {
     context(
         "url parse",
         preceded(
             multispace0,
-            map_res(quoted_data_parse, |d| {
+            map_res(word_parse, |d| {
                 // let url_parsed = url::Url::parse(d);
-                let url_parsed = url_parser::curl_url_parse(d);
-                if let Ok((_, u)) = url_parsed {
-                    Ok(Curl::new_as_url(u))
-                } else {
-                    Err(url_parsed)
+                match d {
+                    Cow::Borrowed(d) => url_parser::curl_url_parse(d)
+                        .map(|(_, u)| Curl::new_as_url(u))
+                        .map_err(|_| ()),
+                    // An unescaped url doesn't borrow from the input
+                    Cow::Owned(d) => url_parser::curl_url_parse(&d)
+                        .map(|(_, u)| Curl::new_as_url(u.into_owned()))
+                        .map_err(|_| ()),
                 }
             }),
         ),
     )(input)
 }
 
-/// Identify the ending pattern: <space*>\<space*>\r\n
+/// Parse the url as one of the params, it may follow flags, e.g. `curl -k 'https://github.com'`
+/// or `curl -k https://github.com`.
+///
+/// Only a word is tried, so a flag or the end of the input isn't taken for a url.
+pub fn url_param_parse(input: &str) -> IResult<&str, Curl<'_>> {
+    let (rest, _) = opt(slash_line_ending)(input)?;
+    if !rest.trim_start().starts_with(starts_word) {
+        return Err(nom::Err::Error(Error::new(rest, ErrorKind::Char)));
+    }
+    url_parse(rest)
+}
+
+/// Parse the url without decomposing it, see `lazy::LazyRequest`.
+pub fn raw_url_parse(input: &str) -> IResult<&str, Cow<'_, str>> {
+    context("raw url parse", preceded(multispace0, word_parse))(input)
+}
+
+/// Identify the ending pattern: <space*>\<space*>\r\n, repeated when a command re-joined by
+/// a chat tool has a run of `\` or a `\` followed by spaces only.
 pub fn slash_line_ending(input: &str) -> IResult<&str, &str> {
     context(
         "Slash line ending",
-        recognize(tuple((
+        recognize(many1(tuple((
             multispace0,
             character::complete::char('\\'),
             multispace0,
-        ))),
+        )))),
     )(input)
 }
 
-/// Parse double-quoted data with support for escaped characters
+/// Parse double-quoted data with support for escaped characters, the data is returned as it's
+/// written, see `unescape_double_quoted`.
 fn double_quoted_data_parse(input: &str) -> IResult<&str, &str> {
     context(
         "Double quoted data parse",
-        delimited(
-            tuple((multispace0, char('\"'))),
-            take_until("\""),
-            tuple((char('\"'), multispace0)),
-        ),
+        delimited(multispace0, double_quoted_segment, multispace0),
     )(input)
 }
 
-/// Parse single-quoted data with support for escaped characters
+/// Parse single-quoted data, where nothing is escaped.
 fn single_quoted_data_parse(input: &str) -> IResult<&str, &str> {
     context(
         "Single quoted data parse",
-        delimited(
-            tuple((multispace0, char('\''))),
-            take_until("\'"),
-            tuple((char('\''), multispace0)),
-        ),
+        delimited(multispace0, single_quoted_segment, multispace0),
     )(input)
 }
 
-/// Get the longest one quoted data between single / double quoted data.
-fn quoted_data_parse<'a>(input: &str) -> IResult<&str, &str> {
-    let double_res = double_quoted_data_parse(input);
-    let single_res = single_quoted_data_parse(input);
+fn single_quoted_segment(input: &str) -> IResult<&str, &str> {
+    delimited(char('\''), take_until_byte(b'\''), char('\''))(input)
+}
 
-    if double_res.is_ok() && single_res.is_ok() {
-        let (_double_rest, double_data) = double_res.unwrap();
-        let (_single_rest, single_data) = single_res.unwrap();
+/// A `"` escaped by a `\\` doesn't close the segment.
+fn double_quoted_segment(input: &str) -> IResult<&str, &str> {
+    let (rest, _) = char('"')(input)?;
+    let bytes = rest.as_bytes();
+    let mut pos = 0;
+    loop {
+        match memchr::memchr2(b'"', b'\\', &bytes[pos..]) {
+            Some(i) if bytes[pos + i] == b'\\' => pos = (pos + i + 2).min(bytes.len()),
+            // An ASCII byte is always a char boundary
+            Some(i) => return Ok((&rest[pos + i + 1..], &rest[..pos + i])),
+            None => return Err(nom::Err::Error(Error::new(rest, ErrorKind::TakeUntil))),
+        }
+    }
+}
 
-        if double_data.len() >= single_data.len() {
-            Ok((_double_rest, double_data))
-        } else {
-            Ok((_single_rest, single_data))
+/// The value of the data of a double-quoted segment: a `\\` escapes `"`, `\\`, `$`, `` ` `` and
+/// removes a line break, it's kept before any other char as in a POSIX shell.
+fn unescape_double_quoted(data: &str) -> Cow<'_, str> {
+    if !data.contains('\\') {
+        return Cow::Borrowed(data);
+    }
+    let mut value = String::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('"' | '\\' | '$' | '`')) => value.extend(chars.next()),
+            ('\\', Some('\n')) => {
+                chars.next();
+            }
+            _ => value.push(c),
         }
-    } else if double_res.is_err() && single_res.is_ok() {
-        single_res
-    } else if double_res.is_ok() && single_res.is_err() {
-        double_res
-    } else {
-        // Both parsing failed, return an error
-        let _single_err = single_res.unwrap_err();
-        let _double_err = double_res.unwrap_err();
+    }
+    Cow::Owned(value)
+}
 
-        #[cfg(feature = "debug-print")]
-        eprintln!(
-            "The origin: ({})\r\nThe single parse error: {}\r\nThe double parse error: {}",
-            input, _single_err, _double_err
-        );
+/// Take the input up to the ASCII byte, found with memchr to scan large inline payloads fast.
+fn take_until_byte(byte: u8) -> impl Fn(&str) -> IResult<&str, &str> {
+    move |input: &str| match memchr::memchr(byte, input.as_bytes()) {
+        // An ASCII byte is always a char boundary
+        Some(i) => Ok((&input[i..], &input[..i])),
+        None => Err(nom::Err::Error(Error::new(input, ErrorKind::TakeUntil))),
+    }
+}
+
+/// A char which ends an unquoted part of a word, see `shell_word_parse`.
+fn ends_word(c: char) -> bool {
+    c.is_whitespace() || "'\"\\|&;<>()".contains(c)
+}
+
+/// A char starting a word: a quote or an unquoted char, but a `-` which starts a flag.
+fn starts_word(c: char) -> bool {
+    matches!(c, '\'' | '"') || (c != '-' && !ends_word(c))
+}
+
+/// Parse a shell word, its adjacent quoted and unquoted parts are concatenated as a shell does,
+/// e.g. `'O'"'"'Brien'` or `'O'\\''Brien'` is `O'Brien`. An unquoted part ends at a whitespace
+/// or a shell operator, e.g. `https://github.com|jq` is `https://github.com`. The value is
+/// borrowed from the input unless it's unescaped or concatenated.
+fn shell_word_parse(input: &str) -> IResult<&str, Cow<'_, str>> {
+    let (mut rest, _) = multispace0(input)?;
+    let mut word: Option<Cow<'_, str>> = None;
+    loop {
+        let (after, part) = match rest.chars().next() {
+            Some('\'') => map(single_quoted_segment, Cow::Borrowed)(rest)?,
+            Some('"') => map(double_quoted_segment, unescape_double_quoted)(rest)?,
+            // A `\\` before a space is a line continuation, see `slash_line_ending`
+            Some('\\') => match rest[1..].chars().next() {
+                Some(c) if !c.is_whitespace() => (
+                    &rest[1 + c.len_utf8()..],
+                    Cow::Borrowed(&rest[1..1 + c.len_utf8()]),
+                ),
+                _ => break,
+            },
+            Some(c) if !ends_word(c) => map(take_while1(|c| !ends_word(c)), Cow::Borrowed)(rest)?,
+            _ => break,
+        };
+        word = Some(match word {
+            None => part,
+            Some(word) => Cow::Owned(word.into_owned() + &part),
+        });
+        rest = after;
+    }
+    match word {
+        Some(word) => Ok((multispace0(rest)?.0, word)),
+        None => Err(nom::Err::Error(Error::new(rest, ErrorKind::Char))),
+    }
+}
+
+/// Parse a value, a shell word quoted or not but not starting with a `-`, see `shell_word_parse`.
+fn word_parse(input: &str) -> IResult<&str, Cow<'_, str>> {
+    let res = match input
+        .trim_start_matches([' ', '\t', '\r', '\n'])
+        .chars()
+        .next()
+    {
+        Some(c) if starts_word(c) => shell_word_parse(input),
+        _ => Err(nom::Err::Error(Error::new(input, ErrorKind::Char))),
+    };
 
-        Err(nom::Err::Failure(Error::new(&input, ErrorKind::Fail)))
+    #[cfg(feature = "trace")]
+    if let Ok((_, data)) = &res {
+        tracing::trace!(len = data.len(), "lexed a word");
     }
+
+    res.map_err(|_e| {
+        #[cfg(feature = "trace")]
+        tracing::trace!(remaining = input.len(), "no word");
+        #[cfg(feature = "debug-print")]
+        eprintln!("The origin: ({})\r\nThe quoted parse error: {}", input, _e);
+
+        nom::Err::Failure(Error::new(input, ErrorKind::Fail))
+    })
 }
 
 pub fn iter_quoted_data_parse(input: &str) -> IResult<&str, Vec<String>> {
@@ -129,14 +258,14 @@ pub fn iter_quoted_data_parse(input: &str) -> IResult<&str, Vec<String>> {
 #[macro_export]
 macro_rules! parse_command {
     ($name:ident,$($tag:expr),+) => {
-        pub fn $name(input: &str) -> IResult<&str, Curl> {
+        pub fn $name(input: &str) -> IResult<&str, Curl<'_>> {
             context(
                 stringify!($name),
                 preceded(
                     opt(slash_line_ending),
-                    map(
-                        tuple((multispace0, alt(($( tag($tag) ),+,)), multispace1, quoted_data_parse)),
-                        |(_,method, _space, data)| Curl::new(method, data).unwrap(),
+                    map_opt(
+                        tuple((multispace0, alt(($( tag($tag) ),+,)), multispace1, word_parse)),
+                        |(_,method, _space, data)| Curl::new(method, data),
                     ),
                 ),
             )(input)
@@ -147,7 +276,7 @@ macro_rules! parse_command {
 #[macro_export]
 macro_rules! parse_commands {
     ($name:ident,$inner_func:ident) => {
-        pub fn $name(input: &str) -> IResult<&str, Vec<Curl>> {
+        pub fn $name(input: &str) -> IResult<&str, Vec<Curl<'_>>> {
             context(
                 stringify!($name),
                 fold_many0($inner_func, Vec::new, |mut acc: Vec<Curl>, m| {
@@ -159,58 +288,126 @@ macro_rules! parse_commands {
     };
 }
 
-parse_command!(method_parse, "-X");
+parse_command!(spaced_method_parse, "-X", "--request");
 parse_commands!(methods_parse, method_parse);
-parse_command!(header_parse, "-H");
+parse_command!(header_parse, "-H", "--header");
 parse_commands!(headers_parse, header_parse);
 parse_command!(data_parse, "-d", "--data");
 parse_commands!(datas_parse, data_parse);
 parse_commands!(flags_parse, flag_parse);
 
-pub fn flag_parse(input: &str) -> IResult<&str, Curl> {
+/// Parse `-X`, its value may be attached to it, e.g. `-XPOST` or `-X'POST'`, the param is the
+/// same as `-X 'POST'`. A valid method is written in uppercase, e.g. `-X 'post'` is `-X 'POST'`.
+pub fn method_parse(input: &str) -> IResult<&str, Curl<'_>> {
+    let (rest, curl) = alt((spaced_method_parse, attached_method_parse))(input)?;
+    Ok((rest, normalize_method(curl)))
+}
+
+fn normalize_method(curl: Curl<'_>) -> Curl<'_> {
+    match curl {
+        Curl::Method(mut m) => {
+            if let Some(Ok(method)) = m.data.as_deref().map(str::parse::<Method>) {
+                if m.data.as_deref() != Some(method.as_str()) {
+                    m.data = Some(method.to_string().into());
+                }
+            }
+            Curl::Method(m)
+        }
+        curl => curl,
+    }
+}
+
+fn attached_method_parse<'a>(input: &'a str) -> IResult<&'a str, Curl<'a>> {
+    context(
+        "attached method parse",
+        preceded(opt(slash_line_ending), |input: &'a str| {
+            let (r, method) = preceded(multispace0, tag("-X"))(input)?;
+            let (rest, data) = match r.starts_with(['\'', '"']) {
+                true => word_parse(r)?,
+                false => map(take_while1(is_method_char), Cow::Borrowed)(r)?,
+            };
+            match Curl::new(method, data) {
+                Some(curl) => Ok((rest, curl)),
+                None => Err(nom::Err::Error(Error::new(r, ErrorKind::Fail))),
+            }
+        }),
+    )(input)
+}
+
+/// A char of an HTTP method token, see RFC 9110.
+fn is_method_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&*+-.^_|~".contains(c)
+}
+
+/// Parse a flag, the flags taking a value (see `options::lookup`) consume the following quoted data.
+pub fn flag_parse(input: &str) -> IResult<&str, Curl<'_>> {
     context(
         "flag parse",
-        preceded(
-            opt(slash_line_ending),
-            map_res(
-                tuple((
-                    tuple((
-                        multispace0,
-                        character::complete::char('-'),
-                        anychar,
-                        alphanumeric0,
-                    )),
-                    peek(rest),
-                )),
-                |(flag, r)| {
-                    let followed_by_data = quoted_data_parse(r);
-                    match followed_by_data.is_err() {
-                        true => {
-                            let (_, a, b, c) = flag;
-                            let flag = format!("{}{}{}", a, b, c);
-                            match Curl::new_as_flag(&flag) {
-                                Some(f) => Ok(f),
-                                None => Err(nom::Err::Failure(Error::new(r, ErrorKind::Fail))),
-                            }
-                        }
-                        false => {
-                            // Success parsed the quote data
-                            Err(nom::Err::Failure(Error::new(r, ErrorKind::Fail)))
-                        }
-                    }
+        preceded(opt(slash_line_ending), |input| {
+            let (r, flag) = preceded(
+                multispace0,
+                recognize(tuple((
+                    character::complete::char('-'),
+                    anychar,
+                    take_while(is_flag_char),
+                ))),
+            )(input)?;
+
+            // -X, -H and -d are left to their own parsers
+            let own_parser = matches!(
+                flag,
+                "-X" | "--request" | "-H" | "--header" | "-d" | "--data"
+            );
+            let takes_value = options::lookup(flag).is_some_and(|o| o.takes_value) && !own_parser;
+            #[cfg(feature = "trace")]
+            tracing::trace!(
+                flag,
+                known = options::lookup(flag).is_some(),
+                takes_value,
+                "classified a flag"
+            );
+            let known = options::lookup(flag).is_some() && !own_parser;
+            match word_parse(r) {
+                Ok((rest, data)) if takes_value => {
+                    Ok((rest, Curl::Flag(CurlStru::new_with_data(flag, data))))
+                }
+                // The word after a known flag without a value is the next param, e.g.
+                // the url
+                Ok(_) if known => Curl::new_as_flag(flag)
+                    .map(|f| (r, f))
+                    .ok_or(nom::Err::Error(Error::new(r, ErrorKind::Fail))),
+                // Success parsed the word, but the flag doesn't take a value
+                Ok(_) => Err(nom::Err::Error(Error::new(r, ErrorKind::Fail))),
+                // A quote left open is a bad value, rather than no value
+                Err(e) if takes_value && r.trim_start().starts_with(['\'', '"']) => Err(e),
+                // curl takes the word after an option requiring a value as it, even a dash-leading
+                // one, e.g. `-o -` or `--max-time -1`
+                Err(_) if takes_value && r.trim_start().starts_with('-') => map(
+                    preceded(multispace0, take_while1(|c| !ends_word(c))),
+                    |data| Curl::Flag(CurlStru::new_with_data(flag, data)),
+                )(r),
+                Err(_) => match Curl::new_as_flag(flag) {
+                    Some(f) => Ok((r, f)),
+                    None => Err(nom::Err::Error(Error::new(r, ErrorKind::Fail))),
                 },
-            ),
-        ),
+            }
+        }),
     )(input)
 }
 
-pub fn commands_parse(input: &str) -> IResult<&str, Vec<Curl>> {
+fn is_flag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '.'
+}
+
+pub fn commands_parse(input: &str) -> IResult<&str, Vec<Curl<'_>>> {
     context(
         "all commands parse",
         fold_many0(
             alt((method_parse, header_parse, data_parse, flag_parse)),
             Vec::new,
             |mut acc, d| {
+                #[cfg(feature = "trace")]
+                trace_param(&d);
                 acc.push(d);
                 // acc.append(&mut d);
                 acc
@@ -219,40 +416,71 @@ pub fn commands_parse(input: &str) -> IResult<&str, Vec<Curl>> {
     )(input)
 }
 
-pub fn curl_cmd_parse(input: &str) -> IResult<&str, Vec<Curl>> {
+/// Parse the params and the urls, in any order, e.g. `-k 'https://github.com' -H 'A: 1'`.
+pub fn params_parse(input: &str) -> IResult<&str, Vec<Curl<'_>>> {
+    context(
+        "params parse",
+        fold_many0(
+            // A param value is consumed by its param, a word left is a url
+            alt((
+                url_param_parse,
+                method_parse,
+                header_parse,
+                data_parse,
+                flag_parse,
+            )),
+            Vec::new,
+            |mut acc, d| {
+                #[cfg(feature = "trace")]
+                trace_param(&d);
+                acc.push(d);
+                acc
+            },
+        ),
+    )(input)
+}
+
+/// Record the kind and identifier of a parsed param, without its value which may be a secret.
+#[cfg(feature = "trace")]
+fn trace_param(curl: &Curl) {
+    let (kind, identifier) = match curl {
+        Curl::URL(_) => ("url", ""),
+        Curl::Method(m) => ("method", &*m.identifier),
+        Curl::Header(h) => ("header", &*h.identifier),
+        Curl::Data(d) => ("data", &*d.identifier),
+        Curl::Flag(f) => ("flag", &*f.identifier),
+    };
+    tracing::trace!(kind, identifier, "parsed a param");
+}
+
+#[cfg_attr(
+    feature = "trace",
+    tracing::instrument(level = "debug", skip_all, fields(len = input.len()))
+)]
+pub fn curl_cmd_parse(input: &str) -> IResult<&str, Vec<Curl<'_>>> {
     if is_curl(input) {
-        let mut curl_cmds = Vec::new();
         let input = remove_curl_cmd_header(input.trim_start()); // Remove Curl header firstly
-        let url_p = url_parse(input); // Parse the Curl::URL
-
-        let r = match url_p {
-            Ok((rest, curl_url)) => {
-                curl_cmds.push(curl_url);
-                rest
-            }
-            Err(_) => {
-                return Err(nom::Err::Error(Error::new(
-                    "No target url found!",
-                    ErrorKind::Fail,
-                )));
-            }
-        };
 
-        // Start to extract all command params...
+        // Extract the params and the urls, in any order
         // For example: -H, -X, -d ...
-        let res = context("curl cmd parse", commands_parse)(r);
-
-        if let Ok((_rest, mut cmds)) = res {
-            curl_cmds.append(&mut cmds);
-            Ok((_rest, curl_cmds))
-        } else {
-            Err(nom::Err::Failure(Error::new(
-                "Fail to parse cmds",
-                ErrorKind::Fail,
-            )))
+        // A failure is kept as is, its input tells where the parser stopped
+        let (rest, curl_cmds) = context("curl cmd parse", params_parse)(input)?;
+        // A command may end with a lone \, e.g. when it's truncated
+        let (rest, _) = opt(slash_line_ending)(rest)?;
+        #[cfg(feature = "trace")]
+        tracing::debug!(
+            params = curl_cmds.len(),
+            unparsed = rest.len(),
+            "parsed the params"
+        );
+        if !curl_cmds.iter().any(|c| matches!(c, Curl::URL(_))) {
+            #[cfg(feature = "trace")]
+            tracing::debug!("no url after curl");
+            return Err(nom::Err::Error(Error::new(NO_URL_FOUND, ErrorKind::Fail)));
         }
+        Ok((rest, curl_cmds))
     } else {
-        Err(nom::Err::Error(Error::new(&input, ErrorKind::Fail)))
+        Err(nom::Err::Error(Error::new(input, ErrorKind::Fail)))
     }
 }
 
@@ -285,7 +513,7 @@ mod tests {
         }
     }
 
-    const TEST_CURL_CMD_FULL: &'static str = r#"
+    const TEST_CURL_CMD_FULL: &str = r#"
         curl 'http://query.sse.com.cn/commonQuery.do?jsonCallBack=jsonpCallback89469743&sqlId=COMMON_SSE_SJ_GPSJ_CJGK_MRGK_C&PRODUCT_CODE=01%2C02%2C03%2C11%2C17&type=inParams&SEARCH_DATE=2024-03-18&_=1710914422498'  \
         -H 'Accept: */*' -X 'TEST' \
         -H 'Accept-Language: en-US,en;q=0.9,zh-CN;q=0.8,zh;q=0.7' --b \
@@ -336,14 +564,79 @@ mod tests {
         generic_command_parse(curl_cmd_parse, input, expect);
     }
 
+    #[test]
+    fn test_curl_cmd_parse_empty_value() {
+        // Found by the curl_cmd_parse fuzz target
+        let (rest, curls) = curl_cmd_parse("curl 'https://github.com' -X '' -k").unwrap();
+        assert_eq!(vec![new_curl!(-X, ""), new_curl!("-k")], curls[1..]);
+        assert_eq!("", rest);
+
+        let input = "curl 'https://github.com' -d '' -H 'X-Empty;' --data-raw \"\" -u ''";
+        let (rest, curls) = curl_cmd_parse(input).unwrap();
+        let expect = vec![
+            new_curl!(-d, ""),
+            new_curl!(-H, "X-Empty;"),
+            Curl::Flag(CurlStru::new_with_data("--data-raw", "")),
+            Curl::Flag(CurlStru::new_with_data("-u", "")),
+        ];
+        assert_eq!(expect, curls[1..]);
+        assert_eq!("", rest);
+    }
+
+    #[test]
+    fn test_slash_line_ending() {
+        assert_eq!(Ok(("-k", " \\\n  ")), slash_line_ending(" \\\n  -k"));
+        assert_eq!(Ok(("-k", " \\ \\\n ")), slash_line_ending(" \\ \\\n -k"));
+        assert_eq!(Ok(("", " \\   ")), slash_line_ending(" \\   "));
+        assert!(slash_line_ending(" -k").is_err());
+    }
+
+    #[test]
+    fn test_curl_cmd_parse_stray_backslashes() {
+        let expect = vec![new_curl!("-k"), new_curl!(-H, "A: 1")];
+        let inputs = [
+            "curl 'https://github.com' -k -H 'A: 1' \\",
+            "curl 'https://github.com' -k -H 'A: 1' \\  ",
+            "curl 'https://github.com' \\  -k \\ \\\n -H 'A: 1'",
+            "curl 'https://github.com' -k \\\\\n -H 'A: 1'\\",
+        ];
+        for input in inputs {
+            let (rest, curls) = curl_cmd_parse(input).unwrap();
+            assert_eq!(expect, curls[1..], "The input: {:?}", input);
+            assert_eq!("", rest, "The input: {:?}", input);
+        }
+    }
+
     #[test]
     fn test_is_curl() {
         let cmd = "\t \r  \n Curl asdjfnv\n";
-        assert!(is_curl(&cmd));
+        assert!(is_curl(cmd));
         let cmd = cmd.trim().to_uppercase();
         assert!(is_curl(&cmd));
     }
 
+    #[test]
+    fn test_curl_program_len() {
+        let cases = [
+            ("curl 'https://github.com'", Some(4)),
+            ("CURL", Some(4)),
+            ("curl.exe 'https://github.com'", Some(8)),
+            ("/usr/bin/curl -k", Some(13)),
+            (r"C:\tools\curl.exe -k", Some(17)),
+            (r"'C:\tools\curl.exe' -k", Some(19)),
+            (r#""C:\Program Files\curl\curl.exe" -k"#, Some(32)),
+            ("curling -k", None),
+            ("/usr/bin/curlx -k", None),
+            ("'curl -k", None),
+            ("wget 'https://github.com'", None),
+        ];
+        for (input, expect) in cases {
+            assert_eq!(expect, curl_program_len(input), "The input: {}", input);
+        }
+        assert_eq!(" -k", remove_curl_cmd_header("/usr/bin/curl -k"));
+        assert!(is_curl(" curl.exe -k"));
+    }
+
     #[test]
     fn test_remove_curl_cmd_headr() {
         let cmd = "\t \r  \n Curl asdjfnv\n".trim_start();
@@ -369,9 +662,9 @@ mod tests {
 
         let expect = Curl::new_as_url(expect_url);
         let input = format!(" curl \r \t   '{}' \\ \r\n-H 'Accept: */*'", full_url_str);
-        let input = remove_curl_cmd_header(&input.trim_start());
+        let input = remove_curl_cmd_header(input.trim_start());
 
-        generic_command_parse(url_parse, &input, expect);
+        generic_command_parse(url_parse, input, expect);
     }
 
     #[test]
@@ -434,10 +727,64 @@ mod tests {
     }
 
     #[test]
-    fn test_quoted_data_parse() {
+    fn test_word_parse() {
         let expect = " hhdf,\\fjsdfjl**''";
         let input = format!("\t \r  \n \n \"{}\" woaini \" \r \n 'nmihao'", expect);
-        generic_command_parse(quoted_data_parse, &input, expect);
+        generic_command_parse(word_parse, &input, Cow::from(expect));
+    }
+
+    #[test]
+    fn test_word_parse_other_quote() {
+        let expect = Cow::from(r#"say "hi""#);
+        generic_command_parse(word_parse, r#" 'say "hi"' -k"#, expect);
+        generic_command_parse(word_parse, r#""it's" -k"#, Cow::from("it's"));
+
+        let result = word_parse(" -k 'a'");
+        assert!(result.is_err(), "The result:\r\n{:#?}", result);
+        let result = word_parse("'unterminated");
+        assert!(result.is_err(), "The result:\r\n{:#?}", result);
+    }
+
+    #[test]
+    fn test_word_parse_escapes() {
+        let cases = [
+            (
+                r#"'{"name":"O'"'"'Brien"}' -k"#,
+                r#"{"name":"O'Brien"}"#,
+                "-k",
+            ),
+            (r#"'O'\''Brien' -k"#, "O'Brien", "-k"),
+            (r#""X: \"v\"" -k"#, r#"X: "v""#, "-k"),
+            (r#""a\\b\$c\d\`""#, r#"a\b$c\d`"#, ""),
+            ("'a'b\\\"c -k", "ab\"c", "-k"),
+            ("'a' 'b'", "a", "'b'"),
+            ("'a'|jq", "a", "|jq"),
+            ("'a' \\\n -k", "a", "\\\n -k"),
+            ("POST -k", "POST", "-k"),
+            ("a=1&b=2", "a=1", "&b=2"),
+            ("Accept:'*/*' -k", "Accept:*/*", "-k"),
+        ];
+        for (input, expect, rest) in cases {
+            assert_eq!(
+                Ok((rest, Cow::from(expect))),
+                word_parse(input),
+                "The input: {}",
+                input
+            );
+        }
+
+        // A value written as is stays borrowed
+        let (_, data) = word_parse("'a\\b' -k").unwrap();
+        assert!(matches!(data, Cow::Borrowed("a\\b")));
+        assert!(word_parse(r#"'a'"b -k"#).is_err());
+        assert!(word_parse(r#""a\" -k"#).is_err());
+    }
+
+    #[test]
+    fn test_word_parse_large_payload() {
+        let payload = "é,{\"a\":1}\n".repeat(100_000);
+        let input = format!("'{}' -k", payload);
+        generic_command_parse(word_parse, &input, Cow::from(payload.as_str()));
     }
 
     #[test]
@@ -472,15 +819,29 @@ mod tests {
 
     #[test]
     fn test_method_parse() {
-        let expect = new_curl!(-X, "AJFjfdslf");
+        let expect = new_curl!(-X, "AJFJFDSLF");
         let input = "\t \r  \n -X \"AJFjfdslf\" HHH -H \"llol:90\"";
         generic_command_parse(method_parse, input, expect);
+
+        let expect = new_curl!(-X, "POST");
+        generic_command_parse(method_parse, " -XPOST 'https://github.com'", expect.clone());
+        generic_command_parse(method_parse, " \\\n -X'POST' -k", expect.clone());
+        generic_command_parse(method_parse, " -X 'post'", expect.clone());
+        generic_command_parse(method_parse, " --request PUT -k", new_curl!(-X, "PUT"));
+        assert!(method_parse(" --request-target '*'").is_err());
+        // An invalid method is kept as it's written, for the lint to report it
+        let expect = new_curl!(-X, "https://github.com");
+        generic_command_parse(method_parse, " -X 'https://github.com'", expect);
+        let expect = new_curl!(-X, "POST");
+        assert_eq!(Ok(("", expect)), method_parse("-XPOST"));
+        assert_eq!("-X 'POST'", new_curl!(-X, "POST").to_string());
+        assert!(method_parse("-X").is_err());
     }
 
     #[test]
     fn test_methods_parse() {
         let expect = vec![
-            new_curl!(-X, "AJFjfdslf"),
+            new_curl!(-X, "AJFJFDSLF"),
             new_curl!(-X, "abc fjdfl  ii\\hhfjsdkf:90"),
         ];
         let input = "\t \r  \n -X \"AJFjfdslf\" -X \"abc fjdfl  ii\\hhfjsdkf:90\" \t\r jflksfl";
@@ -511,6 +872,51 @@ mod tests {
         generic_command_parse(flag_parse, input, expect);
     }
 
+    #[test]
+    fn test_flag_with_value_parse() {
+        let expect = Curl::Flag(CurlStru::new_with_data("--max-time", "10"));
+        let input = " \\\n --max-time '10' -k";
+        generic_command_parse(flag_parse, input, expect);
+
+        let expect = vec![
+            Curl::Flag(CurlStru::new_with_data("-u", "user:passwd")),
+            new_curl!("--http1.1"),
+            new_curl!("-o"),
+        ];
+        let input = " -u \"user:passwd\" --http1.1 -o";
+        generic_command_parse(flags_parse, input, expect);
+
+        // The word is the next param, e.g. the url
+        let result = flag_parse(" --insecure 'https://github.com'");
+        assert_eq!(
+            Ok((" 'https://github.com'", new_curl!("--insecure"))),
+            result
+        );
+        let result = flag_parse(" --not-an-option 'not a value'");
+        assert!(result.is_err(), "The result:\r\n{:#?}", result);
+    }
+
+    #[test]
+    fn test_dash_value_parse() {
+        let expect = vec![
+            Curl::Flag(CurlStru::new_with_data("-o", "-")),
+            Curl::Flag(CurlStru::new_with_data("--max-time", "-1")),
+            Curl::Flag(CurlStru::new_with_data("--data-raw", "--boundary--")),
+            new_curl!("-k"),
+        ];
+        let input = " -o - --max-time -1 \\\n --data-raw '--boundary--' -k";
+        generic_command_parse(flags_parse, input, expect);
+
+        let (_, curls) =
+            curl_cmd_parse("curl 'https://a.com' -d '-5' -H 'X-Count: -1' -H \"-X: 1\"").unwrap();
+        let expect = vec![
+            Curl::new("-d", "-5").unwrap(),
+            Curl::new("-H", "X-Count: -1").unwrap(),
+            Curl::new("-H", "-X: 1").unwrap(),
+        ];
+        assert_eq!(expect, curls[1..]);
+    }
+
     #[test]
     fn test_flags_parse() {
         let expect = vec![new_curl!("--help"), new_curl!("-a")];
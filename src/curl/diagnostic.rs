@@ -0,0 +1,159 @@
+use std::fmt;
+
+use miette::{Diagnostic, LabeledSpan};
+
+use super::{
+    error::{ParseError, TokenKind},
+    lint::{Finding, Severity},
+    method,
+    span::Span,
+};
+
+/// The parse error as a miette diagnostic, a syntax error labels the token the parser stopped
+/// on. Give the parsed command as the source code of the report to render it.
+impl Diagnostic for ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(format!("nomcurl::{}", self.code())))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let help = match self.root() {
+            ParseError::NotCurl => "a command starts with curl",
+            ParseError::MissingUrl => "quote the url, e.g. curl 'https://github.com'",
+            ParseError::BareUrl(url) => {
+                return Some(Box::new(format!("quote the url, e.g. curl '{}'", url)));
+            }
+            ParseError::Syntax(e) if e.token == TokenKind::Value => {
+                "quote the value with ' or \", e.g. -H 'Accept: */*'"
+            }
+            ParseError::UnsupportedDialect(hint) => hint.sign.suggestion(),
+            ParseError::InvalidMethod(m) if method::is_url_like(m) => {
+                "give the url on its own, e.g. curl -X 'POST' 'https://github.com'"
+            }
+            ParseError::InvalidMethod(_) => "a method is a single word, e.g. -X 'PUT'",
+            _ => return None,
+        };
+        Some(Box::new(help))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = match self.root() {
+            ParseError::Syntax(e) => {
+                let label = format!("expected {}", e.expected);
+                LabeledSpan::new(Some(label), e.offset, e.found.len())
+            }
+            ParseError::UnsupportedDialect(hint) => {
+                let label = format!("{}", hint.sign);
+                LabeledSpan::new(Some(label), hint.offset, hint.len)
+            }
+            _ => return None,
+        };
+        Some(Box::new(std::iter::once(span)))
+    }
+}
+
+/// A lint finding with the span of the param it's about, see `span::curl_cmd_spans`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedFinding {
+    pub finding: Finding,
+    pub span: Option<Span>,
+}
+
+impl LocatedFinding {
+    /// Locate every finding with the spans of the command params.
+    pub fn locate(findings: Vec<Finding>, spans: &[Span]) -> Vec<Self> {
+        findings
+            .into_iter()
+            .map(|finding| Self {
+                span: finding.param.and_then(|i| spans.get(i)).copied(),
+                finding,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for LocatedFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.finding.message)
+    }
+}
+
+impl std::error::Error for LocatedFinding {}
+
+impl Diagnostic for LocatedFinding {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(format!("nomcurl::{}", self.finding.lint.code())))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.finding.severity {
+            Severity::Info => miette::Severity::Advice,
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Error => miette::Severity::Error,
+        })
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = self.span?;
+        let label = LabeledSpan::new(Some("this param".into()), span.start, span.end - span.start);
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::{request::parse_curl_command, span::curl_cmd_spans};
+
+    /// The report with the command as its source, rendered without colors to compare it as text.
+    fn render(diagnostic: impl Diagnostic + Send + Sync + 'static, source: &str) -> String {
+        let report = miette::Report::new(diagnostic).with_source_code(source.to_string());
+        let handler = miette::GraphicalReportHandler::new_themed(miette::GraphicalTheme::none());
+        let mut out = String::new();
+        handler.render_report(&mut out, report.as_ref()).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_parse_error_diagnostic() {
+        let input = "curl 'https://github.com' -H |oops";
+        let err = parse_curl_command(input).unwrap_err();
+
+        let report = render(err.clone(), input);
+        assert!(report.contains("nomcurl::bad-quoting"), "{}", report);
+        assert!(report.contains("-H |oops"), "{}", report);
+        assert!(report.contains("expected a quoted value"), "{}", report);
+        assert!(report.contains("help: quote the value"), "{}", report);
+
+        let labels: Vec<LabeledSpan> = err.labels().unwrap().collect();
+        assert_eq!(input.find("|oops"), Some(labels[0].offset()));
+        assert_eq!(5, labels[0].len());
+        assert!(ParseError::NotCurl.labels().is_none());
+    }
+
+    #[test]
+    fn test_invalid_method_diagnostic() {
+        let input = "curl -X 'https://github.com'";
+        let err = parse_curl_command(input).unwrap_err();
+        let report = render(err, input);
+        assert!(report.contains("nomcurl::invalid-method"), "{}", report);
+        assert!(
+            report.contains("help: give the url on its own"),
+            "{}",
+            report
+        );
+    }
+
+    #[test]
+    fn test_located_finding_diagnostic() {
+        let input = "curl 'https://github.com' -k";
+        let request = parse_curl_command(input).unwrap();
+        let spans = curl_cmd_spans(input).unwrap();
+        let findings = LocatedFinding::locate(request.lint(), &spans);
+
+        assert_eq!(Some(miette::Severity::Warning), findings[0].severity());
+        let report = render(findings[0].clone(), input);
+        assert!(report.contains("nomcurl::insecure"), "{}", report);
+        assert!(report.contains("this param"), "{}", report);
+    }
+}
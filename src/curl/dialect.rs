@@ -0,0 +1,241 @@
+//! Signs in a command of a shell dialect the parsers don't read, e.g. a command copied for
+//! cmd.exe, to read it once normalised or tell the user instead of failing on a quote.
+
+use core::fmt;
+
+use super::error::lines_inclusive;
+use crate::prelude::*;
+use crate::shell_quote::Shell;
+
+/// A quoting or line continuation of another shell than the POSIX quoting the parsers read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialectSign {
+    /// A `^"` escape or a `^` line continuation of cmd.exe.
+    Caret,
+    /// A `` `" `` escape or a `` ` `` line continuation of PowerShell.
+    Backtick,
+    /// A `$'...'` ANSI-C quoted string of bash.
+    AnsiCQuote,
+}
+
+impl DialectSign {
+    /// The shell the sign is written for.
+    pub fn shell(self) -> Shell {
+        match self {
+            DialectSign::Caret => Shell::Cmd,
+            DialectSign::Backtick => Shell::PowerShell,
+            DialectSign::AnsiCQuote => Shell::Posix,
+        }
+    }
+
+    /// How to write the command so it's read.
+    pub fn suggestion(self) -> &'static str {
+        match self {
+            DialectSign::Caret | DialectSign::Backtick => {
+                "copy the command for bash, e.g. with \"Copy as cURL (bash)\""
+            }
+            DialectSign::AnsiCQuote => "write the value in single quotes",
+        }
+    }
+}
+
+impl fmt::Display for DialectSign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialectSign::Caret => write!(f, "a cmd.exe ^ escape"),
+            DialectSign::Backtick => write!(f, "a PowerShell ` escape"),
+            DialectSign::AnsiCQuote => write!(f, "a bash $'...' string"),
+        }
+    }
+}
+
+/// The first sign of another dialect in a command, found by `detect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DialectHint {
+    pub sign: DialectSign,
+    /// The byte offset of the sign in the command.
+    pub offset: usize,
+    /// The byte length of the sign, e.g. 2 for `^"`.
+    pub len: usize,
+}
+
+/// E.g. `the command has a cmd.exe ^ escape, copy the command for bash, ...`.
+impl fmt::Display for DialectHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the command has {}, {}",
+            self.sign,
+            self.sign.suggestion()
+        )
+    }
+}
+
+/// The first sign of another dialect than the POSIX quoting in the command, if any.
+///
+/// Quoted values may hold the same characters, so it's only a hint for a command which fails to
+/// parse, see `request::parse_curl_command`.
+pub fn detect(command: &str) -> Option<DialectHint> {
+    let mut line_start = 0;
+    for line in lines_inclusive(command) {
+        let content = line.trim_end();
+        let continuation = match content.chars().last() {
+            Some('^') => Some(DialectSign::Caret),
+            Some('`') => Some(DialectSign::Backtick),
+            _ => None,
+        };
+        let escapes = [
+            ("^\"", DialectSign::Caret),
+            ("`\"", DialectSign::Backtick),
+            ("$'", DialectSign::AnsiCQuote),
+        ];
+        let escape = escapes
+            .iter()
+            .filter_map(|(pattern, sign)| Some((content.find(pattern)?, pattern.len(), *sign)))
+            .min_by_key(|(offset, ..)| *offset);
+        let hint = match (escape, continuation) {
+            (Some((offset, len, sign)), _) => Some((offset, len, sign)),
+            (None, Some(sign)) => Some((content.len() - 1, 1, sign)),
+            (None, None) => None,
+        };
+        if let Some((offset, len, sign)) = hint {
+            return Some(DialectHint {
+                sign,
+                offset: line_start + offset,
+                len,
+            });
+        }
+        line_start += line.len();
+    }
+    None
+}
+
+/// The command written with the POSIX quoting of the sign's shell, `None` for a bash `$'...'`
+/// string which isn't normalised.
+///
+/// A cmd.exe `^` escapes the next char, e.g. `^"` and `^&`, and a PowerShell `` `" `` is a
+/// `\"`. A `^` or `` ` `` ending a line or the command is a line continuation, written `\`.
+pub fn normalize(command: &str, sign: DialectSign) -> Option<String> {
+    let escape = match sign {
+        DialectSign::Caret => '^',
+        DialectSign::Backtick => '`',
+        DialectSign::AnsiCQuote => return None,
+    };
+    let mut normalized = String::with_capacity(command.len());
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != escape {
+            normalized.push(c);
+            continue;
+        }
+        match (sign, chars.peek()) {
+            (_, Some('\r' | '\n') | None) | (DialectSign::Backtick, Some('"')) => {
+                normalized.push('\\')
+            }
+            (DialectSign::Caret, Some(_)) => normalized.extend(chars.next()),
+            _ => normalized.push(c),
+        }
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::{
+        error::ErrorCode, recover::parse_curl_command_lenient, request::parse_curl_command,
+    };
+
+    #[test]
+    fn test_detect() {
+        let input = "curl ^\"https://github.com^\" ^\n  -H ^\"Accept: */*^\"";
+        let hint = detect(input).unwrap();
+        assert_eq!(DialectSign::Caret, hint.sign);
+        assert_eq!((5, 2), (hint.offset, hint.len));
+        assert_eq!(Shell::Cmd, hint.sign.shell());
+
+        let input = "curl 'https://github.com' `\n  -H 'Accept: */*'";
+        let hint = detect(input).unwrap();
+        assert_eq!(DialectSign::Backtick, hint.sign);
+        assert_eq!(input.find('`'), Some(hint.offset));
+
+        let input = "curl 'https://github.com' \\\n  -d $'a\\nb'";
+        let hint = detect(input).unwrap();
+        assert_eq!(DialectSign::AnsiCQuote, hint.sign);
+        assert_eq!(input.find('$'), Some(hint.offset));
+        assert_eq!(
+            "the command has a bash $'...' string, write the value in single quotes",
+            hint.to_string()
+        );
+
+        assert_eq!(None, detect("curl 'https://github.com' \\\n  -k"));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let input = "curl ^\"https://github.com/?a=1^&b=2^\" ^\r\n  -d ^\"{\\^\"a\\^\":1}^\"";
+        assert_eq!(
+            Some("curl \"https://github.com/?a=1&b=2\" \\\r\n  -d \"{\\\"a\\\":1}\"".to_string()),
+            normalize(input, DialectSign::Caret)
+        );
+
+        let input = "curl 'https://github.com' `\n  -H \"X-A: `\"v`\"\"";
+        assert_eq!(
+            Some("curl 'https://github.com' \\\n  -H \"X-A: \\\"v\\\"\"".to_string()),
+            normalize(input, DialectSign::Backtick)
+        );
+
+        assert_eq!(None, normalize("curl $'a'", DialectSign::AnsiCQuote));
+    }
+
+    #[test]
+    fn test_parse_normalized_dialect() {
+        let input = "curl ^\"https://github.com/?a=1^&b=2^\" ^\n  -H ^\"Accept: */*^\" ^\n  -d ^\"{\\^\"a\\^\":1}^\"";
+        let request = parse_curl_command(input).unwrap();
+        assert_eq!(
+            "curl 'https://github.com/?a=1&b=2' -H 'Accept: */*' -d '{\"a\":1}'",
+            request.to_string()
+        );
+        let recovered = parse_curl_command_lenient(input);
+        assert_eq!(Ok(request), recovered.into_request());
+
+        let input = "curl 'https://github.com' `\n  -H 'Accept: */*' `\n  -k";
+        assert_eq!(
+            "curl 'https://github.com' -H 'Accept: */*' -k",
+            parse_curl_command(input).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_unsupported_dialect() {
+        // Still unclosed once normalised
+        let input = "curl ^\"https://github.com^\" ^\n  -H ^\"Accept: */*";
+        let err = parse_curl_command(input).unwrap_err();
+        assert_eq!(ErrorCode::UnsupportedDialect, err.code());
+        assert_eq!(
+            "the command has a cmd.exe ^ escape, copy the command for bash, e.g. with \"Copy as cURL (bash)\"",
+            err.to_string()
+        );
+
+        let recovered = parse_curl_command_lenient(input);
+        assert_eq!(ErrorCode::UnsupportedDialect, recovered.errors[0].code());
+
+        // A command which parses is kept, whatever its values hold
+        assert!(parse_curl_command("curl 'https://github.com' -d '^\"a'").is_ok());
+
+        // A sign ending the command is a continuation, the stray word stays an error
+        for input in ["curl 'https://a.com' oops ^", "curl 'https://a.com' oops `"] {
+            let err = parse_curl_command(input).unwrap_err();
+            assert_eq!(ErrorCode::UnsupportedDialect, err.code(), "{}", input);
+            let recovered = parse_curl_command_lenient(input);
+            assert_eq!(ErrorCode::UnsupportedDialect, recovered.errors[0].code());
+        }
+        for input in ["curl 'https://a.com' -k ^", "curl 'https://a.com' -k `"] {
+            assert_eq!(
+                "curl 'https://a.com' -k",
+                parse_curl_command(input).unwrap().to_string()
+            );
+            assert!(parse_curl_command_lenient(input).is_valid(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use serde::Serialize;
+use std::fmt;
+
+use super::{options, request::ParsedRequest};
+
+/// The part of the request a `Change` is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    Url,
+    Method,
+    Header,
+    Query,
+    Body,
+    Flag,
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Section::Url => write!(f, "url"),
+            Section::Method => write!(f, "method"),
+            Section::Header => write!(f, "header"),
+            Section::Query => write!(f, "query"),
+            Section::Body => write!(f, "body"),
+            Section::Flag => write!(f, "flag"),
+        }
+    }
+}
+
+/// A difference between two requests, `before` is `None` for an addition and `after` for a removal.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Change {
+    pub section: Section,
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl fmt::Display for Change {
+    /// `+`, `-` or `~` followed by the section, the name and the value(s).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = match (&self.before, &self.after) {
+            (None, _) => '+',
+            (_, None) => '-',
+            _ => '~',
+        };
+        write!(f, "{} {}", sign, self.section)?;
+        // The url, method and body are single entries named after their section
+        if self.name != self.section.to_string() {
+            write!(f, " {}", self.name)?;
+        }
+
+        let values: Vec<&str> = [&self.before, &self.after]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        if values.iter().any(|v| !v.is_empty()) {
+            write!(f, ": {}", values.join(" -> "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The structured comparison of two requests: url, method, headers, queries, body and flags.
+///
+/// Headers are compared case-insensitively and flags under their long name, so `-k` and
+/// `--insecure` are the same flag.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RequestDiff {
+    pub changes: Vec<Change>,
+}
+
+impl RequestDiff {
+    pub fn new(before: &ParsedRequest, after: &ParsedRequest) -> Self {
+        let mut changes = Vec::new();
+
+        let url = |r: &ParsedRequest| vec![("url".to_string(), base_url(r))];
+        diff_entries(Section::Url, url(before), url(after), &mut changes);
+
+        let method =
+            |r: &ParsedRequest| vec![("method".to_string(), r.effective_method().to_string())];
+        diff_entries(Section::Method, method(before), method(after), &mut changes);
+
+        diff_entries(
+            Section::Header,
+            headers(before),
+            headers(after),
+            &mut changes,
+        );
+
+        let queries = |r: &ParsedRequest| {
+            let queries = r.url().query_pairs();
+            queries.map(|(k, v)| (k.into(), v.into())).collect()
+        };
+        diff_entries(
+            Section::Query,
+            queries(before),
+            queries(after),
+            &mut changes,
+        );
+
+        let body = |r: &ParsedRequest| match r.data() {
+            data if data.is_empty() => vec![],
+            data => vec![("body".to_string(), data.join("&"))],
+        };
+        diff_entries(Section::Body, body(before), body(after), &mut changes);
+
+        diff_entries(Section::Flag, flags(before), flags(after), &mut changes);
+
+        Self { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for RequestDiff {
+    /// One change per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// The url without its queries, which are compared one by one.
+fn base_url(request: &ParsedRequest) -> String {
+    let mut url = request.url().clone();
+    url.queries = None;
+    url.to_string()
+}
+
+/// The headers by lower-cased name, the values of a repeated header are joined by `, `.
+fn headers(request: &ParsedRequest) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for header in request.headers() {
+        let (name, value) = header.split_once(':').unwrap_or((header, ""));
+        let (name, value) = (name.trim().to_ascii_lowercase(), value.trim());
+        match headers.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, values)) => {
+                values.push_str(", ");
+                values.push_str(value);
+            }
+            None => headers.push((name, value.into())),
+        }
+    }
+    headers
+}
+
+/// The flags by long name, the `-d` like ones are part of the body.
+fn flags(request: &ParsedRequest) -> Vec<(String, String)> {
+    request
+        .flags()
+        .filter(|f| !options::is_data_option(&f.identifier))
+        .map(|f| {
+            let name = options::lookup(&f.identifier).map_or(f.identifier.as_ref(), |o| o.name);
+            (name.into(), f.data.as_deref().unwrap_or_default().into())
+        })
+        .collect()
+}
+
+/// Compare the entries by name: removed and changed ones in the `before` order, then added ones.
+fn diff_entries(
+    section: Section,
+    before: Vec<(String, String)>,
+    after: Vec<(String, String)>,
+    changes: &mut Vec<Change>,
+) {
+    for (name, value) in &before {
+        match after.iter().find(|(n, _)| n == name) {
+            Some((_, v)) if v == value => {}
+            other => changes.push(Change {
+                section,
+                name: name.clone(),
+                before: Some(value.clone()),
+                after: other.map(|(_, v)| v.clone()),
+            }),
+        }
+    }
+    for (name, value) in after {
+        if !before.iter().any(|(n, _)| *n == name) {
+            changes.push(Change {
+                section,
+                name,
+                before: None,
+                after: Some(value),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_request_diff() {
+        let before = parse_curl_command(
+            "curl 'https://github.com/a?page=1&q=x' -H 'Accept: */*' -H 'X-A: 1' -d 'a=1' -k",
+        )
+        .unwrap();
+        let after = parse_curl_command(
+            "curl 'https://github.com/b?page=2&q=x' -X 'PUT' -H 'accept: */*' -H 'X-B: 2' -d 'a=1' --insecure -L",
+        )
+        .unwrap();
+        let expect = "\
+~ url: https://github.com/a -> https://github.com/b
+~ method: POST -> PUT
+- header x-a: 1
++ header x-b: 2
+~ query page: 1 -> 2
++ flag --location
+";
+        let diff = RequestDiff::new(&before, &after);
+        assert_eq!(expect, diff.to_string());
+        assert!(RequestDiff::new(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_request_diff_json() {
+        let before = parse_curl_command("curl 'https://github.com' -d 'a=1'").unwrap();
+        let after = parse_curl_command("curl 'https://github.com'").unwrap();
+        let value = serde_json::to_value(before.diff(&after)).unwrap();
+
+        let expect = serde_json::json!([
+            {"section": "method", "name": "method", "before": "POST", "after": "GET"},
+            {"section": "body", "name": "body", "before": "a=1", "after": null},
+        ]);
+        assert_eq!(expect, value["changes"]);
+    }
+}
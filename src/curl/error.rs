@@ -0,0 +1,409 @@
+use core::fmt;
+use serde::Serialize;
+
+use super::{curl_parsers::NO_URL_FOUND, dialect::DialectHint, Curl};
+use crate::prelude::*;
+
+/// The error returned when a curl command can't be turned into a `ParsedRequest`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The input doesn't start with a `curl` invocation.
+    NotCurl,
+    /// The command has no target url.
+    MissingUrl,
+    /// The command has no target url, but a bare word looks like one, e.g. an unquoted
+    /// `example.com/api`. It holds the url to quote, with a scheme.
+    BareUrl(String),
+    /// The value of `-X` isn't a valid HTTP method.
+    InvalidMethod(String),
+    /// The raw HTTP request text is malformed.
+    InvalidHttp(String),
+    /// The HAR file isn't a valid `log.entries` JSON document.
+    InvalidHar(String),
+    /// The url scheme isn't a known `Protocol`, see `url_parser::ProtocolPolicy`.
+    UnknownProtocol,
+    /// The `--query` expression isn't a valid path.
+    InvalidQuery(String),
+    /// A `KEY=VALUE` variable definition is malformed.
+    InvalidVariable(String),
+    /// The command fails to parse and has signs of another shell dialect, see `dialect::detect`.
+    UnsupportedDialect(DialectHint),
+    /// The nom parsers stopped on a token, see `SyntaxError`.
+    Syntax(SyntaxError),
+    /// The error along with the params parsed before it, see
+    /// `recover::parse_curl_command_partial`.
+    Partial {
+        curls: Vec<Curl<'static>>,
+        error: Box<ParseError>,
+    },
+}
+
+/// The class of a `ParseError`, a stable kebab-case code for tools matching on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorCode {
+    /// The input doesn't start with a `curl` invocation.
+    NotCurl,
+    /// The command has no target url.
+    MissingUrl,
+    /// A dash-prefixed token which isn't a flag.
+    UnknownFlag,
+    /// A param with no value left after it.
+    MissingFlagValue,
+    /// A value with no quotes, or no closing one.
+    BadQuoting,
+    /// The target url can't be read.
+    BadUrl,
+    /// A token which is neither a param nor the url.
+    UnexpectedToken,
+    UnknownProtocol,
+    UnsupportedDialect,
+    InvalidMethod,
+    InvalidHttp,
+    InvalidHar,
+    InvalidQuery,
+    InvalidVariable,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 14] = [
+        ErrorCode::NotCurl,
+        ErrorCode::MissingUrl,
+        ErrorCode::UnknownFlag,
+        ErrorCode::MissingFlagValue,
+        ErrorCode::BadQuoting,
+        ErrorCode::BadUrl,
+        ErrorCode::UnexpectedToken,
+        ErrorCode::UnknownProtocol,
+        ErrorCode::UnsupportedDialect,
+        ErrorCode::InvalidMethod,
+        ErrorCode::InvalidHttp,
+        ErrorCode::InvalidHar,
+        ErrorCode::InvalidQuery,
+        ErrorCode::InvalidVariable,
+    ];
+
+    /// The code as serialized, e.g. `missing-url`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::NotCurl => "not-curl",
+            ErrorCode::MissingUrl => "missing-url",
+            ErrorCode::UnknownFlag => "unknown-flag",
+            ErrorCode::MissingFlagValue => "missing-flag-value",
+            ErrorCode::BadQuoting => "bad-quoting",
+            ErrorCode::BadUrl => "bad-url",
+            ErrorCode::UnexpectedToken => "unexpected-token",
+            ErrorCode::UnknownProtocol => "unknown-protocol",
+            ErrorCode::UnsupportedDialect => "unsupported-dialect",
+            ErrorCode::InvalidMethod => "invalid-method",
+            ErrorCode::InvalidHttp => "invalid-http",
+            ErrorCode::InvalidHar => "invalid-har",
+            ErrorCode::InvalidQuery => "invalid-query",
+            ErrorCode::InvalidVariable => "invalid-variable",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The kind of token the parser was reading when it stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// The target url of the command.
+    Url,
+    /// A `-X`, `-H`, `-d` or flag param.
+    Param,
+    /// The quoted value of a param.
+    Value,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Url => write!(f, "url"),
+            TokenKind::Param => write!(f, "param"),
+            TokenKind::Value => write!(f, "param value"),
+        }
+    }
+}
+
+/// Where the parser stopped in its input, and what it expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub token: TokenKind,
+    /// What the parser expected, e.g. `a closing quote`.
+    pub expected: &'static str,
+    /// The whitespace-delimited text at the offset, empty at the end of the input.
+    pub found: String,
+    /// The flag whose value was being parsed, e.g. `--data-urlencode`.
+    pub flag: Option<String>,
+    /// The byte offset in the input.
+    pub offset: usize,
+    /// The 1-based line of the offset.
+    pub line: usize,
+    /// The 1-based column of the offset, in chars.
+    pub column: usize,
+}
+
+impl SyntaxError {
+    /// The error at the `rest` suffix of the input, the end of the input if it isn't one.
+    pub fn at(input: &str, rest: &str, token: TokenKind, expected: &'static str) -> Self {
+        let offset = suffix_offset(input, rest).unwrap_or(input.len());
+        let found = &input[offset..];
+        let found = &found[..found.find(char::is_whitespace).unwrap_or(found.len())];
+        let (line, column) = line_col(input, offset);
+        let flag = match token {
+            TokenKind::Value => last_token(&input[..offset]).filter(|t| t.starts_with('-')),
+            _ => None,
+        };
+        Self {
+            token,
+            expected,
+            found: found.into(),
+            flag: flag.map(Into::into),
+            offset,
+            line,
+            column,
+        }
+    }
+
+    /// The code of the error, told by the token and the text found.
+    pub fn code(&self) -> ErrorCode {
+        match self.token {
+            TokenKind::Url => ErrorCode::BadUrl,
+            TokenKind::Value if self.found.is_empty() => ErrorCode::MissingFlagValue,
+            TokenKind::Value => ErrorCode::BadQuoting,
+            TokenKind::Param if self.found.starts_with('-') => ErrorCode::UnknownFlag,
+            TokenKind::Param => ErrorCode::UnexpectedToken,
+        }
+    }
+}
+
+/// E.g. `invalid value of -H at line 2, column 6: expected a quoted value, found "oops"`, the
+/// alternate `{:#}` leaves the line and column out for a caller reporting them apart.
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.flag {
+            Some(flag) => write!(f, "invalid value of {}", flag)?,
+            None => write!(f, "invalid {}", self.token)?,
+        }
+        if !f.alternate() {
+            write!(f, " at line {}, column {}", self.line, self.column)?;
+        }
+        write!(f, ": expected {}, found ", self.expected)?;
+        match self.found.is_empty() {
+            true => write!(f, "the end of the input"),
+            false => write!(f, "{:?}", self.found),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NotCurl => write!(f, "input is not a curl command"),
+            ParseError::MissingUrl => write!(f, "no target url found"),
+            ParseError::BareUrl(url) => {
+                write!(f, "no target url found, did you mean '{}'?", url)
+            }
+            ParseError::InvalidMethod(m) => write!(f, "invalid request method: {:?}", m),
+            ParseError::InvalidHttp(msg) => write!(f, "invalid HTTP request: {}", msg),
+            ParseError::InvalidHar(msg) => write!(f, "invalid HAR file: {}", msg),
+            ParseError::UnknownProtocol => {
+                write!(f, "unknown url scheme, expected http, https, ftp or smb")
+            }
+            ParseError::InvalidQuery(q) => write!(f, "invalid query: {:?}", q),
+            ParseError::InvalidVariable(v) => write!(f, "invalid variable definition: {:?}", v),
+            ParseError::UnsupportedDialect(hint) => write!(f, "{}", hint),
+            ParseError::Syntax(e) => fmt::Display::fmt(e, f),
+            ParseError::Partial { error, .. } => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ParseError::NotCurl => ErrorCode::NotCurl,
+            ParseError::MissingUrl | ParseError::BareUrl(_) => ErrorCode::MissingUrl,
+            ParseError::InvalidMethod(_) => ErrorCode::InvalidMethod,
+            ParseError::InvalidHttp(_) => ErrorCode::InvalidHttp,
+            ParseError::InvalidHar(_) => ErrorCode::InvalidHar,
+            ParseError::UnknownProtocol => ErrorCode::UnknownProtocol,
+            ParseError::UnsupportedDialect(_) => ErrorCode::UnsupportedDialect,
+            ParseError::InvalidQuery(_) => ErrorCode::InvalidQuery,
+            ParseError::InvalidVariable(_) => ErrorCode::InvalidVariable,
+            ParseError::Syntax(e) => e.code(),
+            ParseError::Partial { error, .. } => error.code(),
+        }
+    }
+
+    /// The error itself, out of a `Partial` one.
+    pub fn root(&self) -> &ParseError {
+        match self {
+            ParseError::Partial { error, .. } => error.root(),
+            _ => self,
+        }
+    }
+
+    /// The params parsed before the error, empty but for a `Partial` one.
+    pub fn partial_curls(&self) -> &[Curl<'static>] {
+        match self {
+            ParseError::Partial { curls, .. } => curls,
+            _ => &[],
+        }
+    }
+
+    /// The error of a nom parser run on the input, which was reading the token when it stopped.
+    pub fn from_nom(input: &str, token: TokenKind, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        let rest = match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) if e.input == NO_URL_FOUND => {
+                return ParseError::MissingUrl;
+            }
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.input,
+            nom::Err::Incomplete(_) => "",
+        };
+        let unclosed = rest.trim_start().starts_with(['\'', '"']);
+        let expected = match token {
+            TokenKind::Url => "a url, e.g. https://github.com",
+            TokenKind::Param => "a param, e.g. -H 'Accept: */*'",
+            TokenKind::Value if unclosed => "a closing quote",
+            TokenKind::Value => "a quoted value",
+        };
+        let rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '\\');
+        ParseError::Syntax(SyntaxError::at(input, rest, token, expected))
+    }
+}
+
+/// The offset of the `rest` slice in the input, `None` if it isn't a suffix of it.
+fn suffix_offset(input: &str, rest: &str) -> Option<usize> {
+    let offset = (rest.as_ptr() as usize).checked_sub(input.as_ptr() as usize)?;
+    (offset + rest.len() == input.len()).then_some(offset)
+}
+
+/// The last whitespace-delimited token of the text, line continuations skipped.
+fn last_token(text: &str) -> Option<&str> {
+    let text = text.trim_end_matches(|c: char| c.is_whitespace() || c == '\\');
+    text.rsplit(char::is_whitespace)
+        .next()
+        .filter(|t| !t.is_empty())
+}
+
+/// The 1-based line and column, in chars, of the byte offset in the input.
+pub(crate) fn line_col(input: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut line_start = 0;
+    for text in lines_inclusive(input) {
+        if line_start + text.len() > offset || !text.ends_with(['\r', '\n']) {
+            break;
+        }
+        line += 1;
+        line_start += text.len();
+    }
+    (line, input[line_start..offset].chars().count() + 1)
+}
+
+/// The lines of the input with their ending, a `\n`, a `\r\n` or a bare `\r` as commands copied
+/// on Windows or old Macs mix them.
+pub(crate) fn lines_inclusive(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    core::iter::from_fn(move || {
+        let end = match rest.find(['\r', '\n']) {
+            _ if rest.is_empty() => return None,
+            Some(i) if rest[i..].starts_with("\r\n") => i + 2,
+            Some(i) => i + 1,
+            None => rest.len(),
+        };
+        let (line, after) = rest.split_at(end);
+        rest = after;
+        Some(line)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_syntax_error() {
+        let input = "curl 'https://github.com' \\\n  -H |oops -k";
+        let err = parse_curl_command(input).unwrap_err();
+
+        let ParseError::Syntax(e) = &err else {
+            panic!("The error:\r\n{:#?}", err);
+        };
+        assert_eq!(TokenKind::Value, e.token);
+        assert_eq!("|oops", e.found);
+        assert_eq!(Some("-H"), e.flag.as_deref());
+        assert_eq!(input.find("|oops"), Some(e.offset));
+        assert_eq!((2, 6), (e.line, e.column));
+        assert_eq!(
+            "invalid value of -H at line 2, column 6: expected a quoted value, found \"|oops\"",
+            err.to_string()
+        );
+
+        let err = parse_curl_command("curl 'https://github.com' -d 'a=1").unwrap_err();
+        assert_eq!(
+            "invalid value of -d at line 1, column 30: expected a closing quote, found \"'a=1\"",
+            err.to_string()
+        );
+        let err = parse_curl_command("curl 'https://github.com' -X ").unwrap_err();
+        assert_eq!(
+            "invalid value of -X at line 1, column 30: expected a quoted value, found the end of the input",
+            err.to_string()
+        );
+        assert_eq!(
+            "invalid value of -X: expected a quoted value, found the end of the input",
+            format!("{:#}", err)
+        );
+
+        let input = "curl 'https://github.com' \\\n  --data-urlencode 'a=1";
+        let err = parse_curl_command(input).unwrap_err();
+        assert_eq!(
+            "invalid value of --data-urlencode at line 2, column 20: expected a closing quote, found \"'a=1\"",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_error_code() {
+        let code = |input| parse_curl_command(input).unwrap_err().code();
+        assert_eq!(ErrorCode::NotCurl, code("wget 'https://github.com'"));
+        assert_eq!(ErrorCode::MissingUrl, code("curl -k"));
+        assert_eq!(ErrorCode::MissingUrl, code("curl -k example.com"));
+        assert_eq!(
+            ErrorCode::BadQuoting,
+            code("curl 'https://github.com' -H |oops")
+        );
+        assert_eq!(
+            ErrorCode::BadQuoting,
+            code("curl 'https://github.com' -d 'a=1")
+        );
+        assert_eq!(
+            ErrorCode::MissingFlagValue,
+            code("curl 'https://github.com' -X ")
+        );
+        assert_eq!(
+            ErrorCode::InvalidMethod,
+            ParseError::InvalidMethod("GE T".into()).code()
+        );
+
+        let json = serde_json::to_string(&ErrorCode::MissingFlagValue).unwrap();
+        assert_eq!(r#""missing-flag-value""#, json);
+        for code in ErrorCode::ALL {
+            assert_eq!(
+                format!("{:?}", code.as_str()),
+                serde_json::to_string(&code).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{
+    options,
+    request::{find_curl_commands, parse_curl_command, ParsedRequest},
+    span::curl_cmd_spans,
+    Curl,
+};
+
+/// A param of the command with what it does.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Explanation {
+    /// The param in curl syntax, e.g. `-H 'Accept: */*'`.
+    pub param: String,
+    pub text: String,
+}
+
+/// The param on a line, then its explanation indented below it.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n    {}", self.param, self.text)
+    }
+}
+
+/// Explain every param of the request in input order, the flags with the description of
+/// their option in `options::OPTIONS`.
+pub fn explain(request: &ParsedRequest) -> Vec<Explanation> {
+    request
+        .curls()
+        .iter()
+        .map(|curl| Explanation {
+            param: curl.to_string(),
+            text: explain_param(request, curl),
+        })
+        .collect()
+}
+
+/// Explain the param covering the byte offset of the input, e.g. the cursor of an editor
+/// hovering a curl command in a script or markdown. `None` if no param of a parsable curl
+/// command covers the offset.
+pub fn explain_at(input: &str, offset: usize) -> Option<Explanation> {
+    let command = find_curl_commands(input).into_iter().find(|command| {
+        let start = command.as_ptr() as usize - input.as_ptr() as usize;
+        (start..=start + command.len()).contains(&offset)
+    })?;
+    let offset = offset - (command.as_ptr() as usize - input.as_ptr() as usize);
+
+    let request = parse_curl_command(command).ok()?;
+    let index = curl_cmd_spans(command)
+        .ok()?
+        .iter()
+        .position(|span| span.start <= offset && offset < span.end)?;
+    let curl = &request.curls()[index];
+    Some(Explanation {
+        param: curl.to_string(),
+        text: explain_param(&request, curl),
+    })
+}
+
+fn explain_param(request: &ParsedRequest, curl: &Curl) -> String {
+    match curl {
+        Curl::URL(url) => {
+            let mut text = format!("Targets the host {} over {}", url.domain, url.protocol);
+            if let Some(uri) = url.uri.as_deref().filter(|u| !u.is_empty()) {
+                text.push_str(&format!(", path {}", uri));
+            }
+            let queries = url.queries.as_ref().map_or(0, Vec::len);
+            match queries {
+                0 => {}
+                1 => text.push_str(", with 1 query param"),
+                n => text.push_str(&format!(", with {} query params", n)),
+            }
+            text
+        }
+        Curl::Method(m) => format!(
+            "Sets the request method to {}",
+            m.data.as_deref().unwrap_or_default()
+        ),
+        Curl::Header(h) => {
+            let header = h.data.as_deref().unwrap_or_default();
+            match header.split_once(':') {
+                Some((name, value)) if value.trim().is_empty() => {
+                    format!("Removes the request header {}", name.trim())
+                }
+                Some((name, value)) => format!(
+                    "Adds the request header {} with the value {}",
+                    name.trim(),
+                    value.trim()
+                ),
+                None => format!("Adds the malformed request header {}", header),
+            }
+        }
+        Curl::Data(_) => format!(
+            "Sends the data as the request body, which makes it a {}",
+            request.implied_method()
+        ),
+        Curl::Flag(f) => match options::lookup(&f.identifier) {
+            Some(option) => {
+                let mut text = option.description.to_string();
+                if let Some(data) = &f.data {
+                    text.push_str(&format!(": {}", data));
+                }
+                if option.name != f.identifier {
+                    text.push_str(&format!(" ({})", option.name));
+                }
+                text
+            }
+            None => format!("{} isn't an option nomcurl knows about", f.identifier),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_explain() {
+        let input = "curl 'https://github.com/a?b=1' -X 'PUT' -H 'Accept: */*' -H 'Expect:' -d 'a=1' --retry '3' -k --frobnicate";
+        let request = parse_curl_command(input).unwrap();
+        let texts: Vec<String> = explain(&request).into_iter().map(|e| e.text).collect();
+
+        let expect = vec![
+            "Targets the host github.com over https, path /a, with 1 query param",
+            "Sets the request method to PUT",
+            "Adds the request header Accept with the value */*",
+            "Removes the request header Expect",
+            "Sends the data as the request body, which makes it a POST",
+            "Retry request if transient problems occur: 3",
+            "Allow insecure server connections (--insecure)",
+            "--frobnicate isn't an option nomcurl knows about",
+        ];
+        assert_eq!(expect, texts);
+
+        let explanation = &explain(&request)[1];
+        assert_eq!(
+            "-X 'PUT'\n    Sets the request method to PUT",
+            explanation.to_string()
+        );
+    }
+
+    #[test]
+    fn test_explain_at() {
+        let input =
+            "# Create the repo\n$ curl 'https://github.com' \\\n  -X 'POST' -k\n\necho done\n";
+        let text = |needle: &str| {
+            let offset = input.find(needle).unwrap();
+            explain_at(input, offset).map(|e| e.text)
+        };
+
+        assert_eq!(
+            Some("Targets the host github.com over https".to_string()),
+            text("github")
+        );
+        assert_eq!(
+            Some("Sets the request method to POST".to_string()),
+            text("POST")
+        );
+        assert_eq!(
+            Some("Allow insecure server connections (--insecure)".to_string()),
+            text("-k")
+        );
+        assert_eq!(None, text("Create"));
+        assert_eq!(None, text("curl"));
+        assert_eq!(None, text("echo"));
+        assert_eq!(None, explain_at(input, input.len() + 1));
+    }
+}
@@ -0,0 +1,124 @@
+use super::{
+    method::Method,
+    request::{command_start, ends_command, ParsedRequest},
+};
+
+/// Assembles the curl commands of a stream of lines, e.g. a log being written, with the same
+/// rules as `request::find_curl_commands`.
+#[derive(Debug, Clone, Default)]
+pub struct CommandStream {
+    pending: String,
+    quote: Option<char>,
+}
+
+impl CommandStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next line, returns the command it completes, if any.
+    pub fn push_line(&mut self, line: &str) -> Option<String> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        let line = match self.pending.is_empty() {
+            true => &line[command_start(line)?..],
+            false => line,
+        };
+        if !self.pending.is_empty() {
+            self.pending.push('\n');
+        }
+        self.pending.push_str(line);
+
+        match ends_command(line, &mut self.quote) {
+            true => Some(std::mem::take(&mut self.pending)),
+            false => None,
+        }
+    }
+
+    /// The command left unfinished at the end of the stream, if any.
+    pub fn finish(&mut self) -> Option<String> {
+        self.quote = None;
+        Some(std::mem::take(&mut self.pending)).filter(|p| !p.is_empty())
+    }
+}
+
+/// The predicates a request must all match, an empty list matches every request.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RequestFilter {
+    /// Any of the hosts, compared case-insensitively without the port.
+    pub hosts: Vec<String>,
+    /// Any of the effective methods.
+    pub methods: Vec<Method>,
+    /// Every `Name` or `Name: value` header, the value matches when it's contained in the sent one.
+    pub headers: Vec<String>,
+}
+
+impl RequestFilter {
+    pub fn matches(&self, request: &ParsedRequest) -> bool {
+        let domain = &request.url().domain;
+        let host = domain.split(':').next().unwrap_or(domain);
+        let method = request.effective_method();
+
+        (self.hosts.is_empty() || self.hosts.iter().any(|h| h.eq_ignore_ascii_case(host)))
+            && (self.methods.is_empty() || self.methods.contains(&method))
+            && self.headers.iter().all(|header| {
+                let (name, value) = header.split_once(':').unwrap_or((header, ""));
+                request
+                    .headers()
+                    .filter_map(|h| h.split_once(':'))
+                    .any(|(n, v)| {
+                        n.trim().eq_ignore_ascii_case(name.trim()) && v.contains(value.trim())
+                    })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_command_stream() {
+        let lines = [
+            "# a log",
+            "$ curl 'https://github.com' \\",
+            "  -d 'a",
+            "b' -k",
+            "curl 'https://gitlab.com'",
+            "curl 'https://x.com' \\",
+        ];
+        let mut stream = CommandStream::new();
+        let commands: Vec<String> = lines.iter().filter_map(|l| stream.push_line(l)).collect();
+
+        assert_eq!(
+            vec![
+                "curl 'https://github.com' \\\n  -d 'a\nb' -k",
+                "curl 'https://gitlab.com'"
+            ],
+            commands
+        );
+        assert_eq!(Some("curl 'https://x.com' \\".into()), stream.finish());
+        assert_eq!(None, stream.finish());
+    }
+
+    #[test]
+    fn test_request_filter() {
+        let request = parse_curl_command(
+            "curl 'https://API.github.com:8443/a' -H 'Content-Type: application/json' -d '{}'",
+        )
+        .unwrap();
+        let filter = |hosts: &[&str], methods: &[Method], headers: &[&str]| RequestFilter {
+            hosts: hosts.iter().map(|h| h.to_string()).collect(),
+            methods: methods.to_vec(),
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+        };
+
+        assert!(filter(&[], &[], &[]).matches(&request));
+        assert!(filter(&["gitlab.com", "api.github.com"], &[], &[]).matches(&request));
+        assert!(!filter(&["github.com"], &[], &[]).matches(&request));
+        assert!(filter(&[], &[Method::GET, Method::POST], &[]).matches(&request));
+        assert!(!filter(&[], &[Method::GET], &[]).matches(&request));
+        assert!(filter(&[], &[], &["content-type: json", "Content-Type"]).matches(&request));
+        assert!(!filter(&[], &[], &["Authorization"]).matches(&request));
+    }
+}
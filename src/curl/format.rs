@@ -0,0 +1,258 @@
+use super::{
+    highlight::{self, Style},
+    method::Method,
+    options,
+    request::ParsedRequest,
+    Curl,
+};
+use crate::{
+    prelude::*,
+    shell_quote::{self, Shell},
+};
+
+/// How `format` lays out a curl command.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+    /// One param per line, joined by ` \` continuations.
+    pub multiline: bool,
+    /// Order the params by kind: method, headers by name, data, flags by long name.
+    pub sort: bool,
+    /// The indentation of the continuation lines, 2 spaces if `None`.
+    pub indent: Option<usize>,
+    /// Leave out the params which don't change the request, see `is_noop`.
+    pub strip_noop: bool,
+    /// Color the url, method, headers and flags with ANSI escapes, see `highlight::param`.
+    pub color: bool,
+    /// The shell to write the command for, its quoting and its line continuation.
+    pub shell: Shell,
+    /// Quote only the values which need it, e.g. `-X POST`, the `cmd.exe` and PowerShell
+    /// quoting always is. `parse_curl_command` reads the unquoted values back too.
+    pub minimal_quotes: bool,
+}
+
+impl FormatOptions {
+    /// The layout of `nomcurl format`: one param per line in the original order.
+    pub fn pretty() -> Self {
+        Self {
+            multiline: true,
+            ..Default::default()
+        }
+    }
+
+    /// Quote the value for the shell of the options.
+    pub fn quote(&self, arg: &str) -> String {
+        match (self.shell, self.minimal_quotes) {
+            (Shell::Posix, false) => shell_quote::posix_quoted(arg),
+            (shell, _) => shell.quote(arg),
+        }
+    }
+}
+
+/// Write the request back as a curl command, the url always comes right after `curl`.
+pub fn format(request: &ParsedRequest, options: &FormatOptions) -> String {
+    let mut curls: Vec<&Curl> = request.curls().iter().collect();
+    if options.strip_noop {
+        curls.retain(|c| !is_noop(request, c));
+    }
+    write_command(curls, options)
+}
+
+/// Write params back as a curl command laid out as `format` does, e.g. the ones of
+/// `curl_parsers::curl_cmd_parse`. `strip_noop` is left to `format`, which has the request.
+pub fn format_curls(curls: &[Curl], options: &FormatOptions) -> String {
+    write_command(curls.iter().collect(), options)
+}
+
+fn write_command(mut curls: Vec<&Curl>, options: &FormatOptions) -> String {
+    if options.sort {
+        // Stable, so that the data keep their order
+        curls.sort_by_key(|c| sort_key(c));
+    } else {
+        curls.sort_by_key(|c| !matches!(c, Curl::URL(_)));
+    }
+
+    let separator = match options.multiline {
+        true => format!(
+            " {}\n{}",
+            options.shell.continuation(),
+            " ".repeat(options.indent.unwrap_or(2))
+        ),
+        false => " ".to_string(),
+    };
+    // `curl` is an alias of `Invoke-WebRequest` in Windows PowerShell
+    let program = match options.shell {
+        Shell::PowerShell => "curl.exe",
+        _ => "curl",
+    };
+    let mut command = match options.color {
+        true => highlight::paint(program, Style::Command),
+        false => String::from(program),
+    };
+    let quote = |arg: &str| options.quote(arg);
+    for (i, curl) in curls.iter().enumerate() {
+        command.push_str(if i == 0 { " " } else { &separator });
+        match options.color {
+            true => command.push_str(&highlight::param(curl, quote)),
+            false => command.push_str(&param(curl, quote)),
+        }
+    }
+    command
+}
+
+/// The param in curl syntax, its values quoted by the function.
+fn param(curl: &Curl, quote: impl Fn(&str) -> String) -> String {
+    let stru = match curl {
+        Curl::URL(url) => return quote(&url.to_string()),
+        Curl::Method(s) | Curl::Header(s) | Curl::Data(s) | Curl::Flag(s) => s,
+    };
+    match &stru.data {
+        Some(data) => format!("{} {}", stru.identifier, quote(data)),
+        None => stru.identifier.to_string(),
+    }
+}
+
+/// The flags which only change what curl prints.
+const OUTPUT_ONLY_FLAGS: &[&str] = &["--silent", "--show-error", "--verbose"];
+
+/// Whether the param doesn't change the request: an output only flag, or `-X` repeating the
+/// implied method (only when every `-X` does, as curl uses the last one and leaving it out
+/// would let an earlier one win).
+pub fn is_noop(request: &ParsedRequest, curl: &Curl) -> bool {
+    match curl {
+        Curl::Flag(f) => OUTPUT_ONLY_FLAGS
+            .iter()
+            .any(|name| f.identifier == *name || options::is_option(&f.identifier, name)),
+        Curl::Method(_) => {
+            let implied = request.implied_method();
+            request.curls().iter().all(|c| match c {
+                Curl::Method(m) => m
+                    .data
+                    .as_deref()
+                    .and_then(|m| m.parse::<Method>().ok())
+                    .is_some_and(|m| m == implied),
+                _ => true,
+            })
+        }
+        _ => false,
+    }
+}
+
+fn sort_key(curl: &Curl) -> (u8, String) {
+    match curl {
+        Curl::URL(_) => (0, String::new()),
+        Curl::Method(_) => (1, String::new()),
+        Curl::Header(h) => {
+            let header = h.data.as_deref().unwrap_or_default();
+            let name = header.split(':').next().unwrap_or_default();
+            (2, name.trim().to_ascii_lowercase())
+        }
+        Curl::Data(_) => (3, String::new()),
+        Curl::Flag(f) if options::is_data_option(&f.identifier) => (3, String::new()),
+        Curl::Flag(f) => {
+            let name = options::lookup(&f.identifier).map_or(f.identifier.as_ref(), |o| o.name);
+            (4, name.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::{curl_parsers::curl_cmd_parse, request::parse_curl_command};
+
+    const TEST_CURL_CMD: &str = "curl 'https://github.com' -k -H 'X-B: 2' -d 'b=1' -X 'POST' --data-raw 'a=1' -H \"Accept: it's\" -L";
+
+    #[test]
+    fn test_format() {
+        let request = parse_curl_command(TEST_CURL_CMD).unwrap();
+
+        let expect = "curl 'https://github.com' \\\n  -k \\\n  -H 'X-B: 2' \\\n  -d 'b=1' \\\n  -X 'POST' \\\n  --data-raw 'a=1' \\\n  -H \"Accept: it's\" \\\n  -L";
+        assert_eq!(expect, format(&request, &FormatOptions::pretty()));
+
+        let options = FormatOptions {
+            multiline: true,
+            sort: true,
+            indent: Some(4),
+            ..Default::default()
+        };
+        let expect = "curl 'https://github.com' \\\n    -X 'POST' \\\n    -H \"Accept: it's\" \\\n    -H 'X-B: 2' \\\n    -d 'b=1' \\\n    --data-raw 'a=1' \\\n    -k \\\n    -L";
+        assert_eq!(expect, format(&request, &options));
+
+        assert_eq!(TEST_CURL_CMD, format(&request, &FormatOptions::default()));
+    }
+
+    #[test]
+    fn test_format_strip_noop() {
+        let options = FormatOptions {
+            strip_noop: true,
+            ..Default::default()
+        };
+        let strip = |input: &str| format(&parse_curl_command(input).unwrap(), &options);
+
+        let input = "curl 'https://github.com' \\\n  -X 'POST' \\\n  -d 'a=1' -s -S --verbose -k";
+        assert_eq!("curl 'https://github.com' -d 'a=1' -k", strip(input));
+
+        let input = "curl 'https://github.com' -X 'PUT' -d 'a=1' -X 'POST'";
+        assert_eq!(input, strip(input));
+    }
+
+    #[test]
+    fn test_format_shell() {
+        let input = "curl 'https://github.com/a?b=1' -X 'POST' -H 'Content-Type: application/json' -d '{\"a\":\"it'\\''s\"}' -k";
+        let request = parse_curl_command(input).unwrap();
+
+        let options = FormatOptions {
+            minimal_quotes: true,
+            ..Default::default()
+        };
+        let expect = r#"curl 'https://github.com/a?b=1' -X POST -H 'Content-Type: application/json' -d '{"a":"it'\''s"}' -k"#;
+        assert_eq!(expect, format(&request, &options));
+
+        let options = FormatOptions {
+            shell: Shell::Cmd,
+            ..FormatOptions::pretty()
+        };
+        let expect = "curl \"https://github.com/a?b=1\" ^\n  -X POST ^\n  -H \"Content-Type: application/json\" ^\n  -d \"{\\\"a\\\":\\\"it's\\\"}\" ^\n  -k";
+        assert_eq!(expect, format(&request, &options));
+
+        let options = FormatOptions {
+            shell: Shell::PowerShell,
+            ..FormatOptions::pretty()
+        };
+        let expect = "curl.exe 'https://github.com/a?b=1' `\n  -X POST `\n  -H 'Content-Type: application/json' `\n  -d '{\"a\":\"it''s\"}' `\n  -k";
+        assert_eq!(expect, format(&request, &options));
+    }
+
+    #[test]
+    fn test_format_curls() {
+        let input = "curl -k 'https://github.com' -H 'A: 1'";
+        let (_, curls) = curl_cmd_parse(input).unwrap();
+        assert_eq!(
+            "curl 'https://github.com' -k -H 'A: 1'",
+            format_curls(&curls, &FormatOptions::default())
+        );
+
+        // A request edited then written back
+        let mut request = parse_curl_command(input).unwrap();
+        request.set_header("B", "it's");
+        let command = request.to_curl_string();
+        assert_eq!(
+            "curl 'https://github.com' -k -H 'A: 1' -H \"B: it's\"",
+            command
+        );
+        let parsed = parse_curl_command(&command).unwrap();
+        assert_eq!(Some("it's"), parsed.header("b"));
+        assert_eq!(command, parsed.to_curl_string());
+    }
+
+    #[test]
+    fn test_format_color() {
+        let request = parse_curl_command("curl 'https://github.com' -X 'POST'").unwrap();
+        let options = FormatOptions {
+            color: true,
+            ..Default::default()
+        };
+        let expect = "\x1b[1mcurl\x1b[0m \x1b[4;32m'https://github.com'\x1b[0m \x1b[34m-X\x1b[0m \x1b[1;35m'POST'\x1b[0m";
+        assert_eq!(expect, format(&request, &options));
+    }
+}
@@ -0,0 +1,76 @@
+use super::Curl;
+use crate::prelude::*;
+
+/// The kinds of text colored in the terminal output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// The `curl` program and the section titles.
+    Command,
+    Url,
+    Method,
+    HeaderName,
+    /// A header value or a param value.
+    Value,
+    Flag,
+}
+
+impl Style {
+    /// The SGR parameters of the style.
+    fn code(self) -> &'static str {
+        match self {
+            Style::Command => "1",
+            Style::Url => "4;32",
+            Style::Method => "1;35",
+            Style::HeaderName => "36",
+            Style::Value => "33",
+            Style::Flag => "34",
+        }
+    }
+}
+
+/// The text wrapped in the ANSI escapes of the style.
+pub fn paint(text: &str, style: Style) -> String {
+    format!("\x1b[{}m{}\x1b[0m", style.code(), text)
+}
+
+/// The param in curl syntax with its parts colored, its values quoted by the function, e.g.
+/// `shell_quote::posix_quoted` as its `Display`.
+pub fn param(curl: &Curl, quote: impl Fn(&str) -> String) -> String {
+    let (stru, value_style) = match curl {
+        Curl::URL(url) => return paint(&quote(&url.to_string()), Style::Url),
+        Curl::Method(m) => (m, Style::Method),
+        Curl::Header(h) => (h, Style::Value),
+        Curl::Data(d) | Curl::Flag(d) => (d, Style::Value),
+    };
+
+    let identifier = paint(&stru.identifier, Style::Flag);
+    let Some(data) = &stru.data else {
+        return identifier;
+    };
+    let data = quote(data);
+    let value = match (curl, data.split_once(':')) {
+        (Curl::Header(_), Some((name, value))) => format!(
+            "{}:{}",
+            paint(name, Style::HeaderName),
+            paint(value, Style::Value)
+        ),
+        _ => paint(&data, value_style),
+    };
+    format!("{} {}", identifier, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{new_curl, shell_quote::posix_quoted as quote};
+
+    #[test]
+    fn test_param() {
+        let header = new_curl!(-H, "Accept: */*");
+        let expect = "\x1b[34m-H\x1b[0m \x1b[36m'Accept\x1b[0m:\x1b[33m */*'\x1b[0m";
+        assert_eq!(expect, param(&header, quote));
+
+        let flag = new_curl!("-k");
+        assert_eq!("\x1b[34m-k\x1b[0m", param(&flag, quote));
+    }
+}
@@ -0,0 +1,253 @@
+use alloc::borrow::Cow;
+use core::cell::OnceCell;
+
+use super::{
+    curl_parsers::{
+        commands_parse, raw_url_parse, remove_curl_cmd_header, slash_line_ending, url_param_parse,
+    },
+    dialect,
+    error::{ParseError, TokenKind},
+    options,
+    request::{is_curl_invocation, ParsedRequest},
+    url_parser::{curl_url_parse, CurlURL},
+    Curl, CurlStru,
+};
+use crate::prelude::*;
+use nom::{combinator::opt, sequence::preceded};
+
+/// A curl command parsed without decomposing its url, which is only parsed on first access, for
+/// workloads looking at the flags and headers alone.
+#[derive(Debug, Clone)]
+pub struct LazyRequest<'a> {
+    raw_url: Cow<'a, str>,
+    url: OnceCell<Result<CurlURL<'a>, ParseError>>,
+    /// The index in the params the url is at.
+    url_index: usize,
+    /// The params but the url.
+    curls: Vec<Curl<'a>>,
+}
+
+impl<'a> LazyRequest<'a> {
+    /// The url as written in the command, without its quotes.
+    pub fn raw_url(&self) -> &str {
+        &self.raw_url
+    }
+
+    /// The url, parsed on the first call.
+    pub fn url(&self) -> Result<&CurlURL<'a>, ParseError> {
+        self.url
+            .get_or_init(|| parse_url(&self.raw_url))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// The params but the url, in input order.
+    pub fn curls(&self) -> &[Curl<'a>] {
+        &self.curls
+    }
+
+    /// The raw `Name: value` strings of every `-H`.
+    pub fn headers(&self) -> impl Iterator<Item = &str> {
+        self.curls.iter().filter_map(|c| match c {
+            Curl::Header(h) => h.data.as_deref(),
+            _ => None,
+        })
+    }
+
+    /// The value of the first header with the given name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers().find_map(|h| {
+            let (n, v) = h.split_once(':')?;
+            n.trim().eq_ignore_ascii_case(name).then(|| v.trim())
+        })
+    }
+
+    /// The last flag matching the given option, under either its long name or its short alias,
+    /// see `ParsedRequest::flag`.
+    pub fn flag(&self, name: &str) -> Option<&CurlStru<'a>> {
+        self.curls.iter().rev().find_map(|c| match c {
+            Curl::Flag(f) if f.identifier == name || options::is_option(&f.identifier, name) => {
+                Some(f)
+            }
+            _ => None,
+        })
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flag(name).is_some()
+    }
+
+    /// Copy the borrowed strings, detaching the request from the input. It's of any lifetime, as
+    /// the cell of the url makes a `LazyRequest<'static>` no `LazyRequest<'a>`.
+    pub fn into_owned<'b>(self) -> LazyRequest<'b> {
+        let url = match self.url.into_inner() {
+            Some(url) => OnceCell::from(url.map(CurlURL::into_owned)),
+            None => OnceCell::new(),
+        };
+        LazyRequest {
+            raw_url: Cow::Owned(self.raw_url.into_owned()),
+            url,
+            url_index: self.url_index,
+            curls: self.curls.into_iter().map(Curl::into_owned).collect(),
+        }
+    }
+
+    /// The fully parsed request, an invalid url is an error.
+    pub fn into_request(mut self) -> Result<ParsedRequest<'a>, ParseError> {
+        let url = match self.url.take() {
+            Some(url) => url,
+            None => parse_url(&self.raw_url),
+        }?;
+        self.curls.insert(self.url_index, Curl::URL(url));
+        ParsedRequest::new(self.curls)
+    }
+}
+
+/// An unescaped url is parsed into an owned one, as it doesn't borrow from the input.
+fn parse_url<'a>(raw_url: &Cow<'a, str>) -> Result<CurlURL<'a>, ParseError> {
+    let parsed = match raw_url {
+        Cow::Borrowed(raw_url) => curl_url_parse(raw_url).map(|(_, url)| url),
+        Cow::Owned(raw_url) => curl_url_parse(raw_url).map(|(_, url)| url.into_owned()),
+    };
+    parsed.map_err(|e| ParseError::from_nom(raw_url, TokenKind::Url, e))
+}
+
+/// Parse a single curl command like `request::parse_curl_command`, but leave its url unparsed
+/// until it's accessed.
+pub fn parse_curl_command_lazy(input: &str) -> Result<LazyRequest<'_>, ParseError> {
+    let input = input.trim_start();
+    if !is_curl_invocation(input) {
+        return Err(ParseError::NotCurl);
+    }
+
+    let err = match parse_lazy_posix_command(input) {
+        Ok(request) if dialect::detect(input).is_none() => return Ok(request),
+        // The url is parsed now, as the quoting may mangle it, e.g. `^"https://a.com^"`
+        Ok(request) => match request.url().err() {
+            Some(e) => e,
+            None => return Ok(request),
+        },
+        Err(e) => e,
+    };
+    // Another shell's quoting is likely why the parsers stopped
+    let Some(hint) = dialect::detect(input) else {
+        return Err(err);
+    };
+    match dialect::normalize(input, hint.sign)
+        .map(|command| parse_lazy_posix_command(&command).map(LazyRequest::into_owned))
+    {
+        Some(Ok(request)) => Ok(request),
+        _ => Err(ParseError::UnsupportedDialect(hint)),
+    }
+}
+
+fn parse_lazy_posix_command(input: &str) -> Result<LazyRequest<'_>, ParseError> {
+    // The first word which isn't a param value is the raw url, any other url is parsed
+    let mut rest = remove_curl_cmd_header(input);
+    let mut raw_url = None;
+    let mut curls = Vec::new();
+    loop {
+        let (after, mut params) =
+            commands_parse(rest).map_err(|e| ParseError::from_nom(input, TokenKind::Value, e))?;
+        curls.append(&mut params);
+        rest = after;
+        if raw_url.is_none() {
+            let Ok((after, url)) = preceded(opt(slash_line_ending), raw_url_parse)(rest) else {
+                break;
+            };
+            raw_url = Some((curls.len(), url));
+            rest = after;
+        } else {
+            let Ok((after, url)) = url_param_parse(rest) else {
+                break;
+            };
+            curls.push(url);
+            rest = after;
+        }
+    }
+
+    let (url_index, raw_url) = raw_url.ok_or(ParseError::MissingUrl)?;
+    // A word which is no param is reported rather than dropped with the params after it
+    if !rest.trim().is_empty() {
+        let e = nom::error::Error::new(rest, nom::error::ErrorKind::Fail);
+        return Err(ParseError::from_nom(
+            input,
+            TokenKind::Param,
+            nom::Err::Error(e),
+        ));
+    }
+    Ok(LazyRequest {
+        raw_url,
+        url: OnceCell::new(),
+        url_index,
+        curls,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    const TEST_CURL_CMD: &str =
+        "curl 'https://github.com/a?b=1' -H 'Accept: */*' -d 'a=1' -k -m '10'";
+
+    #[test]
+    fn test_parse_curl_command_lazy() {
+        let request = parse_curl_command_lazy(TEST_CURL_CMD).unwrap();
+        assert_eq!("https://github.com/a?b=1", request.raw_url());
+        assert_eq!(Some("*/*"), request.header("accept"));
+        assert_eq!(
+            Some("10"),
+            request.flag("--max-time").and_then(|f| f.data.as_deref())
+        );
+        assert!(request.has_flag("--insecure"));
+        assert_eq!("github.com", request.url().unwrap().domain);
+
+        let expect = parse_curl_command(TEST_CURL_CMD);
+        assert_eq!(expect, request.into_request());
+
+        let input = "curl -k -H 'Accept: */*' 'https://github.com' -d 'a=1'";
+        let request = parse_curl_command_lazy(input).unwrap();
+        assert_eq!("https://github.com", request.raw_url());
+        assert_eq!(3, request.curls().len());
+        assert_eq!(parse_curl_command(input), request.into_request());
+
+        let result = parse_curl_command_lazy("curl -H 'Accept: */*'");
+        assert_eq!(
+            Err(ParseError::MissingUrl),
+            result.map(|r| r.raw_url().to_string())
+        );
+        let result = parse_curl_command_lazy("wget 'https://github.com'");
+        assert_eq!(
+            Err(ParseError::NotCurl),
+            result.map(|r| r.raw_url().to_string())
+        );
+    }
+
+    #[test]
+    fn test_lazy_agrees_with_strict() {
+        let inputs = [
+            TEST_CURL_CMD,
+            "curl 'https://github.com' -k oops -H 'A: 1'",
+            "curl 'https://github.com' -H 'A: 1' | jq",
+            "curl 'https://github.com' 'https://a.com/b' -d 'a=1'",
+            "curl ^\"https://github.com^\" -H ^\"A: 1^\" --compressed",
+            "curl \"https://github.com\" `\n  -H \"A: 1\"",
+            "curl 'https://github.com' oops ^",
+        ];
+        for input in inputs {
+            let lazy = parse_curl_command_lazy(input).and_then(LazyRequest::into_request);
+            assert_eq!(parse_curl_command(input), lazy, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_lazy_request_invalid_url() {
+        let request = parse_curl_command_lazy("curl 'not a url' -H 'A: 1'").unwrap();
+
+        assert_eq!(Some("1"), request.header("A"));
+        assert!(request.url().is_err());
+        assert!(request.into_request().is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use super::{
+    curl_parsers::{commands_parse, curl_cmd_parse, is_curl, remove_curl_cmd_header},
+    error::{ParseError, TokenKind},
+    options,
+    request::ParsedRequest,
+    Curl,
+};
+
+/// Parse the params of an overlay command, which unlike a request may have no url, e.g. a
+/// `curl -H 'Authorization: Bearer x' -k` profile.
+pub fn parse_overlay(input: &str) -> Result<Vec<Curl<'_>>, ParseError> {
+    let input = input.trim();
+    if !is_curl(input) {
+        return Err(ParseError::NotCurl);
+    }
+    let error = |e| ParseError::from_nom(input, TokenKind::Value, e);
+    match curl_cmd_parse(input).map_err(error) {
+        Ok((_, curls)) => Ok(curls),
+        Err(ParseError::MissingUrl) => {
+            let (_, curls) =
+                commands_parse(remove_curl_cmd_header(input).trim_start()).map_err(error)?;
+            Ok(curls)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Apply the overlay params on top of the base request, the overlay wins:
+/// - its url and `-X` replace the base ones,
+/// - its headers replace the base headers with the same name,
+/// - its data, if any, replace all the base data,
+/// - its flags replace the base flags of the same option.
+pub fn merge<'a>(base: &ParsedRequest<'a>, overlay: &[Curl<'a>]) -> ParsedRequest<'a> {
+    let mut request = base.clone();
+
+    for curl in overlay {
+        match curl {
+            Curl::URL(url) => *request.url_mut() = url.clone(),
+            Curl::Method(m) => request.set_method(m.data.as_deref().and_then(|m| m.parse().ok())),
+            Curl::Header(h) => {
+                let header = h.data.as_deref().unwrap_or_default();
+                let (name, value) = header.split_once(':').unwrap_or((header, ""));
+                request.set_header(name.trim(), value.trim());
+            }
+            _ => {}
+        }
+    }
+
+    let overlay_data = overlay.iter().any(is_data);
+    let overlay_flags: Vec<&str> = overlay
+        .iter()
+        .filter_map(|c| match c {
+            Curl::Flag(f) if !is_data(c) => Some(option_name(&f.identifier)),
+            _ => None,
+        })
+        .collect();
+
+    let mut curls = request.into_curls();
+    curls.retain(|c| match c {
+        _ if is_data(c) => !overlay_data,
+        Curl::Flag(f) => !overlay_flags.contains(&option_name(&f.identifier)),
+        _ => true,
+    });
+    curls.extend(
+        overlay
+            .iter()
+            .filter(|c| is_data(c) || matches!(c, Curl::Flag(_)))
+            .cloned(),
+    );
+    ParsedRequest::new(curls).expect("the base url is kept")
+}
+
+fn is_data(curl: &Curl) -> bool {
+    match curl {
+        Curl::Data(_) => true,
+        Curl::Flag(f) => options::is_data_option(&f.identifier),
+        _ => false,
+    }
+}
+
+/// The long name of the flag, so that `-k` and `--insecure` are the same option.
+fn option_name(flag: &str) -> &str {
+    options::lookup(flag).map_or(flag, |o| o.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_merge() {
+        let base = parse_curl_command(
+            "curl 'https://github.com/a' -H 'Authorization: Bearer old' -H 'Accept: */*' -d 'a=1' -m '10' -k",
+        )
+        .unwrap();
+
+        let overlay = parse_overlay(
+            "curl -H 'authorization: Bearer new' -H 'X-A: 1' --max-time '5' --insecure",
+        )
+        .unwrap();
+        let expect = "curl 'https://github.com/a' -H 'authorization: Bearer new' -H 'Accept: */*' -H 'X-A: 1' -d 'a=1' --max-time '5' --insecure";
+        assert_eq!(expect, merge(&base, &overlay).to_string());
+
+        let overlay =
+            parse_curl_command("curl 'https://gitlab.com/b' -X 'PUT' --data-raw 'b=2'").unwrap();
+        let expect = "curl 'https://gitlab.com/b' -X 'PUT' -H 'Authorization: Bearer old' -H 'Accept: */*' -m '10' -k --data-raw 'b=2'";
+        assert_eq!(expect, base.merge(&overlay).to_string());
+    }
+
+    #[test]
+    fn test_parse_overlay() {
+        assert_eq!(Ok(Vec::new()), parse_overlay("curl"));
+        assert_eq!(Err(ParseError::NotCurl), parse_overlay("wget -k"));
+        assert_eq!(1, parse_overlay("curl \\\n  -k").unwrap().len());
+    }
+}
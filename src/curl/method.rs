@@ -0,0 +1,172 @@
+use core::{fmt, str::FromStr};
+use serde::{Deserialize, Serialize};
+
+use super::error::ParseError;
+use crate::prelude::*;
+
+/// The HTTP request method, parsed case-insensitively and written in uppercase.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(into = "String", try_from = "String")]
+pub enum Method {
+    #[default]
+    GET,
+    POST,
+    PUT,
+    PATCH,
+    DELETE,
+    HEAD,
+    OPTIONS,
+    TRACE,
+    CONNECT,
+    Custom(String),
+}
+
+impl Method {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::GET => "GET",
+            Self::POST => "POST",
+            Self::PUT => "PUT",
+            Self::PATCH => "PATCH",
+            Self::DELETE => "DELETE",
+            Self::HEAD => "HEAD",
+            Self::OPTIONS => "OPTIONS",
+            Self::TRACE => "TRACE",
+            Self::CONNECT => "CONNECT",
+            Self::Custom(m) => m,
+        }
+    }
+
+    /// A method of RFC 9110 rather than a custom verb, e.g. `PROPFIND`.
+    pub fn is_standard(&self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+}
+
+/// What to do with a custom verb given to `-X`, e.g. `PROPFIND`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MethodPolicy {
+    /// Keep the method silently.
+    Accept,
+    /// Keep the method, the `custom-method` lint reports it.
+    #[default]
+    Warn,
+    /// Fail with `ParseError::InvalidMethod`, the lint reports it as an error.
+    Reject,
+}
+
+impl MethodPolicy {
+    pub const ALL: [MethodPolicy; 3] = [
+        MethodPolicy::Accept,
+        MethodPolicy::Warn,
+        MethodPolicy::Reject,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MethodPolicy::Accept => "accept",
+            MethodPolicy::Warn => "warn",
+            MethodPolicy::Reject => "reject",
+        }
+    }
+}
+
+/// A `-X` value which is a url rather than a method, e.g. `curl -X 'https://github.com'`.
+pub fn is_url_like(method: &str) -> bool {
+    method.contains("://") || method.starts_with('/')
+}
+
+impl FromStr for Method {
+    type Err = ParseError;
+
+    /// A custom method must be a valid HTTP token, e.g. `PROPFIND`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let method = match s.to_uppercase().as_str() {
+            "GET" => Self::GET,
+            "POST" => Self::POST,
+            "PUT" => Self::PUT,
+            "PATCH" => Self::PATCH,
+            "DELETE" => Self::DELETE,
+            "HEAD" => Self::HEAD,
+            "OPTIONS" => Self::OPTIONS,
+            "TRACE" => Self::TRACE,
+            "CONNECT" => Self::CONNECT,
+            m if !m.is_empty() && m.chars().all(is_token_char) => Self::Custom(m.into()),
+            _ => return Err(ParseError::InvalidMethod(s.into())),
+        };
+        Ok(method)
+    }
+}
+
+impl TryFrom<String> for Method {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Method> for String {
+    fn from(value: Method) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The `tchar` of RFC 7230.
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::generic_parse;
+
+    #[test]
+    fn test_method_from_str() {
+        generic_parse(str::parse::<Method>, "get", Ok(Method::GET));
+        generic_parse(str::parse::<Method>, "Patch", Ok(Method::PATCH));
+        generic_parse(
+            str::parse::<Method>,
+            "PROPFIND",
+            Ok(Method::Custom("PROPFIND".into())),
+        );
+        generic_parse(
+            str::parse::<Method>,
+            "propfind",
+            Ok(Method::Custom("PROPFIND".into())),
+        );
+        generic_parse(
+            str::parse::<Method>,
+            "GET POST",
+            Err(ParseError::InvalidMethod("GET POST".into())),
+        );
+        generic_parse(
+            str::parse::<Method>,
+            "",
+            Err(ParseError::InvalidMethod("".into())),
+        );
+    }
+
+    #[test]
+    fn test_method_display() {
+        assert_eq!("DELETE", Method::DELETE.to_string());
+        assert_eq!("PURGE", Method::Custom("PURGE".into()).to_string());
+    }
+
+    #[test]
+    fn test_is_url_like() {
+        assert!(is_url_like("https://github.com"));
+        assert!(is_url_like("/api/users"));
+        assert!(!is_url_like("POST"));
+        assert!(Method::CONNECT.is_standard());
+        assert!(!Method::Custom("PURGE".into()).is_standard());
+    }
+}
@@ -1,9 +1,54 @@
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod cookies;
 pub mod curl_parsers;
+#[cfg(feature = "fancy-errors")]
+pub mod diagnostic;
+pub mod dialect;
+#[cfg(feature = "std")]
+pub mod diff;
+pub mod document;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod explain;
+#[cfg(feature = "std")]
+pub mod filter;
+pub mod format;
+pub mod highlight;
+pub mod lazy;
+#[cfg(feature = "std")]
+pub mod lint;
+#[cfg(feature = "std")]
+pub mod merge;
+pub mod method;
+pub mod options;
+#[cfg(feature = "std")]
+pub mod query;
+pub mod recover;
+#[cfg(feature = "std")]
+pub mod redact;
+pub mod request;
+#[cfg(feature = "std")]
+pub mod span;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod summary;
 pub mod url_parser;
+#[cfg(feature = "std")]
+pub mod variables;
+
+use alloc::borrow::Cow;
+use core::fmt;
 
 // use url::Url;
+use error::{ParseError, SyntaxError, TokenKind};
 use url_parser::CurlURL;
 
+use crate::prelude::*;
+use crate::shell_quote::posix_quoted as quote;
+
 #[macro_export]
 macro_rules! new_curl {
     ($identifier:expr) => {
@@ -14,60 +59,72 @@ macro_rules! new_curl {
     };
 }
 
+/// A param borrowing from the parsed input, see `into_owned` to keep it around.
 #[derive(Debug, PartialEq, Clone)]
-pub struct CurlStru {
-    pub identifier: String,
-    pub data: Option<String>,
+pub struct CurlStru<'a> {
+    pub identifier: Cow<'a, str>,
+    pub data: Option<Cow<'a, str>>,
 }
 
-impl CurlStru {
-    pub fn new(identifier: &str) -> Self {
+impl<'a> CurlStru<'a> {
+    pub fn new(identifier: impl Into<Cow<'a, str>>) -> Self {
         CurlStru {
             identifier: identifier.into(),
             data: None,
         }
     }
 
-    pub fn new_with_data(identifier: &str, data: &str) -> Self {
+    pub fn new_with_data(
+        identifier: impl Into<Cow<'a, str>>,
+        data: impl Into<Cow<'a, str>>,
+    ) -> Self {
         CurlStru {
             identifier: identifier.into(),
             data: Some(data.into()),
         }
     }
 
-    pub fn set_data(&mut self, data: Option<String>) {
+    pub fn set_data(&mut self, data: Option<Cow<'a, str>>) {
         self.data = data;
     }
+
+    /// Copy the borrowed strings, detaching the param from the input.
+    pub fn into_owned(self) -> CurlStru<'static> {
+        CurlStru {
+            identifier: Cow::Owned(self.identifier.into_owned()),
+            data: self.data.map(|d| Cow::Owned(d.into_owned())),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum Curl {
-    Method(CurlStru),
+pub enum Curl<'a> {
+    Method(CurlStru<'a>),
     // URL(Url),
-    URL(CurlURL),
-    Header(CurlStru),
-    Data(CurlStru),
-    Flag(CurlStru),
+    URL(CurlURL<'a>),
+    Header(CurlStru<'a>),
+    Data(CurlStru<'a>),
+    Flag(CurlStru<'a>),
 }
 
-impl Curl {
-    pub fn new(identifier: &str, param: &str) -> Option<Self> {
-        if param.is_empty() {
-            return None;
-        }
-
+impl<'a> Curl<'a> {
+    /// The `-X`, `-H` or `-d` param of the value, an empty one included, e.g. `-d ''`. The long
+    /// names are written as the short ones.
+    pub fn new(identifier: &'a str, param: impl Into<Cow<'a, str>>) -> Option<Self> {
+        let param = param.into();
         match identifier {
-            "-X" => Some(Curl::Method(CurlStru::new_with_data(identifier, param))),
-            "-H" => Some(Curl::Header(CurlStru::new_with_data(identifier, param))),
+            "-X" | "--request" => Some(Curl::Method(CurlStru::new_with_data("-X", param))),
+            "-H" | "--header" => Some(Curl::Header(CurlStru::new_with_data("-H", param))),
             "-d" | "--data" => Some(Curl::Data(CurlStru::new_with_data("-d", param))),
             _ => {
+                #[cfg(feature = "std")]
                 eprintln!("Haven't implement it yet...");
                 None
             }
         }
     }
 
-    pub fn new_as_flag(identifier: &str) -> Option<Self> {
+    pub fn new_as_flag(identifier: &'a str) -> Option<Self> {
         // TODO: Do more check to ensure it's a flag param for curl
         if identifier.is_empty() {
             None
@@ -76,11 +133,102 @@ impl Curl {
         }
     }
 
-    pub fn new_as_url(url: CurlURL) -> Self {
+    pub fn new_as_url(url: CurlURL<'a>) -> Self {
         Curl::URL(url)
     }
 
     // pub fn new_as_url(url: url::Url) -> Self {
     //     Curl::URL(url)
     // }
+
+    /// Copy the borrowed strings, detaching the param from the input.
+    pub fn into_owned(self) -> Curl<'static> {
+        match self {
+            Curl::Method(m) => Curl::Method(m.into_owned()),
+            Curl::URL(u) => Curl::URL(u.into_owned()),
+            Curl::Header(h) => Curl::Header(h.into_owned()),
+            Curl::Data(d) => Curl::Data(d.into_owned()),
+            Curl::Flag(f) => Curl::Flag(f.into_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Curl<'_> {
+    /// Write the param back in curl syntax, e.g. `-H 'Accept: */*'`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Curl::URL(url) => write!(f, "{}", quote(&url.to_string())),
+            Curl::Method(stru) | Curl::Header(stru) | Curl::Data(stru) | Curl::Flag(stru) => {
+                write!(f, "{}", stru.identifier)?;
+                match &stru.data {
+                    Some(data) => write!(f, " {}", quote(data)),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Curl<'a> {
+    type Error = ParseError;
+
+    /// Parse a single param of a curl command, e.g. `-H 'Accept: */*'` or `'https://github.com'`.
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (rest, curl) = nom::branch::alt((
+            curl_parsers::method_parse,
+            curl_parsers::header_parse,
+            curl_parsers::data_parse,
+            curl_parsers::flag_parse,
+            curl_parsers::url_parse,
+        ))(value)
+        .map_err(|e| ParseError::from_nom(value, TokenKind::Param, e))?;
+
+        match rest.trim().is_empty() {
+            true => Ok(curl),
+            false => Err(ParseError::Syntax(SyntaxError::at(
+                value,
+                rest.trim_start(),
+                TokenKind::Param,
+                "the end of the param",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::generic_parse;
+
+    #[test]
+    fn test_curl_try_from() {
+        generic_parse(
+            Curl::try_from,
+            " -H 'Accept: */*' ",
+            Ok(new_curl!(-H, "Accept: */*")),
+        );
+        generic_parse(Curl::try_from, "--insecure", Ok(new_curl!("--insecure")));
+
+        let expect = CurlURL::try_from("https://github.com").map(Curl::new_as_url);
+        generic_parse(Curl::try_from, "'https://github.com'", expect);
+
+        let result = Curl::try_from("-X 'POST' -H 'A: 1'");
+        assert!(result.is_err(), "The result:\r\n{:#?}", result);
+    }
+
+    #[test]
+    fn test_curl_display() {
+        let to_string = |curl: Curl| curl.to_string();
+        generic_parse(
+            to_string,
+            new_curl!(-H, "Accept: */*"),
+            "-H 'Accept: */*'".into(),
+        );
+        generic_parse(to_string, new_curl!(-d, "it's"), "-d \"it's\"".into());
+        generic_parse(to_string, new_curl!("--insecure"), "--insecure".into());
+
+        let url = CurlURL::try_from("https://github.com/a?b=1").unwrap();
+        let expect = "'https://github.com/a?b=1'".to_string();
+        generic_parse(to_string, Curl::new_as_url(url), expect);
+    }
 }
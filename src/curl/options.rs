@@ -0,0 +1,352 @@
+use core::fmt;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::prelude::*;
+
+/// The group a curl option belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Request,
+    Header,
+    Body,
+    Auth,
+    Tls,
+    Proxy,
+    Connection,
+    Redirect,
+    Cookie,
+    Output,
+    Other,
+}
+
+impl Category {
+    pub const ALL: [Category; 11] = [
+        Category::Request,
+        Category::Header,
+        Category::Body,
+        Category::Auth,
+        Category::Tls,
+        Category::Proxy,
+        Category::Connection,
+        Category::Redirect,
+        Category::Cookie,
+        Category::Output,
+        Category::Other,
+    ];
+
+    /// The lowercase name of the category, as serialized.
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Request => "request",
+            Category::Header => "header",
+            Category::Body => "body",
+            Category::Auth => "auth",
+            Category::Tls => "tls",
+            Category::Proxy => "proxy",
+            Category::Connection => "connection",
+            Category::Redirect => "redirect",
+            Category::Cookie => "cookie",
+            Category::Output => "output",
+            Category::Other => "other",
+        }
+    }
+}
+
+/// Metadata of a known curl option.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurlOption {
+    pub name: &'static str,
+    pub short: Option<&'static str>,
+    pub takes_value: bool,
+    pub category: Category,
+    pub description: &'static str,
+    /// The curl version which added the option, e.g. `7.82.0`.
+    pub since: &'static str,
+    /// Set when modern curl deprecated or removed the option.
+    pub deprecated: Option<Deprecation>,
+}
+
+/// How curl phased an option out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Deprecation {
+    /// The curl version which deprecated the option, or removed it.
+    pub version: &'static str,
+    /// Whether curl rejects the option now, rather than ignoring it.
+    pub removed: bool,
+    /// The option to use instead, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<&'static str>,
+}
+
+/// E.g. `deprecated in 7.20.0, use --ssl instead`.
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.removed {
+            true => write!(f, "removed in {}", self.version)?,
+            false => write!(f, "deprecated in {}", self.version)?,
+        }
+        match self.replacement {
+            Some(replacement) => write!(f, ", use {} instead", replacement),
+            None => Ok(()),
+        }
+    }
+}
+
+const fn deprecated(
+    version: &'static str,
+    replacement: Option<&'static str>,
+) -> Option<Deprecation> {
+    Some(Deprecation {
+        version,
+        removed: false,
+        replacement,
+    })
+}
+
+const fn removed(version: &'static str, replacement: Option<&'static str>) -> Option<Deprecation> {
+    Some(Deprecation {
+        version,
+        removed: true,
+        replacement,
+    })
+}
+
+impl CurlOption {
+    /// The number of values the option consumes, 0 or 1.
+    pub fn arity(&self) -> usize {
+        usize::from(self.takes_value)
+    }
+
+    /// The other names of the option, its short alias if any.
+    pub fn aliases(&self) -> impl Iterator<Item = &'static str> {
+        self.short.into_iter()
+    }
+
+    /// Whether every occurrence of the option counts, e.g. `-H`, rather than the last one alone as
+    /// for `-m`. A flag without a value means the same given once or more.
+    pub fn repeatable(&self) -> bool {
+        !self.takes_value
+            || is_data_option(self.name)
+            || matches!(
+                self.name,
+                "--header"
+                    | "--form"
+                    | "--form-string"
+                    | "--cookie"
+                    | "--resolve"
+                    | "--upload-file"
+                    | "--output"
+                    | "--url"
+            )
+    }
+}
+
+/// Serialized with its `arity` and `aliases` in place of `takes_value` and `short`, and without
+/// `deprecated` for a current option.
+impl Serialize for CurlOption {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut option = serializer.serialize_struct("CurlOption", 7)?;
+        option.serialize_field("name", self.name)?;
+        option.serialize_field("aliases", &self.aliases().collect::<Vec<_>>())?;
+        option.serialize_field("arity", &self.arity())?;
+        option.serialize_field("category", &self.category)?;
+        option.serialize_field("description", self.description)?;
+        option.serialize_field("since", self.since)?;
+        match &self.deprecated {
+            Some(deprecated) => option.serialize_field("deprecated", deprecated)?,
+            None => option.skip_field("deprecated")?,
+        }
+        option.end()
+    }
+}
+
+macro_rules! curl_option {
+    ($name:expr, $short:expr, $takes_value:expr, $category:ident, $description:expr, $since:expr) => {
+        curl_option!($name, $short, $takes_value, $category, $description, $since; None)
+    };
+    ($name:expr, $short:expr, $takes_value:expr, $category:ident, $description:expr, $since:expr; $deprecated:expr) => {
+        CurlOption {
+            name: $name,
+            short: $short,
+            takes_value: $takes_value,
+            category: Category::$category,
+            description: $description,
+            since: $since,
+            deprecated: $deprecated,
+        }
+    };
+}
+
+/// The registry of the curl options nomcurl knows about.
+#[rustfmt::skip]
+pub static OPTIONS: &[CurlOption] = &[
+    curl_option!("--request", Some("-X"), true, Request, "Specify the request method", "6.0"),
+    curl_option!("--get", Some("-G"), false, Request, "Put the post data in the url and use GET", "7.8.1"),
+    curl_option!("--head", Some("-I"), false, Request, "Fetch the headers only", "4.0"),
+    curl_option!("--url", None, true, Request, "The url to work with", "7.5"),
+    curl_option!("--upload-file", Some("-T"), true, Request, "Transfer a local file to the url", "4.0"),
+    curl_option!("--header", Some("-H"), true, Header, "Pass a custom header to the server", "5.0"),
+    curl_option!("--user-agent", Some("-A"), true, Header, "Send the User-Agent header", "4.5.1"),
+    curl_option!("--referer", Some("-e"), true, Header, "Send the Referer header", "4.0"),
+    curl_option!("--compressed", None, false, Header, "Request a compressed response", "7.10"),
+    curl_option!("--data", Some("-d"), true, Body, "HTTP POST data", "4.0"),
+    curl_option!("--data-raw", None, true, Body, "HTTP POST data, '@' allowed", "7.43.0"),
+    curl_option!("--data-binary", None, true, Body, "HTTP POST binary data", "7.2"),
+    curl_option!("--data-ascii", None, true, Body, "HTTP POST ascii data", "7.2"),
+    curl_option!("--data-urlencode", None, true, Body, "HTTP POST data url encoded", "7.18.0"),
+    curl_option!("--json", None, true, Body, "HTTP POST JSON data", "7.82.0"),
+    curl_option!("--form", Some("-F"), true, Body, "Specify multipart form data", "5.0"),
+    curl_option!("--form-string", None, true, Body, "Specify multipart form data literally", "7.13.2"),
+    curl_option!("--user", Some("-u"), true, Auth, "Server user and password", "4.0"),
+    curl_option!("--basic", None, false, Auth, "Use HTTP Basic Authentication", "7.10.6"),
+    curl_option!("--digest", None, false, Auth, "Use HTTP Digest Authentication", "7.10.6"),
+    curl_option!("--ntlm", None, false, Auth, "Use HTTP NTLM authentication", "7.10.6"),
+    curl_option!("--ntlm-wb", None, false, Auth, "Use HTTP NTLM authentication with winbind", "7.22.0"; deprecated("8.8.0", None)),
+    curl_option!("--krb4", None, true, Auth, "Enable Kerberos 4 with this security level", "7.3"; removed("7.17.0", Some("--krb"))),
+    curl_option!("--negotiate", None, false, Auth, "Use HTTP Negotiate (SPNEGO) authentication", "7.10.6"),
+    curl_option!("--oauth2-bearer", None, true, Auth, "OAuth 2 Bearer Token", "7.33.0"),
+    curl_option!("--netrc", Some("-n"), false, Auth, "Read the credentials from ~/.netrc", "4.6"),
+    curl_option!("--insecure", Some("-k"), false, Tls, "Allow insecure server connections", "7.10"),
+    curl_option!("--cacert", None, true, Tls, "CA certificate to verify peer against", "7.5"),
+    curl_option!("--capath", None, true, Tls, "CA directory to verify peer against", "7.9.8"),
+    curl_option!("--cert", Some("-E"), true, Tls, "Client certificate file and password", "5.0"),
+    curl_option!("--key", None, true, Tls, "Private key file name", "7.9.3"),
+    curl_option!("--tlsv1.2", None, false, Tls, "Use TLSv1.2 or greater", "7.34.0"),
+    curl_option!("--tlsv1.3", None, false, Tls, "Use TLSv1.3 or greater", "7.52.0"),
+    curl_option!("--sslv2", Some("-2"), false, Tls, "Use SSLv2", "5.9"; deprecated("7.77.0", Some("--tlsv1.2"))),
+    curl_option!("--sslv3", Some("-3"), false, Tls, "Use SSLv3", "7.9.2"; deprecated("7.77.0", Some("--tlsv1.2"))),
+    curl_option!("--ftp-ssl", None, false, Tls, "Try SSL/TLS for FTP", "7.11.0"; deprecated("7.20.0", Some("--ssl"))),
+    curl_option!("--ftp-ssl-reqd", None, false, Tls, "Require SSL/TLS for FTP", "7.15.5"; deprecated("7.20.0", Some("--ssl-reqd"))),
+    curl_option!("--egd-file", None, true, Tls, "EGD socket path for random data", "7.7"; deprecated("7.84.0", None)),
+    curl_option!("--random-file", None, true, Tls, "File for reading random data from", "7.7"; deprecated("7.84.0", None)),
+    curl_option!("--proxy", Some("-x"), true, Proxy, "Use this proxy", "4.0"),
+    curl_option!("--proxy-user", Some("-U"), true, Proxy, "Proxy user and password", "4.0"),
+    curl_option!("--noproxy", None, true, Proxy, "List of hosts which do not use proxy", "7.19.4"),
+    curl_option!("--socks5", None, true, Proxy, "SOCKS5 proxy on given host and port", "7.18.0"),
+    curl_option!("--socks5-gssapi-service", None, true, Proxy, "SOCKS5 proxy service name", "7.19.4"; deprecated("7.49.0", Some("--proxy-service-name"))),
+    curl_option!("--max-time", Some("-m"), true, Connection, "Maximum time allowed for transfer", "4.0"),
+    curl_option!("--connect-timeout", None, true, Connection, "Maximum time allowed for connection", "7.7"),
+    curl_option!("--retry", None, true, Connection, "Retry request if transient problems occur", "7.12.3"),
+    curl_option!("--retry-delay", None, true, Connection, "Wait time between retries", "7.12.3"),
+    curl_option!("--retry-max-time", None, true, Connection, "Retry only within this period", "7.12.3"),
+    curl_option!("--resolve", None, true, Connection, "Resolve the host and port to this address", "7.21.3"),
+    curl_option!("--ipv4", Some("-4"), false, Connection, "Resolve names to IPv4 addresses", "7.10.8"),
+    curl_option!("--ipv6", Some("-6"), false, Connection, "Resolve names to IPv6 addresses", "7.10.8"),
+    curl_option!("--http1.0", Some("-0"), false, Connection, "Use HTTP 1.0", "7.9.1"),
+    curl_option!("--http1.1", None, false, Connection, "Use HTTP 1.1", "7.33.0"),
+    curl_option!("--http2", None, false, Connection, "Use HTTP 2", "7.33.0"),
+    curl_option!("--location", Some("-L"), false, Redirect, "Follow redirects", "4.9"),
+    curl_option!("--max-redirs", None, true, Redirect, "Maximum number of redirects allowed", "7.5"),
+    curl_option!("--cookie", Some("-b"), true, Cookie, "Send cookies from string/file", "4.9"),
+    curl_option!("--cookie-jar", Some("-c"), true, Cookie, "Write cookies to filename after operation", "7.9"),
+    curl_option!("--output", Some("-o"), true, Output, "Write to file instead of stdout", "4.0"),
+    curl_option!("--remote-name", Some("-O"), false, Output, "Write output to a file named as the remote file", "4.0"),
+    curl_option!("--silent", Some("-s"), false, Output, "Silent mode", "4.0"),
+    curl_option!("--show-error", Some("-S"), false, Output, "Show error even when -s is used", "5.9"),
+    curl_option!("--verbose", Some("-v"), false, Output, "Make the operation more talkative", "4.0"),
+    curl_option!("--include", Some("-i"), false, Output, "Include protocol response headers in the output", "4.0"),
+    curl_option!("--write-out", Some("-w"), true, Output, "Use output FORMAT after completion", "6.5"),
+    curl_option!("--fail", Some("-f"), false, Output, "Fail silently (no output at all) on HTTP errors", "4.0"),
+    curl_option!("--metalink", None, false, Other, "Process the urls as metalink XML files", "7.27.0"; removed("7.78.0", None)),
+];
+
+/// Look up an option by its long name or its short alias, e.g. `--insecure` or `-k`.
+pub fn lookup(flag: &str) -> Option<&'static CurlOption> {
+    OPTIONS
+        .iter()
+        .find(|o| o.name == flag || o.short == Some(flag))
+}
+
+/// Whether the flag is the given option, under either its long name or its short alias.
+pub fn is_option(flag: &str, name: &str) -> bool {
+    lookup(flag).is_some_and(|o| o.name == name)
+}
+
+/// Whether the flag sends its value as the request body the way `-d` does.
+pub fn is_data_option(flag: &str) -> bool {
+    lookup(flag).is_some_and(|o| {
+        matches!(
+            o.name,
+            "--data"
+                | "--data-raw"
+                | "--data-binary"
+                | "--data-ascii"
+                | "--data-urlencode"
+                | "--json"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::generic_parse;
+
+    #[test]
+    fn test_lookup() {
+        let expect = lookup("--insecure");
+        generic_parse(lookup, "-k", expect);
+
+        let option = lookup("--max-time").unwrap();
+        assert_eq!(Some("-m"), option.short);
+        assert!(option.takes_value);
+        assert_eq!(Category::Connection, option.category);
+        assert_eq!(1, option.arity());
+        assert_eq!(vec!["-m"], option.aliases().collect::<Vec<_>>());
+        assert_eq!("4.0", option.since);
+        assert_eq!(0, lookup("--compressed").unwrap().arity());
+
+        let json = serde_json::to_string(option).unwrap();
+        let expect = r#"{"name":"--max-time","aliases":["-m"],"arity":1,"category":"connection","description":"Maximum time allowed for transfer","since":"4.0"}"#;
+        assert_eq!(expect, json);
+
+        generic_parse(lookup, "--not-an-option", None);
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let option = lookup("--ftp-ssl").unwrap();
+        let deprecated = option.deprecated.unwrap();
+        assert!(!deprecated.removed);
+        assert_eq!(
+            "deprecated in 7.20.0, use --ssl instead",
+            deprecated.to_string()
+        );
+        assert_eq!(
+            "removed in 7.78.0",
+            lookup("--metalink")
+                .unwrap()
+                .deprecated
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(None, lookup("--ssl-reqd").and_then(|o| o.deprecated));
+
+        let json = serde_json::to_string(lookup("-2").unwrap()).unwrap();
+        assert!(
+            json.ends_with(
+                r#""deprecated":{"version":"7.77.0","removed":false,"replacement":"--tlsv1.2"}}"#
+            ),
+            "{}",
+            json
+        );
+    }
+
+    #[test]
+    fn test_repeatable() {
+        assert!(lookup("-H").unwrap().repeatable());
+        assert!(lookup("--data-raw").unwrap().repeatable());
+        assert!(lookup("-k").unwrap().repeatable());
+        assert!(!lookup("-m").unwrap().repeatable());
+        assert!(!lookup("-X").unwrap().repeatable());
+    }
+
+    #[test]
+    fn test_is_option() {
+        assert!(is_option("-X", "--request"));
+        assert!(is_option("--request", "--request"));
+        assert!(!is_option("-H", "--request"));
+    }
+}
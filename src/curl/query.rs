@@ -0,0 +1,118 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{take_until, take_while1},
+    character::complete::{char, digit1},
+    combinator::{all_consuming, map, map_res, opt},
+    multi::many0,
+    sequence::{delimited, preceded},
+    IResult,
+};
+use serde_json::Value;
+
+use super::error::ParseError;
+
+/// A step of a query path.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// `.name` or `["name"]`.
+    Key(String),
+    /// `[0]`.
+    Index(usize),
+}
+
+fn key_segment(input: &str) -> IResult<&str, Segment> {
+    map(
+        preceded(
+            char('.'),
+            take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-'),
+        ),
+        |key: &str| Segment::Key(key.into()),
+    )(input)
+}
+
+fn bracket_segment(input: &str) -> IResult<&str, Segment> {
+    delimited(
+        preceded(opt(char('.')), char('[')),
+        alt((
+            map(
+                alt((
+                    delimited(char('"'), take_until("\""), char('"')),
+                    delimited(char('\''), take_until("'"), char('\'')),
+                )),
+                |key: &str| Segment::Key(key.into()),
+            ),
+            map_res(digit1, |i: &str| i.parse().map(Segment::Index)),
+        )),
+        char(']'),
+    )(input)
+}
+
+fn segments(input: &str) -> IResult<&str, Vec<Segment>> {
+    alt((
+        all_consuming(many0(alt((key_segment, bracket_segment)))),
+        // `.` alone is the whole document
+        map(all_consuming(char('.')), |_| Vec::new()),
+    ))(input)
+}
+
+/// Evaluate a jq-lite path such as `.url.domain`, `.flags[0]` or `.headers["Authorization"]`.
+///
+/// A key looked up in an array of `{ name, value }` objects (the headers and the flags) finds
+/// the value of the first one with that name, compared case-insensitively. A missing path
+/// evaluates to `null`, as in jq.
+pub fn query(document: &Value, expr: &str) -> Result<Value, ParseError> {
+    let (_, segments) = segments(expr.trim()).map_err(|_| ParseError::InvalidQuery(expr.into()))?;
+
+    let mut value = document;
+    for segment in segments {
+        let next = match (&segment, value) {
+            (Segment::Key(key), Value::Object(map)) => map.get(key),
+            (Segment::Key(key), Value::Array(items)) => items
+                .iter()
+                .find(|item| {
+                    item.get("name")
+                        .and_then(Value::as_str)
+                        .is_some_and(|name| name.eq_ignore_ascii_case(key))
+                })
+                .map(|item| item.get("value").unwrap_or(&Value::Null)),
+            (Segment::Index(index), Value::Array(items)) => items.get(*index),
+            _ => None,
+        };
+        match next {
+            Some(next) => value = next,
+            None => return Ok(Value::Null),
+        }
+    }
+    Ok(value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_query() {
+        let request = parse_curl_command(
+            "curl 'https://github.com/a?b=1' -H 'Authorization: Bearer x' -d 'a=1' -m '10' -k",
+        )
+        .unwrap();
+        let query = |expr: &str| request.query(expr).unwrap();
+
+        assert_eq!("github.com", query(".url.domain"));
+        assert_eq!("Bearer x", query(".headers[\"authorization\"]"));
+        assert_eq!("10", query(".flags['-m']"));
+        assert_eq!("a=1", query(".data[0]"));
+        assert_eq!(serde_json::json!(["b", "1"]), query(".url.queries.[0]"));
+        assert_eq!(Value::Null, query(".headers.Accept"));
+        assert_eq!(Value::Null, query(".flags[\"-k\"]"));
+        assert_eq!(Value::Null, query(".url.domain.x"));
+        assert_eq!("https", query(".")["url"]["protocol"]);
+
+        assert_eq!(
+            Err(ParseError::InvalidQuery("url".into())),
+            request.query("url")
+        );
+        assert!(request.query(".data[x]").is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use super::{
+    curl_parsers::{
+        data_parse, flag_parse, header_parse, method_parse, remove_curl_cmd_header, url_param_parse,
+    },
+    dialect,
+    error::{ParseError, TokenKind},
+    options,
+    request::{is_curl_invocation, parse_curl_command, ParsedRequest},
+    Curl,
+};
+use crate::prelude::*;
+use nom::branch::alt;
+
+/// The params of a command parsed in lenient mode, along with the errors of the tokens skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovered<'a> {
+    /// The params parsed, in input order.
+    pub curls: Vec<Curl<'a>>,
+    /// Every problem found, in input order, empty for a valid command.
+    pub errors: Vec<ParseError>,
+}
+
+impl<'a> Recovered<'a> {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The request of the params parsed, which fails without a url only.
+    pub fn into_request(self) -> Result<ParsedRequest<'a>, ParseError> {
+        ParsedRequest::new(self.curls)
+    }
+}
+
+/// Parse a single curl command like `request::parse_curl_command`, but keep going after a bad
+/// token: it's reported and skipped up to the next flag, so every problem is found in one run.
+pub fn parse_curl_command_lenient(input: &str) -> Recovered<'_> {
+    let mut recovered = recover_posix_command(input);
+    if let (false, Some(hint)) = (recovered.errors.is_empty(), dialect::detect(input)) {
+        // The command of another shell is valid if it's read once normalised
+        if let Some(normalized) = dialect::normalize(input, hint.sign) {
+            let normalized = recover_posix_command(&normalized);
+            if normalized.errors.is_empty() {
+                recovered.curls = normalized.curls.into_iter().map(Curl::into_owned).collect();
+                recovered.errors.clear();
+                return recovered;
+            }
+        }
+        recovered
+            .errors
+            .insert(0, ParseError::UnsupportedDialect(hint));
+    }
+    recovered
+}
+
+fn recover_posix_command(input: &str) -> Recovered<'_> {
+    let mut recovered = Recovered {
+        curls: Vec::new(),
+        errors: Vec::new(),
+    };
+    let trimmed = input.trim_start();
+    if !is_curl_invocation(trimmed) {
+        recovered.errors.push(ParseError::NotCurl);
+        return recovered;
+    }
+
+    let mut rest = remove_curl_cmd_header(trimmed);
+    let mut has_url = false;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '\\');
+        if rest.is_empty() {
+            break;
+        }
+
+        let parsed = alt((
+            url_param_parse,
+            method_parse,
+            header_parse,
+            data_parse,
+            flag_parse,
+        ))(rest)
+        .or_else(|e| bare_flag(rest).ok_or(e));
+        let (after, curl) = match parsed {
+            Ok((after, curl)) if after.len() < rest.len() => (after, curl),
+            // A bare word which isn't a url
+            Err(e) if rest.starts_with(['\'', '"']) => {
+                recovered
+                    .errors
+                    .push(ParseError::from_nom(input, TokenKind::Url, e));
+                rest = skip_to_next_flag(rest);
+                continue;
+            }
+            Ok(_) => {
+                let err = nom::error::Error::new(rest, nom::error::ErrorKind::Fail);
+                let e = ParseError::from_nom(input, TokenKind::Param, nom::Err::Error(err));
+                recovered.errors.push(e);
+                rest = skip_to_next_flag(rest);
+                continue;
+            }
+            Err(e) => {
+                let token = match e {
+                    nom::Err::Failure(_) => TokenKind::Value,
+                    _ => TokenKind::Param,
+                };
+                recovered.errors.push(ParseError::from_nom(input, token, e));
+                rest = skip_to_next_flag(rest);
+                continue;
+            }
+        };
+        has_url |= matches!(curl, Curl::URL(_));
+        recovered.curls.push(curl);
+        rest = after;
+    }
+
+    if !has_url {
+        recovered.errors.push(ParseError::MissingUrl);
+    }
+    recovered
+}
+
+/// Parse a single curl command like `request::parse_curl_command`, but a syntax error comes as a
+/// `ParseError::Partial` with the params parsed before the bad token, e.g. for a UI to still show
+/// the url and the headers.
+pub fn parse_curl_command_partial(input: &str) -> Result<ParsedRequest<'_>, ParseError> {
+    let error = match parse_curl_command(input) {
+        Err(ParseError::Syntax(e)) => e,
+        result => return result,
+    };
+    let curls = parse_curl_command_lenient(&input[..error.offset])
+        .curls
+        .into_iter()
+        .map(Curl::into_owned)
+        .collect();
+    Err(ParseError::Partial {
+        curls,
+        error: Box::new(ParseError::Syntax(error)),
+    })
+}
+
+/// A flag unknown to `options`, which `flag_parse` rejects when a word follows it.
+fn bare_flag(input: &str) -> Option<(&str, Curl<'_>)> {
+    let len = input.find(char::is_whitespace).unwrap_or(input.len());
+    let (flag, rest) = input.split_at(len);
+    let takes_value = options::lookup(flag).is_some_and(|o| o.takes_value);
+    if !flag.starts_with('-')
+        || takes_value
+        || matches!(
+            flag,
+            "-X" | "--request" | "-H" | "--header" | "-d" | "--data"
+        )
+    {
+        return None;
+    }
+    Some((rest, Curl::new_as_flag(flag)?))
+}
+
+/// The text from the next flag after the bad token at the start of the input, a `-` following a
+/// space out of quotes, or the end of the input.
+fn skip_to_next_flag(input: &str) -> &str {
+    let mut quote = None;
+    let mut after_space = false;
+    for (i, c) in input.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '-') if after_space => return &input[i..],
+            _ => {}
+        }
+        after_space = quote.is_none() && c.is_whitespace();
+    }
+    ""
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::error::ErrorCode;
+
+    #[test]
+    fn test_parse_curl_command_lenient() {
+        let input =
+            "curl 'https://github.com' -H |oops -k \\\n  -d 'a=1' |stray -X '' -m '10' -d 'b=2";
+        let recovered = parse_curl_command_lenient(input);
+
+        let params: Vec<String> = recovered.curls.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            vec!["'https://github.com'", "-k", "-d 'a=1'", "-X ''", "-m '10'"],
+            params
+        );
+        let errors: Vec<String> = recovered
+            .errors
+            .iter()
+            .map(|e| format!("{:#}", e))
+            .collect();
+        assert_eq!(
+            vec![
+                "invalid value of -H: expected a quoted value, found \"|oops\"",
+                "invalid param: expected a param, e.g. -H 'Accept: */*', found \"|stray\"",
+                "invalid value of -d: expected a closing quote, found \"'b=2\"",
+            ],
+            errors
+        );
+        assert!(!recovered.is_valid());
+        assert!(recovered.into_request().is_ok());
+    }
+
+    #[test]
+    fn test_parse_curl_command_lenient_valid() {
+        let input = "curl 'https://github.com' -X 'POST' -H 'A: 1' -d 'a=1' --retry '3' -k";
+        let recovered = parse_curl_command_lenient(input);
+
+        assert!(recovered.is_valid());
+        assert_eq!(parse_curl_command(input), recovered.into_request());
+    }
+
+    #[test]
+    fn test_parse_curl_command_lenient_missing_url() {
+        let recovered = parse_curl_command_lenient("curl -k -H 'A: 1'");
+        assert_eq!(2, recovered.curls.len());
+        assert_eq!(vec![ParseError::MissingUrl], recovered.errors);
+
+        let recovered = parse_curl_command_lenient("curl -k 'https://github.com' 'oops'");
+        assert_eq!(2, recovered.curls.len());
+        assert_eq!(
+            "invalid url at line 1, column 30: expected a url, e.g. https://github.com, found \"'oops'\"",
+            recovered.errors[0].to_string()
+        );
+
+        let recovered = parse_curl_command_lenient("wget 'https://github.com'");
+        assert_eq!(vec![ParseError::NotCurl], recovered.errors);
+    }
+
+    #[test]
+    fn test_parse_curl_command_partial() {
+        let input = "curl 'https://github.com' -H 'Accept: */*' -k -d ;oops -H 'A: 1'";
+        let err = parse_curl_command_partial(input).unwrap_err();
+
+        let params: Vec<String> = err.partial_curls().iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            vec!["'https://github.com'", "-H 'Accept: */*'", "-k"],
+            params
+        );
+        assert_eq!(parse_curl_command(input).unwrap_err(), *err.root());
+        assert_eq!(ErrorCode::BadQuoting, err.code());
+        assert_eq!(
+            parse_curl_command(input).unwrap_err().to_string(),
+            err.to_string()
+        );
+
+        assert_eq!(
+            Err(ParseError::MissingUrl),
+            parse_curl_command_partial("curl -k")
+        );
+        assert!(parse_curl_command_partial("curl 'https://github.com' -k").is_ok());
+    }
+}
@@ -0,0 +1,1074 @@
+use alloc::borrow::Cow;
+use core::{fmt, str::FromStr};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    curl_parsers::{
+        curl_cmd_parse, curl_program_len, params_parse, raw_url_parse, remove_curl_cmd_header,
+    },
+    dialect,
+    document::RequestDocument,
+    error::{lines_inclusive, ParseError, TokenKind},
+    format,
+    method::{self, Method, MethodPolicy},
+    options,
+    url_parser::{self, CurlURL, Protocol, ProtocolPolicy},
+    Curl, CurlStru,
+};
+use crate::prelude::*;
+
+#[cfg(feature = "std")]
+use super::{cookies, diff, explain, lint, merge, query, redact, summary};
+
+/// A fully parsed curl command: the target url plus every parsed param in input order.
+///
+/// It borrows from the parsed input, see `into_owned` to keep it around, and (de)serializes as
+/// a `document::RequestDocument`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(into = "RequestDocument", try_from = "RequestDocument")]
+pub struct ParsedRequest<'a> {
+    curls: Vec<Curl<'a>>,
+}
+
+impl<'a> ParsedRequest<'a> {
+    /// Build a request from parsed params, the params must contain a `Curl::URL`.
+    pub fn new(curls: Vec<Curl<'a>>) -> Result<Self, ParseError> {
+        if curls.iter().any(|c| matches!(c, Curl::URL(_))) {
+            Ok(Self { curls })
+        } else {
+            Err(ParseError::MissingUrl)
+        }
+    }
+
+    /// Build a request of params known to contain a `Curl::URL`, e.g. by the expansion of
+    /// `nomcurl_macros::curl!`, see `new`.
+    #[doc(hidden)]
+    pub fn new_unchecked(curls: Vec<Curl<'a>>) -> Self {
+        debug_assert!(curls.iter().any(|c| matches!(c, Curl::URL(_))));
+        Self { curls }
+    }
+
+    pub fn curls(&self) -> &[Curl<'a>] {
+        &self.curls
+    }
+
+    pub fn into_curls(self) -> Vec<Curl<'a>> {
+        self.curls
+    }
+
+    /// Copy the borrowed strings, detaching the request from the input.
+    pub fn into_owned(self) -> ParsedRequest<'static> {
+        ParsedRequest {
+            curls: self.curls.into_iter().map(Curl::into_owned).collect(),
+        }
+    }
+
+    /// The first target url of the command.
+    pub fn url(&self) -> &CurlURL<'a> {
+        self.curls
+            .iter()
+            .find_map(|c| match c {
+                Curl::URL(u) => Some(u),
+                _ => None,
+            })
+            .expect("ParsedRequest always holds a url")
+    }
+
+    pub fn url_mut(&mut self) -> &mut CurlURL<'a> {
+        self.curls
+            .iter_mut()
+            .find_map(|c| match c {
+                Curl::URL(u) => Some(u),
+                _ => None,
+            })
+            .expect("ParsedRequest always holds a url")
+    }
+
+    /// The method of the last `-X`, if any, as curl uses the last one. An invalid method is
+    /// ignored.
+    pub fn method(&self) -> Option<Method> {
+        let last = self.curls.iter().rev().find_map(|c| match c {
+            Curl::Method(m) => Some(m),
+            _ => None,
+        })?;
+        last.data.as_deref()?.parse().ok()
+    }
+
+    /// The method curl would actually send: `-X` wins, otherwise it's implied by `-I`, `-G`,
+    /// `-T`, `-F` or the presence of data.
+    pub fn effective_method(&self) -> Method {
+        self.method().unwrap_or_else(|| self.implied_method())
+    }
+
+    /// The method implied by `-I`, `-G`, `-T`, `-F` or the presence of data, ignoring `-X`.
+    pub fn implied_method(&self) -> Method {
+        if self.has_flag("--head") {
+            Method::HEAD
+        } else if self.has_flag("--get") {
+            Method::GET
+        } else if self.has_flag("--upload-file") {
+            Method::PUT
+        } else if !self.data().is_empty() || self.has_flag("--form") {
+            Method::POST
+        } else {
+            Method::GET
+        }
+    }
+
+    /// The url curl would actually request, `-G` appends the payloads to the queries.
+    pub fn effective_url(&self) -> String {
+        let url = self.url().to_string();
+        let data = self.payloads();
+
+        if !self.has_flag("--get") || data.is_empty() {
+            return url;
+        }
+
+        let has_query = self.url().queries.as_ref().is_some_and(|q| !q.is_empty());
+        let separator = if has_query { '&' } else { '?' };
+        format!("{}{}{}", url, separator, data.join("&"))
+    }
+
+    /// The raw `Name: value` strings of every `-H`.
+    pub fn headers(&self) -> impl Iterator<Item = &str> {
+        self.curls.iter().filter_map(|c| match c {
+            Curl::Header(h) => h.data.as_deref(),
+            _ => None,
+        })
+    }
+
+    /// The value of the first header with the given name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers().find_map(|h| {
+            let (n, v) = split_header(h)?;
+            n.trim().eq_ignore_ascii_case(name).then(|| v.trim())
+        })
+    }
+
+    /// Replace the first header with the given name and drop its repetitions, or add the header
+    /// after the last one.
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        let header = format!("{}: {}", name, value);
+        let mut found = false;
+        self.curls.retain_mut(|c| match c {
+            Curl::Header(h) if is_header(h, name) => {
+                h.data = Some(header.clone().into());
+                !core::mem::replace(&mut found, true)
+            }
+            _ => true,
+        });
+
+        if !found {
+            let index = self
+                .curls
+                .iter()
+                .rposition(|c| matches!(c, Curl::URL(_) | Curl::Method(_) | Curl::Header(_)))
+                .map_or(0, |i| i + 1);
+            let header = Curl::Header(CurlStru::new_with_data("-H", header));
+            self.curls.insert(index, header);
+        }
+    }
+
+    /// Remove every header with the given name, returns whether any was found.
+    pub fn remove_header(&mut self, name: &str) -> bool {
+        let len = self.curls.len();
+        self.curls
+            .retain(|c| !matches!(c, Curl::Header(h) if is_header(h, name)));
+        self.curls.len() != len
+    }
+
+    /// Replace the `-X` methods by the given one, `None` leaves the method implied.
+    pub fn set_method(&mut self, method: Option<Method>) {
+        self.curls.retain(|c| !matches!(c, Curl::Method(_)));
+        if let Some(method) = method {
+            let index = self
+                .curls
+                .iter()
+                .position(|c| matches!(c, Curl::URL(_)))
+                .map_or(0, |i| i + 1);
+            let method = Curl::Method(CurlStru::new_with_data("-X", method.to_string()));
+            self.curls.insert(index, method);
+        }
+    }
+
+    /// The payloads of `-d` and its `--data-*` / `--json` siblings.
+    pub fn data(&self) -> Vec<&str> {
+        self.curls
+            .iter()
+            .filter_map(|c| match c {
+                Curl::Data(d) => d.data.as_deref(),
+                Curl::Flag(f) if options::is_data_option(&f.identifier) => f.data.as_deref(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The payloads as curl sends them, a `--data-urlencode` one percent-encoded, see `data` for
+    /// the written ones.
+    pub fn payloads(&self) -> Vec<Cow<'_, str>> {
+        self.curls
+            .iter()
+            .filter_map(|c| match c {
+                Curl::Data(d) => d.data.as_deref().map(Cow::Borrowed),
+                Curl::Flag(f) if options::is_option(&f.identifier, "--data-urlencode") => {
+                    f.data.as_deref().map(urlencoded)
+                }
+                Curl::Flag(f) if options::is_data_option(&f.identifier) => {
+                    f.data.as_deref().map(Cow::Borrowed)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn flags(&self) -> impl Iterator<Item = &CurlStru<'a>> {
+        self.curls.iter().filter_map(|c| match c {
+            Curl::Flag(f) => Some(f),
+            _ => None,
+        })
+    }
+
+    /// The last flag matching the given option, under either its long name or its short alias,
+    /// as curl uses the last value of an option given more than once.
+    pub fn flag(&self, name: &str) -> Option<&CurlStru<'a>> {
+        self.flags()
+            .filter(|f| f.identifier == name || options::is_option(&f.identifier, name))
+            .last()
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flag(name).is_some()
+    }
+
+    /// The cookies of the `-b` strings and `Cookie` headers, see `cookies::cookies`.
+    #[cfg(feature = "std")]
+    pub fn cookies(&self) -> Vec<cookies::Cookie> {
+        cookies::cookies(self)
+    }
+
+    /// What every param of the command does, see `explain::explain`.
+    #[cfg(feature = "std")]
+    pub fn explain(&self) -> Vec<explain::Explanation> {
+        explain::explain(self)
+    }
+
+    /// Counts and settings overview of the request, see `summary::RequestSummary`.
+    #[cfg(feature = "std")]
+    pub fn summary(&self) -> summary::RequestSummary {
+        summary::RequestSummary::new(self)
+    }
+
+    /// Apply the policy to the scheme of the url, only `ProtocolPolicy::Reject` fails.
+    pub fn check_protocol(&self, policy: ProtocolPolicy) -> Result<(), ParseError> {
+        match (policy, &self.url().protocol) {
+            (ProtocolPolicy::Reject, Protocol::Other(_)) => Err(ParseError::UnknownProtocol),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate every `-X` method, a custom verb only fails with `MethodPolicy::Reject`.
+    pub fn check_method(&self, policy: MethodPolicy) -> Result<(), ParseError> {
+        for curl in &self.curls {
+            let Curl::Method(m) = curl else {
+                continue;
+            };
+            let value = m.data.as_deref().unwrap_or_default();
+            match (policy, value.parse::<Method>()) {
+                (MethodPolicy::Reject, Ok(Method::Custom(_))) | (_, Err(_)) => {
+                    return Err(ParseError::InvalidMethod(value.into()))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the command for likely mistakes, see `lint::lint`.
+    #[cfg(feature = "std")]
+    pub fn lint(&self) -> Vec<lint::Finding> {
+        lint::lint(self)
+    }
+
+    /// Write the request back as a curl command laid out by the options, see `format::format`.
+    pub fn format(&self, options: &format::FormatOptions) -> String {
+        format::format(self, options)
+    }
+
+    /// The curl command of the request on a single line, every value quoted for a POSIX shell so
+    /// that it parses back to the same request. See `format` for the other layouts and shells.
+    pub fn to_curl_string(&self) -> String {
+        self.format(&format::FormatOptions::default())
+    }
+
+    /// A copy of the request with its secrets replaced, see `redact::Redactor`.
+    #[cfg(feature = "std")]
+    pub fn redacted(&self) -> ParsedRequest<'a> {
+        redact::Redactor::new().redact(self)
+    }
+
+    /// The structured comparison with another request, see `diff::RequestDiff`.
+    #[cfg(feature = "std")]
+    pub fn diff(&self, other: &ParsedRequest<'_>) -> diff::RequestDiff {
+        diff::RequestDiff::new(self, other)
+    }
+
+    /// The request with the params of the other one applied on top, see `merge::merge`.
+    #[cfg(feature = "std")]
+    pub fn merge(&self, overlay: &ParsedRequest<'a>) -> ParsedRequest<'a> {
+        merge::merge(self, overlay.curls())
+    }
+
+    /// The pretty printed JSON of the request, see `document::RequestDocument`.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Read a request back from the JSON of `to_json`, or from a hand-written spec where the url
+    /// is a string and the headers are `Name: value` strings or a map.
+    pub fn from_json(input: &str) -> serde_json::Result<ParsedRequest<'static>> {
+        serde_json::from_str(input)
+    }
+
+    /// Evaluate a jq-lite path over the JSON of the request, see `query::query`.
+    #[cfg(feature = "std")]
+    pub fn query(&self, expr: &str) -> Result<serde_json::Value, ParseError> {
+        let document = serde_json::to_value(self).expect("a request is always serializable");
+        query::query(&document, expr)
+    }
+
+    /// The YAML of the request, with the same structure as `to_json`.
+    #[cfg(feature = "std")]
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Read a request back from the YAML of `to_yaml`, e.g. after editing it by hand.
+    #[cfg(feature = "std")]
+    pub fn from_yaml(input: &str) -> Result<ParsedRequest<'static>, serde_yaml::Error> {
+        serde_yaml::from_str(input)
+    }
+
+    /// The TOML of the request, with the same structure as `to_json`.
+    #[cfg(feature = "std")]
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// Read a request back from the TOML of `to_toml`, e.g. to turn it into a curl command.
+    #[cfg(feature = "std")]
+    pub fn from_toml(input: &str) -> Result<ParsedRequest<'static>, toml::de::Error> {
+        toml::from_str(input)
+    }
+
+    /// The raw HTTP/1.1 request curl would send, see `codegen::raw_http::generate`.
+    #[cfg(feature = "std")]
+    pub fn to_raw_http(&self) -> std::io::Result<Vec<u8>> {
+        crate::codegen::raw_http::generate(self)
+    }
+}
+
+impl fmt::Display for ParsedRequest<'_> {
+    /// Write the request back as a single line curl command.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(&format::FormatOptions::default()))
+    }
+}
+
+/// The request owns its strings, see `parse_curl_command` to borrow them from the input.
+impl FromStr for ParsedRequest<'static> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_curl_command(s).map(ParsedRequest::into_owned)
+    }
+}
+
+/// Parse a single curl command into a `ParsedRequest`.
+///
+/// A command written for cmd.exe or PowerShell, e.g. with `^` line continuations, is read once
+/// normalised (see `dialect::normalize`), the request then owns its strings.
+#[cfg_attr(feature = "trace", tracing::instrument(level = "debug", skip_all, err))]
+pub fn parse_curl_command(input: &str) -> Result<ParsedRequest<'_>, ParseError> {
+    if !is_curl_invocation(input.trim_start()) {
+        return Err(ParseError::NotCurl);
+    }
+
+    let err = match parse_posix_command(input) {
+        Ok(request) => return Ok(request),
+        Err(e) => e,
+    };
+    // Another shell's quoting is likely why the parsers stopped
+    let Some(hint) = dialect::detect(input) else {
+        return Err(err);
+    };
+    #[cfg(feature = "trace")]
+    tracing::debug!(sign = %hint.sign, "normalising the command");
+    match dialect::normalize(input, hint.sign)
+        .map(|command| parse_posix_command(&command).map(ParsedRequest::into_owned))
+    {
+        Some(Ok(request)) => Ok(request),
+        _ => Err(ParseError::UnsupportedDialect(hint)),
+    }
+}
+
+fn parse_posix_command(input: &str) -> Result<ParsedRequest<'_>, ParseError> {
+    let (rest, curls) = curl_cmd_parse(input).map_err(|e| {
+        match ParseError::from_nom(input, TokenKind::Value, e) {
+            ParseError::MissingUrl => misplaced_url(input)
+                .map(ParseError::InvalidMethod)
+                .or_else(|| bare_url(input).map(ParseError::BareUrl))
+                .unwrap_or(ParseError::MissingUrl),
+            e => e,
+        }
+    })?;
+    // The params stop at a word which is none, e.g. a stray one or a pipe: it's reported
+    // rather than dropped along with the params after it
+    if !rest.trim().is_empty() {
+        let e = nom::error::Error::new(rest, nom::error::ErrorKind::Fail);
+        return Err(ParseError::from_nom(
+            input,
+            TokenKind::Param,
+            nom::Err::Error(e),
+        ));
+    }
+    ParsedRequest::new(curls)
+}
+
+/// A url given to `-X` in a command without a url, e.g. `curl -X 'https://github.com'`.
+fn misplaced_url(input: &str) -> Option<String> {
+    let (_, curls) = params_parse(remove_curl_cmd_header(input.trim_start())).ok()?;
+    curls.into_iter().find_map(|c| match c {
+        Curl::Method(m) => m
+            .data
+            .filter(|m| method::is_url_like(m))
+            .map(|m| m.into_owned()),
+        _ => None,
+    })
+}
+
+/// The url of a command without one, from the word the params parser stopped on when it looks
+/// like a url, e.g. `http://example.com/api` for `curl -k example.com/api`.
+fn bare_url(input: &str) -> Option<String> {
+    let (rest, _) = params_parse(remove_curl_cmd_header(input.trim_start())).ok()?;
+    let rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '\\');
+    let word = match raw_url_parse(rest) {
+        // A quoted url with a scheme failed to parse for another reason
+        Ok((_, word)) if word.contains("://") => return None,
+        Ok((_, word)) => word,
+        Err(_) => Cow::Borrowed(&rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())]),
+    };
+    if !url_parser::looks_like_url(&word) {
+        return None;
+    }
+    Some(match word.contains("://") {
+        true => word.into_owned(),
+        // curl assumes http for a url without a scheme
+        false => format!("http://{}", word),
+    })
+}
+
+/// Find every curl invocation in the input (e.g. a shell script or a log extract) and parse them all.
+pub fn parse_all_curl_commands(input: &str) -> Vec<Result<ParsedRequest<'_>, ParseError>> {
+    find_curl_commands(input)
+        .into_iter()
+        .map(parse_curl_command)
+        .collect()
+}
+
+/// Split the input into the text of each curl invocation.
+///
+/// An invocation starts on a line beginning with `curl` (an optional `$ ` prompt is skipped)
+/// and runs until a line which neither ends with a `\` continuation nor leaves a quote open.
+pub fn find_curl_commands(input: &str) -> Vec<&str> {
+    let mut commands = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut quote: Option<char> = None;
+    let mut offset = 0;
+
+    for line in lines_inclusive(input) {
+        let line_start = offset;
+        offset += line.len();
+
+        let cmd_start = match start {
+            Some(s) => s,
+            None => {
+                let Some(s) = command_start(line) else {
+                    continue;
+                };
+                start = Some(line_start + s);
+                line_start + s
+            }
+        };
+
+        let scan_from = cmd_start.max(line_start);
+        if ends_command(&input[scan_from..offset], &mut quote) {
+            commands.push(input[cmd_start..offset].trim_end());
+            start = None;
+        }
+    }
+
+    if let Some(s) = start {
+        commands.push(input[s..].trim_end());
+    }
+
+    commands
+}
+
+/// The offset of the curl invocation the line begins with, after an optional `$ ` prompt.
+pub(crate) fn command_start(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    let cmd = trimmed.strip_prefix("$ ").unwrap_or(trimmed).trim_start();
+    is_curl_invocation(cmd).then(|| line.len() - cmd.len())
+}
+
+/// Track the quotes of the line of a command, whether the command ends with it: no quote is
+/// left open and the line has no `\` continuation. A quote escaped by a `\` out of single quotes
+/// is skipped, as `curl_parsers` reads it.
+pub(crate) fn ends_command(line: &str, quote: &mut Option<char>) -> bool {
+    // Jump from quote to quote, a large payload is skipped at once
+    let bytes = line.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let found = match *quote {
+            Some('\'') => memchr::memchr(b'\'', &bytes[pos..]),
+            Some(q) => memchr::memchr2(q as u8, b'\\', &bytes[pos..]),
+            None => memchr::memchr3(b'\'', b'"', b'\\', &bytes[pos..]),
+        };
+        let Some(i) = found else {
+            break;
+        };
+        match bytes[pos + i] {
+            b'\\' => {
+                pos += i + 2;
+                continue;
+            }
+            q => {
+                *quote = match *quote {
+                    Some(_) => None,
+                    None => Some(q as char),
+                }
+            }
+        }
+        pos += i + 1;
+    }
+    quote.is_none() && !line.trim_end().ends_with('\\')
+}
+
+/// The `--data-urlencode` value as curl sends it: `content` and `=content` are encoded,
+/// `name=content` has its content encoded, `@file` and `name@file` read a file and are kept.
+fn urlencoded(data: &str) -> Cow<'_, str> {
+    match data.find(['=', '@']) {
+        Some(i) if data.as_bytes()[i] == b'@' => Cow::Borrowed(data),
+        Some(0) => Cow::Owned(url_parser::percent_encode(&data[1..])),
+        Some(i) => Cow::Owned(format!(
+            "{}={}",
+            &data[..i],
+            url_parser::percent_encode(&data[i + 1..])
+        )),
+        None => Cow::Owned(url_parser::percent_encode(data)),
+    }
+}
+
+/// Whether the `-H` param sets the header with the given name, compared case-insensitively.
+fn is_header(header: &CurlStru, name: &str) -> bool {
+    let header = header.data.as_deref().unwrap_or_default();
+    split_header(header).is_some_and(|(n, _)| n.trim().eq_ignore_ascii_case(name))
+}
+
+/// Split a `Name: value` header, or a `Name;` one which curl sends with an empty value.
+pub(crate) fn split_header(header: &str) -> Option<(&str, &str)> {
+    match header.split_once(':') {
+        Some(split) => Some(split),
+        None => Some((header.trim_end().strip_suffix(';')?, "")),
+    }
+}
+
+/// `curl` or `curl.exe`, maybe under a path, followed by a whitespace or nothing, so that e.g.
+/// `curling` isn't taken as a curl command, see `curl_parsers::curl_program_len`.
+pub fn is_curl_invocation(input: &str) -> bool {
+    curl_program_len(input).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::error;
+    use crate::test_util::{generic_parse, golden, strategies};
+    use proptest::{collection::vec, prelude::*};
+    use std::borrow::Cow;
+
+    proptest! {
+        #[test]
+        fn test_parsed_request_round_trip(
+            url in strategies::url(),
+            params in vec(strategies::param(), 0..8),
+        ) {
+            let url = CurlURL::try_from(url.as_str()).unwrap().into_owned();
+            let mut curls = vec![Curl::URL(url)];
+            curls.extend(params);
+            let request = ParsedRequest::new(curls).unwrap();
+
+            let command = request.to_string();
+            prop_assert_eq!(Ok(request), parse_curl_command(&command), "{}", command);
+        }
+    }
+
+    const TEST_SCRIPT: &str = r#"
+#!/bin/sh
+echo "fetching"
+curl 'https://github.com/rust-lang/rust' -H 'Accept: */*'
+$ curl 'http://example.com/api?id=1' \
+    -X 'POST' \
+    -d 'name=
+nomcurl'
+curling is not a command
+curl
+"#;
+
+    #[test]
+    fn test_find_curl_commands() {
+        let expect = vec![
+            "curl 'https://github.com/rust-lang/rust' -H 'Accept: */*'",
+            "curl 'http://example.com/api?id=1' \\\n    -X 'POST' \\\n    -d 'name=\nnomcurl'",
+            "curl",
+        ];
+
+        generic_parse(find_curl_commands, TEST_SCRIPT, expect);
+    }
+
+    #[test]
+    fn test_parse_curl_command_escaped_quotes() {
+        let input = r#"curl 'https://a.com' -d '{"name":"O'"'"'Brien"}' -H "X: \"v\"" -k"#;
+        let request = parse_curl_command(input).unwrap();
+        assert_eq!(vec![r#"{"name":"O'Brien"}"#], request.data());
+        assert_eq!(Some(r#""v""#), request.header("X"));
+        assert!(request.has_flag("--insecure"));
+
+        let script = format!("{} \\\n  -m '5'\ncurl 'https://b.com'", input);
+        assert_eq!(2, find_curl_commands(&script).len());
+    }
+
+    #[test]
+    fn test_parse_curl_command_program() {
+        let inputs = [
+            "curl.exe 'https://github.com' -k",
+            "/usr/bin/curl 'https://github.com' -k",
+            r"'C:\tools\curl.exe' 'https://github.com' -k",
+            r#""C:\Program Files\curl\curl.exe" 'https://github.com' -k"#,
+        ];
+        for input in inputs {
+            let request = parse_curl_command(input).unwrap();
+            assert_eq!("curl 'https://github.com' -k", request.to_string());
+        }
+        assert_eq!(
+            Err(ParseError::NotCurl),
+            parse_curl_command("/usr/bin/curling 'https://github.com'")
+        );
+
+        let script = "$ /usr/local/bin/curl 'https://a.com'\necho\ncurl.exe 'https://b.com'";
+        assert_eq!(2, parse_all_curl_commands(script).len());
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let lines = [
+            "curl 'https://github.com' \\",
+            "  -H 'Accept: */*' \\",
+            "  -XPOST \\",
+            "  -d 'a=1' -k",
+        ];
+        let expect = parse_curl_command(&lines.join("\n")).unwrap().into_owned();
+        let endings = [
+            ["\n", "\n", "\n"],
+            ["\r\n", "\r\n", "\r\n"],
+            ["\r", "\r", "\r"],
+            ["\r\n", "\r", "\n"],
+            ["\n", "\r\n", "\r"],
+        ];
+        for ending in endings {
+            let mut input = lines[0].to_string();
+            for (line, ending) in lines[1..].iter().zip(ending) {
+                input.push_str(ending);
+                input.push_str(line);
+            }
+
+            assert_eq!(
+                Ok(&expect),
+                parse_curl_command(&input).as_ref(),
+                "{:?}",
+                input
+            );
+            let offset = input.find("-d").unwrap();
+            assert_eq!((4, 3), error::line_col(&input, offset), "{:?}", input);
+
+            let script = format!("{}{}curl 'https://a.com'{}", input, ending[0], ending[1]);
+            let commands = find_curl_commands(&script);
+            assert_eq!(vec![input.as_str(), "curl 'https://a.com'"], commands);
+        }
+    }
+
+    #[test]
+    fn test_multi_line_quotes() {
+        let input = "curl 'https://github.com' \\\n  -H 'Content-Type: application/json' \\\n  -d '{\n  \"a\": 1,\n  \"b\": [\n    -1\n  ]\n}' \\\n  -k";
+        let request = parse_curl_command(input).unwrap();
+        assert_eq!(
+            vec!["{\n  \"a\": 1,\n  \"b\": [\n    -1\n  ]\n}"],
+            request.data()
+        );
+        assert!(request.has_flag("--insecure"));
+        assert_eq!(request, parse_curl_command(&request.to_string()).unwrap());
+
+        // A `\\` ending a line in double quotes removes the line break, a line break in the quotes
+        // is kept as is, with a lone `\\` on its own line
+        let input =
+            "curl 'https://github.com' -d \"{\r\n  \\\"a\\\": 1, \\\n  \\\"b\\\": 2\r\n \\\n}\" -k";
+        let request = parse_curl_command(input).unwrap();
+        assert_eq!(vec!["{\r\n  \"a\": 1,   \"b\": 2\r\n }"], request.data());
+
+        // A line in the quotes looking like a param or a command is a part of the value
+        let script = "curl 'https://a.com' --data-raw 'x\n  -H \"A: 1\"\ncurl http://b.com\n' -k\ncurl 'https://c.com'";
+        let commands = find_curl_commands(script);
+        assert_eq!(2, commands.len(), "{:#?}", commands);
+        let request = parse_curl_command(commands[0]).unwrap();
+        assert_eq!(0, request.headers().count());
+        assert_eq!(
+            Some("x\n  -H \"A: 1\"\ncurl http://b.com\n"),
+            request.flag("--data-raw").and_then(|f| f.data.as_deref())
+        );
+        assert!(request.has_flag("-k"));
+
+        // The position of an error after the value counts its lines
+        let err = parse_curl_command("curl 'https://a.com' -d 'a\nb\nc' -H |oops").unwrap_err();
+        let ParseError::Syntax(e) = err else {
+            panic!("The error:\r\n{:#?}", err);
+        };
+        assert_eq!((3, 7), (e.line, e.column));
+    }
+
+    #[test]
+    fn test_golden_corpus() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/golden");
+        // The fixtures the parser can't read yet, e.g. `"chrome_cmd"`
+        const KNOWN_FAILURES: &[&str] = &[];
+        golden::check(dir.as_ref(), KNOWN_FAILURES, |input| {
+            let request = parse_curl_command(input.trim_end()).map_err(|e| e.to_string())?;
+            // The command written back, with its url first, reads as the same request
+            let command = request.to_string();
+            let reparsed = parse_curl_command(&command).unwrap();
+            assert_eq!(
+                RequestDocument::from(&request),
+                RequestDocument::from(&reparsed)
+            );
+            let lints: Vec<_> = request.lint().iter().map(|f| f.lint.code()).collect();
+            let snapshot = serde_json::json!({
+                "request": RequestDocument::from(&request),
+                "command": command,
+                "lints": lints,
+            });
+            Ok(serde_json::to_string_pretty(&snapshot).unwrap() + "\n")
+        });
+    }
+
+    #[test]
+    fn test_parse_all_curl_commands() {
+        let results = parse_all_curl_commands(TEST_SCRIPT);
+
+        assert_eq!(3, results.len(), "The results:\r\n{:#?}", results);
+        assert_eq!("github.com", results[0].as_ref().unwrap().url().domain);
+        assert_eq!(3, results[1].as_ref().unwrap().curls().len());
+        assert_eq!(Err(ParseError::MissingUrl), results[2]);
+    }
+
+    #[test]
+    #[cfg(feature = "trace")]
+    fn test_parse_curl_command_trace() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Captured(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for Captured {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            parse_curl_command("curl 'https://github.com/a?b=1' -H 'Authorization: secret' -k")
+                .unwrap();
+        });
+
+        let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("decomposed the url"), "{}", logs);
+        assert!(
+            logs.contains("domain=\"github.com\" userinfo=false queries=1"),
+            "{}",
+            logs
+        );
+        assert!(logs.contains("classified a flag flag=\"-k\" known=true takes_value=false"));
+        assert!(logs.contains("parsed the params params=3"));
+        assert!(!logs.contains("secret"));
+    }
+
+    #[test]
+    fn test_parse_curl_command() {
+        generic_parse(
+            parse_curl_command,
+            "wget 'http://a.com'",
+            Err(ParseError::NotCurl),
+        );
+
+        let request = parse_curl_command("curl 'http://a.com/b' -H 'A: 1'").unwrap();
+        assert_eq!("a.com", request.url().domain);
+        assert_eq!(Some("/b"), request.url().uri.as_deref());
+    }
+
+    #[test]
+    fn test_parse_curl_command_method() {
+        let request = parse_curl_command("curl 'http://a.com' -X 'delete'").unwrap();
+        assert_eq!("curl 'http://a.com' -X 'DELETE'", request.to_string());
+
+        assert_eq!(
+            Err(ParseError::InvalidMethod("https://a.com".into())),
+            parse_curl_command("curl -X 'https://a.com'")
+        );
+        assert_eq!(
+            Err(ParseError::MissingUrl),
+            parse_curl_command("curl -X 'POST'")
+        );
+    }
+
+    #[test]
+    fn test_parse_curl_command_bare_url() {
+        let cases = [
+            ("curl example.com/api", "http://example.com/api"),
+            (
+                "curl -k \\\n  localhost:8080/health -v",
+                "http://localhost:8080/health",
+            ),
+            (
+                "curl -X 'POST' 'api.example.com/v1' -k",
+                "http://api.example.com/v1",
+            ),
+        ];
+        for (input, url) in cases {
+            let err = parse_curl_command(input).unwrap_err();
+            assert_eq!(ParseError::BareUrl(url.into()), err, "{}", input);
+        }
+        assert_eq!(
+            "no target url found, did you mean 'http://example.com/api'?",
+            parse_curl_command("curl example.com/api")
+                .unwrap_err()
+                .to_string()
+        );
+
+        // No word looks like a url
+        for input in ["curl -k", "curl -k oops", "curl -k 'POST'"] {
+            assert_eq!(Err(ParseError::MissingUrl), parse_curl_command(input));
+        }
+    }
+
+    #[test]
+    fn test_parse_curl_command_caret_continuation() {
+        let input =
+            "curl \"https://a.com/x\" ^\n  -H \"Accept: */*\" ^\n  -H \"X-A: 1\" ^\n  --compressed";
+        let request = parse_curl_command(input).unwrap();
+        let headers: Vec<&str> = request.headers().collect();
+        assert_eq!(vec!["Accept: */*", "X-A: 1"], headers);
+        assert!(request.has_flag("--compressed"));
+    }
+
+    #[test]
+    fn test_parse_curl_command_leftover() {
+        let input = "curl 'https://a.com' -H 'A: 1' oops -d 'x'";
+        let Err(ParseError::Syntax(e)) = parse_curl_command(input) else {
+            panic!("the stray word is accepted");
+        };
+        assert_eq!("oops", e.found);
+        assert_eq!(input.find("oops"), Some(e.offset));
+        assert_eq!(error::ErrorCode::UnexpectedToken, e.code());
+
+        // The lenient parser reports the same word
+        let recovered = crate::curl::recover::parse_curl_command_lenient(input);
+        assert_eq!(vec![ParseError::Syntax(e)], recovered.errors);
+
+        let result = parse_curl_command("curl 'https://a.com' | jq '.a'");
+        assert!(matches!(result, Err(ParseError::Syntax(e)) if e.found == "|"));
+    }
+
+    #[test]
+    fn test_parse_curl_command_unquoted() {
+        let request = parse_curl_command("curl -s -X POST https://example.com").unwrap();
+        assert_eq!(
+            "curl 'https://example.com' -s -X 'POST'",
+            request.to_string()
+        );
+
+        let request =
+            parse_curl_command("curl https://example.com/a?b=1 -H Accept:json -d a=1 -o out.json")
+                .unwrap();
+        assert_eq!(
+            "curl 'https://example.com/a?b=1' -H 'Accept:json' -d 'a=1' -o 'out.json'",
+            request.to_string()
+        );
+    }
+
+    #[test]
+    fn test_check_method() {
+        let request = parse_curl_command("curl 'http://a.com' -X 'propfind'").unwrap();
+        assert_eq!(Ok(()), request.check_method(MethodPolicy::Warn));
+        assert_eq!(
+            Err(ParseError::InvalidMethod("PROPFIND".into())),
+            request.check_method(MethodPolicy::Reject)
+        );
+
+        let request = parse_curl_command("curl 'http://a.com' -X 'a b'").unwrap();
+        assert_eq!(
+            Err(ParseError::InvalidMethod("a b".into())),
+            request.check_method(MethodPolicy::Accept)
+        );
+        let request = parse_curl_command("curl 'http://a.com' -X 'get'").unwrap();
+        assert_eq!(Ok(()), request.check_method(MethodPolicy::Reject));
+    }
+
+    #[test]
+    fn test_parsed_request_into_owned() {
+        let input = String::from("curl 'http://a.com/b?c=1' -H 'A: 1' -d 'x' --max-time '5'");
+        let request = parse_curl_command(&input).unwrap();
+        let Curl::Header(header) = &request.curls()[1] else {
+            panic!("The params:\r\n{:#?}", request.curls());
+        };
+        assert!(matches!(request.url().domain, Cow::Borrowed(_)));
+        assert!(matches!(header.data, Some(Cow::Borrowed("A: 1"))));
+
+        let owned: ParsedRequest<'static> = request.clone().into_owned();
+        assert_eq!(request, owned);
+        drop(input);
+        assert!(matches!(owned.url().domain, Cow::Owned(_)));
+        assert_eq!(Some("1"), owned.header("A"));
+    }
+
+    #[test]
+    fn test_parsed_request_edits() {
+        let mut request =
+            parse_curl_command("curl 'http://a.com/b' -H 'A: 1' -H 'a: 2' -d 'x' -k").unwrap();
+
+        request.set_header("A", "3");
+        request.set_header("B", "4");
+        request.set_method(Some(Method::PUT));
+        request.url_mut().domain = "b.com".into();
+        let expect = "curl 'http://b.com/b' -X 'PUT' -H 'A: 3' -H 'B: 4' -d 'x' -k";
+        assert_eq!(expect, request.to_string());
+
+        assert!(request.remove_header("b"));
+        assert!(!request.remove_header("C"));
+        request.set_method(None);
+        assert_eq!(
+            "curl 'http://b.com/b' -H 'A: 3' -d 'x' -k",
+            request.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parsed_request_accessors() {
+        let request = parse_curl_command(
+            "curl 'http://a.com' -X 'PUT' -H 'Content-Type: text/plain' -d 'a' --data-raw 'b' -F 'c=d' -k",
+        )
+        .unwrap();
+
+        assert_eq!(Some(Method::PUT), request.method());
+        assert_eq!(Some("text/plain"), request.header("content-type"));
+        assert_eq!(None, request.header("Accept"));
+        assert_eq!(vec!["a", "b"], request.data());
+        assert!(request.has_flag("--insecure"));
+        assert!(!request.has_flag("--location"));
+    }
+
+    #[test]
+    fn test_parsed_request_last_wins() {
+        let request =
+            parse_curl_command("curl 'http://a.com' -X 'POST' -m '5' -X 'PUT' --max-time '10'")
+                .unwrap();
+
+        // Every token is kept, but the last occurrence is the one used
+        let methods = request
+            .curls
+            .iter()
+            .filter(|c| matches!(c, Curl::Method(_)));
+        assert_eq!(2, methods.count());
+        assert_eq!(Some(Method::PUT), request.method());
+        assert_eq!(
+            Some("10"),
+            request.flag("--max-time").and_then(|f| f.data.as_deref())
+        );
+        assert_eq!(2, request.flags().count());
+    }
+
+    #[test]
+    fn test_effective_method_and_url() {
+        let cases = vec![
+            ("curl 'http://a.com'", Method::GET),
+            ("curl 'http://a.com' -X 'patch' -d 'a'", Method::PATCH),
+            ("curl 'http://a.com' -X 'a b' -d 'a'", Method::POST),
+            ("curl 'http://a.com' -d 'a'", Method::POST),
+            ("curl 'http://a.com' -F 'a=@b'", Method::POST),
+            ("curl 'http://a.com' -G -d 'a'", Method::GET),
+            ("curl 'http://a.com' -I", Method::HEAD),
+            ("curl 'http://a.com' -T 'a.txt'", Method::PUT),
+        ];
+        for (input, expect) in cases {
+            let request = parse_curl_command(input).unwrap();
+            assert_eq!(expect, request.effective_method(), "The input: {}", input);
+        }
+
+        let request = parse_curl_command("curl 'http://a.com/b?c=1' -G -d 'd=2' -d 'e=3'").unwrap();
+        assert_eq!("http://a.com/b?c=1&d=2&e=3", request.effective_url());
+        let request = parse_curl_command("curl 'http://a.com/b' -d 'd=2'").unwrap();
+        assert_eq!("http://a.com/b", request.effective_url());
+    }
+
+    #[test]
+    fn test_payloads() {
+        let input = "curl 'http://a.com' -d 'a=1' --data-urlencode 'b=x y' --data-urlencode '=é' --data-urlencode 'a&b' --data-urlencode '@file'";
+        let request = parse_curl_command(input).unwrap();
+        assert_eq!(
+            vec!["a=1", "b=x%20y", "%C3%A9", "a%26b", "@file"],
+            request.payloads()
+        );
+    }
+
+    #[test]
+    fn test_parsed_request_from_str() {
+        let input = "curl 'http://a.com/b' -H 'A: 1'";
+        let expect = parse_curl_command(input);
+
+        generic_parse(str::parse::<ParsedRequest>, input, expect);
+        generic_parse(
+            str::parse::<ParsedRequest>,
+            "curl -H 'A: 1'",
+            Err(ParseError::MissingUrl),
+        );
+    }
+
+    #[test]
+    fn test_parsed_request_display() {
+        let input =
+            "curl 'https://github.com/a' -X 'POST' -H 'A: 1' -d \"it's\" --insecure -m '10'";
+        let request = parse_curl_command(input).unwrap();
+
+        assert_eq!(input, request.to_string());
+        assert_eq!(Ok(request.clone()), request.to_string().parse());
+    }
+}
@@ -0,0 +1,146 @@
+use nom::branch::alt;
+use serde::Serialize;
+
+use super::{
+    curl_parsers::{
+        curl_cmd_parse, data_parse, flag_parse, header_parse, is_curl, method_parse,
+        remove_curl_cmd_header, url_param_parse,
+    },
+    error::{line_col, ParseError, TokenKind},
+    Curl,
+};
+
+/// The byte range of a param in the command text, without its surrounding spaces and `\`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The 1-based line and column of the span start in the input.
+    pub fn line_col(&self, input: &str) -> (usize, usize) {
+        line_col(input, self.start)
+    }
+}
+
+/// The span of every param `curl_cmd_parse` returns, the urls included, in the same order.
+pub fn curl_cmd_spans(input: &str) -> Result<Vec<Span>, ParseError> {
+    // Fail the same way as the parser does
+    let error = |e| ParseError::from_nom(input, TokenKind::Value, e);
+    curl_cmd_parse(input).map_err(error)?;
+
+    let rest = remove_curl_cmd_header(input.trim_start());
+    Ok(param_spans(input, rest).0)
+}
+
+/// The span of the token where the parser stops, e.g. the url a command lacks or a param it
+/// can't read, empty at the end of the input when a url is expected there. `None` if the whole
+/// command is parsed.
+pub fn error_span(input: &str) -> Option<Span> {
+    let trimmed = input.trim_start();
+    if !is_curl(trimmed) {
+        return Some(token_span(input, input.len() - trimmed.len()));
+    }
+
+    let (has_url, rest) = param_spans(input, remove_curl_cmd_header(trimmed)).1;
+    let unparsed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '\\');
+    if !unparsed.is_empty() {
+        return Some(token_span(input, input.len() - unparsed.len()));
+    }
+    // Every param is parsed, but none is the url
+    (!has_url).then_some(Span {
+        start: input.len(),
+        end: input.len(),
+    })
+}
+
+/// The spans of the params and the urls, whether there is a url, and the text left after them.
+fn param_spans<'a>(input: &str, mut rest: &'a str) -> (Vec<Span>, (bool, &'a str)) {
+    let mut spans = Vec::new();
+    let mut has_url = false;
+    let mut param = alt((
+        url_param_parse,
+        method_parse,
+        header_parse,
+        data_parse,
+        flag_parse,
+    ));
+    while let Ok((after, curl)) = param(rest) {
+        if after.len() == rest.len() {
+            break;
+        }
+        has_url |= matches!(curl, Curl::URL(_));
+        spans.push(span_between(input, rest, after));
+        rest = after;
+    }
+    (spans, (has_url, rest))
+}
+
+/// The span of the whitespace-delimited token starting at the offset.
+fn token_span(input: &str, start: usize) -> Span {
+    let token = &input[start..];
+    let len = token.find(char::is_whitespace).unwrap_or(token.len());
+    Span {
+        start,
+        end: start + len,
+    }
+}
+
+/// The span of the text consumed from `before` to `after`, both being suffixes of the input.
+fn span_between(input: &str, before: &str, after: &str) -> Span {
+    let offset = input.len() - before.len();
+    let consumed = &before[..before.len() - after.len()];
+    let trimmed = consumed.trim_start_matches(|c: char| c.is_whitespace() || c == '\\');
+
+    Span {
+        start: offset + consumed.len() - trimmed.len(),
+        end: offset + consumed.trim_end().len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curl_cmd_spans() {
+        let input = "curl 'https://github.com' \\\n  -X 'POST' -k\n  -d \"a=1\"  ";
+        let spans = curl_cmd_spans(input).unwrap();
+        let texts: Vec<&str> = spans.iter().map(|s| &input[s.start..s.end]).collect();
+
+        assert_eq!(
+            vec!["'https://github.com'", "-X 'POST'", "-k", "-d \"a=1\""],
+            texts
+        );
+        assert_eq!((2, 3), spans[1].line_col(input));
+        assert_eq!((3, 3), spans[3].line_col(input));
+
+        assert_eq!(Err(ParseError::MissingUrl), curl_cmd_spans("curl -k"));
+
+        let input = "curl -k -X 'POST' 'https://github.com' -H 'A: 1'";
+        let spans = curl_cmd_spans(input).unwrap();
+        let texts: Vec<&str> = spans.iter().map(|s| &input[s.start..s.end]).collect();
+        assert_eq!(
+            vec!["-k", "-X 'POST'", "'https://github.com'", "-H 'A: 1'"],
+            texts
+        );
+    }
+
+    #[test]
+    fn test_error_span() {
+        let text = |input: &'static str| error_span(input).map(|s| &input[s.start..s.end]);
+
+        assert_eq!(None, text("curl 'https://github.com' -k"));
+        assert_eq!(Some("wget"), text("  wget 'https://github.com'"));
+        assert_eq!(None, text("curl -k 'https://github.com'"));
+        assert_eq!(Some(""), text("curl "));
+        assert_eq!(Some(""), text("curl -k"));
+
+        let input = "curl 'https://github.com' \\\n  -H 'A: 1' \\\n  oops 'x'";
+        let span = error_span(input).unwrap();
+        assert_eq!("oops", &input[span.start..span.end]);
+        assert_eq!((3, 3), span.line_col(input));
+    }
+}
@@ -0,0 +1,74 @@
+use super::{
+    method::Method,
+    options::{self, Category},
+    request::ParsedRequest,
+};
+
+/// Counts and settings overview of a `ParsedRequest`, handy to index large amounts of commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestSummary {
+    pub method: Method,
+    pub url: String,
+    pub header_count: usize,
+    pub data_count: usize,
+    pub flag_count: usize,
+    /// The total length in bytes of every data payload.
+    pub body_size: usize,
+    pub has_auth: bool,
+    pub has_tls: bool,
+    pub has_proxy: bool,
+}
+
+impl RequestSummary {
+    pub fn new(request: &ParsedRequest) -> Self {
+        let data = request.data();
+        let has_category = |category: Category| {
+            request
+                .flags()
+                .any(|f| options::lookup(&f.identifier).is_some_and(|o| o.category == category))
+        };
+
+        Self {
+            method: request.effective_method(),
+            url: request.effective_url(),
+            header_count: request.headers().count(),
+            data_count: data.len(),
+            flag_count: request.flags().count(),
+            body_size: data.iter().map(|d| d.len()).sum(),
+            has_auth: has_category(Category::Auth)
+                || request.header("Authorization").is_some()
+                || request.url().userinfo.is_some(),
+            has_tls: has_category(Category::Tls),
+            has_proxy: has_category(Category::Proxy),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_request_summary() {
+        let input = "curl 'https://github.com/a' -H 'A: 1' -H 'B: 2' -d 'abc' --data-raw 'de' -k -x 'http://proxy:8080' --compressed";
+        let expect = RequestSummary {
+            method: Method::POST,
+            url: "https://github.com/a".into(),
+            header_count: 2,
+            data_count: 2,
+            flag_count: 4,
+            body_size: 5,
+            has_auth: false,
+            has_tls: true,
+            has_proxy: true,
+        };
+        assert_eq!(expect, parse_curl_command(input).unwrap().summary());
+
+        let input = "curl 'https://github.com' -H 'Authorization: Bearer abc'";
+        let summary = parse_curl_command(input).unwrap().summary();
+        assert!(summary.has_auth);
+        assert!(!summary.has_tls);
+        assert_eq!(0, summary.body_size);
+    }
+}
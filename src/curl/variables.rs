@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use super::error::ParseError;
+
+/// Replace the `$VAR`, `${VAR}` and `{{VAR}}` placeholders of the input by their values.
+///
+/// The unknown placeholders are kept as they are and returned, in the order they appear, so
+/// that e.g. a `$5` price in a body is left alone.
+pub fn substitute(input: &str, vars: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut output = String::with_capacity(input.len());
+    let mut unresolved: Vec<String> = Vec::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find(['$', '{']) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some((name, len)) = placeholder(rest) else {
+            let c = rest.chars().next().unwrap();
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+        match vars.get(name) {
+            Some(value) => output.push_str(value),
+            None => {
+                output.push_str(&rest[..len]);
+                if !unresolved.iter().any(|u| u == name) {
+                    unresolved.push(name.into());
+                }
+            }
+        }
+        rest = &rest[len..];
+    }
+    output.push_str(rest);
+    (output, unresolved)
+}
+
+/// The variable name and the length of the placeholder starting the input.
+fn placeholder(input: &str) -> Option<(&str, usize)> {
+    let (inner, open, close) = if let Some(inner) = input.strip_prefix("{{") {
+        (inner, 2, "}}")
+    } else if let Some(inner) = input.strip_prefix("${") {
+        (inner, 2, "}")
+    } else {
+        let inner = input.strip_prefix('$')?;
+        let len = name_len(inner);
+        return (len > 0).then(|| (&inner[..len], len + 1));
+    };
+
+    let end = inner.find(close)?;
+    let name = inner[..end].trim();
+    (name_len(name) == name.len() && !name.is_empty()).then(|| (name, open + end + close.len()))
+}
+
+/// The length of the `[A-Za-z_][A-Za-z0-9_]*` name starting the input.
+fn name_len(input: &str) -> usize {
+    match input.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => input
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(input.len()),
+        _ => 0,
+    }
+}
+
+/// Parse a `KEY=VALUE` definition, the value may be wrapped in single or double quotes.
+pub fn parse_variable(definition: &str) -> Result<(String, String), ParseError> {
+    let definition = definition.trim();
+    let definition = definition.strip_prefix("export ").unwrap_or(definition);
+    match definition.split_once('=') {
+        Some((name, value)) if name_len(name.trim()) == name.trim().len() && !name.is_empty() => {
+            let value = value.trim();
+            let unquoted = ['"', '\'']
+                .iter()
+                .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)));
+            Ok((name.trim().into(), unquoted.unwrap_or(value).into()))
+        }
+        _ => Err(ParseError::InvalidVariable(definition.into())),
+    }
+}
+
+/// Parse the variables of a `.env` file, blank lines and `#` comments are skipped.
+pub fn parse_env_file(content: &str) -> Result<Vec<(String, String)>, ParseError> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(parse_variable)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute() {
+        let vars: HashMap<String, String> = [("HOST", "github.com"), ("id", "42")]
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+
+        let input =
+            "curl 'https://$HOST/a/${id}?b={{ id }}' -d 'price=$5&c=$MISSING&d={{MISSING}}{x}'";
+        let expect =
+            "curl 'https://github.com/a/42?b=42' -d 'price=$5&c=$MISSING&d={{MISSING}}{x}'";
+        let (output, unresolved) = substitute(input, &vars);
+        assert_eq!(expect, output);
+        assert_eq!(vec!["MISSING".to_string()], unresolved);
+    }
+
+    #[test]
+    fn test_parse_env_file() {
+        let content = "# comment\nHOST=github.com\n\nexport TOKEN=\"a b\"\nEMPTY=\n";
+        let expect = vec![
+            ("HOST".to_string(), "github.com".to_string()),
+            ("TOKEN".to_string(), "a b".to_string()),
+            ("EMPTY".to_string(), "".to_string()),
+        ];
+        assert_eq!(Ok(expect), parse_env_file(content));
+
+        assert_eq!(
+            Err(ParseError::InvalidVariable("1A=b".into())),
+            parse_variable("1A=b")
+        );
+        assert!(parse_variable("A").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! Curl commands of outgoing requests, e.g. to log them in a reproducible form.
+
+use http::{HeaderMap, Method as HttpMethod};
+
+use crate::curl::{
+    error::ParseError, method::Method, request::ParsedRequest, url_parser::CurlURL, Curl, CurlStru,
+};
+
+/// A request which can be written as a curl command.
+pub trait Curlify {
+    /// The request as parsed from the equivalent curl command, an invalid url is an error.
+    fn curlify(&self) -> Result<ParsedRequest<'static>, ParseError>;
+
+    /// The single line curl command of the request.
+    fn to_curl(&self) -> Result<String, ParseError> {
+        self.curlify().map(|request| request.to_string())
+    }
+}
+
+/// A body of an `http::Request` which can be sent by curl.
+pub trait CurlBody {
+    /// The bytes of the body, empty for no body.
+    fn curl_body(&self) -> &[u8];
+}
+
+impl CurlBody for () {
+    fn curl_body(&self) -> &[u8] {
+        &[]
+    }
+}
+
+impl CurlBody for String {
+    fn curl_body(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl CurlBody for &str {
+    fn curl_body(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl CurlBody for Vec<u8> {
+    fn curl_body(&self) -> &[u8] {
+        self
+    }
+}
+
+impl CurlBody for &[u8] {
+    fn curl_body(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<B: CurlBody> Curlify for http::Request<B> {
+    fn curlify(&self) -> Result<ParsedRequest<'static>, ParseError> {
+        let uri = self.uri().to_string();
+        let body = self.body().curl_body();
+        curlify(self.method(), &uri, self.headers(), body)
+    }
+}
+
+/// A streamed body isn't part of the command.
+#[cfg(feature = "reqwest")]
+impl Curlify for reqwest::Request {
+    fn curlify(&self) -> Result<ParsedRequest<'static>, ParseError> {
+        let body = self.body().and_then(reqwest::Body::as_bytes);
+        curlify(
+            self.method(),
+            self.url().as_str(),
+            self.headers(),
+            body.unwrap_or_default(),
+        )
+    }
+}
+
+/// The params of the request: the url, the headers, the body as `--data-raw` and the method
+/// unless the body implies it, a `HEAD` being `-I`.
+fn curlify(
+    method: &HttpMethod,
+    uri: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<ParsedRequest<'static>, ParseError> {
+    let url = CurlURL::try_from(uri)?.into_owned();
+    let mut curls = vec![Curl::URL(url)];
+    for (name, value) in headers {
+        // curl sets it from the body
+        if name == http::header::CONTENT_LENGTH {
+            continue;
+        }
+        let header = match value.is_empty() {
+            // `-H 'Name:'` would remove the header
+            true => format!("{};", name),
+            false => format!("{}: {}", name, String::from_utf8_lossy(value.as_bytes())),
+        };
+        curls.push(Curl::Header(CurlStru::new_with_data("-H", header)));
+    }
+    if !body.is_empty() {
+        let body = String::from_utf8_lossy(body).into_owned();
+        curls.push(Curl::Flag(CurlStru::new_with_data("--data-raw", body)));
+    }
+    if method == HttpMethod::HEAD {
+        curls.push(Curl::Flag(CurlStru::new("-I")));
+    }
+
+    let mut request = ParsedRequest::new(curls)?;
+    let method: Method = method.as_str().parse()?;
+    if method != request.implied_method() {
+        request.set_method(Some(method));
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curlify_http_request() {
+        let request = http::Request::builder()
+            .method("PUT")
+            .uri("https://github.com/a?b=1")
+            .header("X-A", "1")
+            .header("Content-Length", "6")
+            .body("name=x")
+            .unwrap();
+        let expect = "curl 'https://github.com/a?b=1' -X 'PUT' -H 'x-a: 1' --data-raw 'name=x'";
+        assert_eq!(Ok(expect.to_string()), request.to_curl());
+
+        let request = http::Request::get("https://github.com").header("X-Empty", "");
+        let expect = "curl 'https://github.com/' -H 'x-empty;'";
+        assert_eq!(Ok(expect.to_string()), request.body(()).unwrap().to_curl());
+
+        let request = http::Request::post("https://github.com").body(r#"{"a":1}"#.to_string());
+        let expect = r#"curl 'https://github.com/' --data-raw '{"a":1}'"#;
+        assert_eq!(Ok(expect.to_string()), request.unwrap().to_curl());
+
+        let request = http::Request::head("https://github.com").body(()).unwrap();
+        assert_eq!(
+            Ok("curl 'https://github.com/' -I".into()),
+            request.to_curl()
+        );
+        let request = http::Request::get("https://github.com").body(()).unwrap();
+        let parsed = request.curlify().unwrap();
+        assert_eq!(Method::GET, parsed.effective_method());
+        assert_eq!("curl 'https://github.com/'", parsed.to_string());
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[test]
+    fn test_curlify_reqwest_request() {
+        let request = reqwest::Client::new()
+            .delete("https://github.com/a")
+            .bearer_auth("abc")
+            .build()
+            .unwrap();
+        let expect = "curl 'https://github.com/a' -X 'DELETE' -H 'authorization: Bearer abc'";
+        assert_eq!(Ok(expect.to_string()), request.to_curl());
+    }
+}
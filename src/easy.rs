@@ -0,0 +1,343 @@
+//! Configuration of a curl-rust `Easy` handle from a parsed request.
+
+use std::{fmt, io, time::Duration};
+
+use curl::easy::{Easy, Form, List};
+
+use crate::{
+    codegen::{Body, FormField, RequestParts},
+    curl::{method::Method, request::ParsedRequest},
+};
+
+/// The error returned when the setters can't be applied to a handle.
+#[derive(Debug)]
+pub enum EasyError {
+    /// A `-d @file` can't be read.
+    Io(io::Error),
+    Curl(curl::Error),
+    Form(curl::FormError),
+}
+
+impl fmt::Display for EasyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EasyError::Io(e) => write!(f, "can't read the request body: {}", e),
+            EasyError::Curl(e) => write!(f, "can't set the handle option: {}", e),
+            EasyError::Form(e) => write!(f, "can't build the form: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for EasyError {}
+
+impl From<io::Error> for EasyError {
+    fn from(err: io::Error) -> Self {
+        EasyError::Io(err)
+    }
+}
+
+impl From<curl::Error> for EasyError {
+    fn from(err: curl::Error) -> Self {
+        EasyError::Curl(err)
+    }
+}
+
+impl From<curl::FormError> for EasyError {
+    fn from(err: curl::FormError) -> Self {
+        EasyError::Form(err)
+    }
+}
+
+/// A setter call of an `Easy` handle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Setter {
+    Url(String),
+    CustomRequest(String),
+    Nobody(bool),
+    Post(bool),
+    PostFields(String),
+    /// `-d @path`, the file content is sent.
+    PostFile(String),
+    HttpPost(Vec<FormField>),
+    HttpHeaders(Vec<String>),
+    Username(String),
+    Password(String),
+    SslVerifyPeer(bool),
+    SslVerifyHost(bool),
+    FollowLocation(bool),
+    MaxRedirections(u32),
+    Timeout(Duration),
+    ConnectTimeout(Duration),
+    Proxy(String),
+}
+
+impl Setter {
+    pub fn apply(&self, easy: &mut Easy) -> Result<(), EasyError> {
+        match self {
+            Setter::Url(url) => easy.url(url)?,
+            Setter::CustomRequest(method) => easy.custom_request(method)?,
+            Setter::Nobody(enable) => easy.nobody(*enable)?,
+            Setter::Post(enable) => easy.post(*enable)?,
+            Setter::PostFields(data) => easy.post_fields_copy(data.as_bytes())?,
+            Setter::PostFile(path) => easy.post_fields_copy(&std::fs::read(path)?)?,
+            Setter::HttpPost(fields) => {
+                let mut form = Form::new();
+                for field in fields {
+                    match field {
+                        FormField::Text(name, value) => {
+                            form.part(name).contents(value.as_bytes()).add()?
+                        }
+                        FormField::File(name, path) => form.part(name).file(path).add()?,
+                        FormField::Content(name, path) => {
+                            form.part(name).file_content(path).add()?
+                        }
+                    }
+                }
+                easy.httppost(form)?
+            }
+            Setter::HttpHeaders(headers) => {
+                let mut list = List::new();
+                for header in headers {
+                    list.append(header)?;
+                }
+                easy.http_headers(list)?
+            }
+            Setter::Username(user) => easy.username(user)?,
+            Setter::Password(pass) => easy.password(pass)?,
+            Setter::SslVerifyPeer(verify) => easy.ssl_verify_peer(*verify)?,
+            Setter::SslVerifyHost(verify) => easy.ssl_verify_host(*verify)?,
+            Setter::FollowLocation(enable) => easy.follow_location(*enable)?,
+            Setter::MaxRedirections(max) => easy.max_redirections(*max)?,
+            Setter::Timeout(timeout) => easy.timeout(*timeout)?,
+            Setter::ConnectTimeout(timeout) => easy.connect_timeout(*timeout)?,
+            Setter::Proxy(url) => easy.proxy(url)?,
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Setter {
+    /// The Rust statements of the call on an `easy` handle, e.g. `easy.url("https://a.com")?;`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Setter::Url(url) => write!(f, "easy.url({:?})?;", url),
+            Setter::CustomRequest(method) => write!(f, "easy.custom_request({:?})?;", method),
+            Setter::Nobody(enable) => write!(f, "easy.nobody({})?;", enable),
+            Setter::Post(enable) => write!(f, "easy.post({})?;", enable),
+            Setter::PostFields(data) => write!(f, "easy.post_fields_copy({:?}.as_bytes())?;", data),
+            Setter::PostFile(path) => {
+                write!(f, "easy.post_fields_copy(&std::fs::read({:?})?)?;", path)
+            }
+            Setter::HttpPost(fields) => {
+                writeln!(f, "let mut form = Form::new();")?;
+                for field in fields {
+                    match field {
+                        FormField::Text(name, value) => writeln!(
+                            f,
+                            "form.part({:?}).contents({:?}.as_bytes()).add()?;",
+                            name, value
+                        )?,
+                        FormField::File(name, path) => {
+                            writeln!(f, "form.part({:?}).file({:?}).add()?;", name, path)?
+                        }
+                        FormField::Content(name, path) => {
+                            writeln!(f, "form.part({:?}).file_content({:?}).add()?;", name, path)?
+                        }
+                    }
+                }
+                write!(f, "easy.httppost(form)?;")
+            }
+            Setter::HttpHeaders(headers) => {
+                writeln!(f, "let mut headers = List::new();")?;
+                for header in headers {
+                    writeln!(f, "headers.append({:?})?;", header)?;
+                }
+                write!(f, "easy.http_headers(headers)?;")
+            }
+            Setter::Username(user) => write!(f, "easy.username({:?})?;", user),
+            Setter::Password(pass) => write!(f, "easy.password({:?})?;", pass),
+            Setter::SslVerifyPeer(verify) => write!(f, "easy.ssl_verify_peer({})?;", verify),
+            Setter::SslVerifyHost(verify) => write!(f, "easy.ssl_verify_host({})?;", verify),
+            Setter::FollowLocation(enable) => write!(f, "easy.follow_location({})?;", enable),
+            Setter::MaxRedirections(max) => write!(f, "easy.max_redirections({})?;", max),
+            Setter::Timeout(timeout) => write!(
+                f,
+                "easy.timeout(Duration::from_millis({}))?;",
+                timeout.as_millis()
+            ),
+            Setter::ConnectTimeout(timeout) => write!(
+                f,
+                "easy.connect_timeout(Duration::from_millis({}))?;",
+                timeout.as_millis()
+            ),
+            Setter::Proxy(url) => write!(f, "easy.proxy({:?})?;", url),
+        }
+    }
+}
+
+/// The setter calls configuring a handle for the request: the url, method, headers, `-u` auth,
+/// body, `-k`, `-L`, `--max-redirs`, `--max-time`, `--connect-timeout` and `-x`.
+pub fn setters(request: &ParsedRequest) -> Vec<Setter> {
+    let parts = RequestParts::new(request);
+    let mut setters = vec![Setter::Url(parts.url.clone())];
+
+    let is_form = matches!(parts.body, Some(Body::Form(_)));
+    match (&parts.method, &parts.body) {
+        (Method::HEAD, _) => setters.push(Setter::Nobody(true)),
+        (Method::GET, None) | (Method::POST, Some(_)) => {}
+        // Without a body, `post(true)` would read it from stdin
+        (method, _) => setters.push(Setter::CustomRequest(method.to_string())),
+    }
+
+    let mut headers: Vec<String> = parts
+        .headers
+        .iter()
+        // libcurl sets the multipart Content-Type with its boundary
+        .filter(|(name, _)| !(is_form && name.eq_ignore_ascii_case("Content-Type")))
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect();
+    if let Some(token) = &parts.bearer_token {
+        headers.push(format!("Authorization: Bearer {}", token));
+    }
+    if !headers.is_empty() {
+        setters.push(Setter::HttpHeaders(headers));
+    }
+    if let Some((user, pass)) = &parts.basic_auth {
+        setters.push(Setter::Username(user.clone()));
+        setters.extend(pass.clone().map(Setter::Password));
+    }
+
+    match &parts.body {
+        Some(Body::Raw(raw) | Body::Json(raw)) => {
+            setters.push(Setter::Post(true));
+            setters.push(Setter::PostFields(raw.clone()));
+        }
+        Some(Body::File(path)) => {
+            setters.push(Setter::Post(true));
+            setters.push(Setter::PostFile(path.clone()));
+        }
+        Some(Body::Form(fields)) => setters.push(Setter::HttpPost(fields.clone())),
+        None => {}
+    }
+
+    if parts.insecure {
+        setters.push(Setter::SslVerifyPeer(false));
+        setters.push(Setter::SslVerifyHost(false));
+    }
+    if parts.follow_redirects {
+        setters.push(Setter::FollowLocation(true));
+        setters.extend(parts.max_redirects.map(Setter::MaxRedirections));
+    }
+    if let Some(timeout) = parts.timeout {
+        setters.push(Setter::Timeout(Duration::from_secs_f64(timeout)));
+    }
+    if let Some(timeout) = parts.connect_timeout {
+        setters.push(Setter::ConnectTimeout(Duration::from_secs_f64(timeout)));
+    }
+    setters.extend(parts.proxy.map(Setter::Proxy));
+    setters
+}
+
+/// Configure the handle for the request, see `setters`.
+pub fn apply(request: &ParsedRequest, easy: &mut Easy) -> Result<(), EasyError> {
+    setters(request).iter().try_for_each(|s| s.apply(easy))
+}
+
+/// A Rust function building the `Easy` handle of the request, see `setters`.
+pub fn generate(request: &ParsedRequest) -> String {
+    let setters = setters(request);
+
+    let mut imports = vec!["Easy"];
+    if setters.iter().any(|s| matches!(s, Setter::HttpPost(_))) {
+        imports.push("Form");
+    }
+    if setters.iter().any(|s| matches!(s, Setter::HttpHeaders(_))) {
+        imports.push("List");
+    }
+    let mut code = format!("use curl::easy::{{{}}};\n", imports.join(", "));
+    if setters
+        .iter()
+        .any(|s| matches!(s, Setter::Timeout(_) | Setter::ConnectTimeout(_)))
+    {
+        code.push_str("use std::time::Duration;\n");
+    }
+
+    code.push_str("\nfn request() -> Result<Easy, Box<dyn std::error::Error>> {\n");
+    code.push_str("    let mut easy = Easy::new();\n");
+    for setter in &setters {
+        for line in setter.to_string().lines() {
+            code.push_str(&format!("    {}\n", line));
+        }
+    }
+    code.push_str("    Ok(easy)\n}\n");
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    #[test]
+    fn test_setters() {
+        let input = "curl 'https://github.com/a' -X 'PUT' -H 'X-A: 1' -d 'a=1' -u 'user:pwd' -k -L --max-redirs '3' -m '2.5' -x 'http://127.0.0.1:8080'";
+        let request = parse_curl_command(input).unwrap();
+        let expect = vec![
+            Setter::Url("https://github.com/a".into()),
+            Setter::CustomRequest("PUT".into()),
+            Setter::HttpHeaders(vec![
+                "X-A: 1".into(),
+                "Content-Type: application/x-www-form-urlencoded".into(),
+            ]),
+            Setter::Username("user".into()),
+            Setter::Password("pwd".into()),
+            Setter::Post(true),
+            Setter::PostFields("a=1".into()),
+            Setter::SslVerifyPeer(false),
+            Setter::SslVerifyHost(false),
+            Setter::FollowLocation(true),
+            Setter::MaxRedirections(3),
+            Setter::Timeout(Duration::from_millis(2500)),
+            Setter::Proxy("http://127.0.0.1:8080".into()),
+        ];
+        assert_eq!(expect, setters(&request));
+
+        let mut easy = Easy::new();
+        assert!(apply(&request, &mut easy).is_ok());
+
+        let request = parse_curl_command("curl 'https://github.com' -I").unwrap();
+        let expect = vec![
+            Setter::Url("https://github.com".into()),
+            Setter::Nobody(true),
+        ];
+        assert_eq!(expect, setters(&request));
+
+        let request = parse_curl_command("curl 'https://github.com' -d '@/nonexistent'").unwrap();
+        let result = apply(&request, &mut Easy::new());
+        assert!(matches!(result, Err(EasyError::Io(_))), "{:?}", result);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = "curl 'https://github.com' -H 'X-A: \"1\"' -F 'a=1' -F 'f=@a.txt' --connect-timeout '1'";
+        let request = parse_curl_command(input).unwrap();
+        let expect = r#"use curl::easy::{Easy, Form, List};
+use std::time::Duration;
+
+fn request() -> Result<Easy, Box<dyn std::error::Error>> {
+    let mut easy = Easy::new();
+    easy.url("https://github.com")?;
+    let mut headers = List::new();
+    headers.append("X-A: \"1\"")?;
+    easy.http_headers(headers)?;
+    let mut form = Form::new();
+    form.part("a").contents("1".as_bytes()).add()?;
+    form.part("f").file("a.txt").add()?;
+    easy.httppost(form)?;
+    easy.connect_timeout(Duration::from_millis(1000))?;
+    Ok(easy)
+}
+"#;
+        assert_eq!(expect, generate(&request));
+    }
+}
@@ -0,0 +1,178 @@
+use std::{fmt, io, time::Duration, time::Instant};
+
+use reqwest::blocking::multipart;
+
+use crate::{
+    client,
+    codegen::{Body, FormField, RequestParts},
+    curl::request::ParsedRequest,
+};
+
+/// The error returned when a request can't be sent.
+#[derive(Debug)]
+pub enum ExecError {
+    /// A `-d @file` or `-F name=@file` can't be read.
+    Io(io::Error),
+    Http(reqwest::Error),
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecError::Io(e) => write!(f, "can't read the request body: {}", e),
+            ExecError::Http(e) => write!(f, "request failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExecError {}
+
+impl From<io::Error> for ExecError {
+    fn from(err: io::Error) -> Self {
+        ExecError::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for ExecError {
+    fn from(err: reqwest::Error) -> Self {
+        ExecError::Http(err)
+    }
+}
+
+/// The response of a request sent by `send`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    /// The reason phrase of the status, e.g. `Not Found`.
+    pub reason: String,
+    /// The HTTP version, e.g. `HTTP/1.1`.
+    pub version: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// The time from sending the request to reading the whole body.
+    pub elapsed: Duration,
+}
+
+/// Send the request with a blocking reqwest client, with the settings of the async one, see
+/// `ParsedRequest::client_builder`, and the same `RequestParts` the code generators use.
+pub fn send(request: &ParsedRequest) -> Result<Response, ExecError> {
+    let parts = RequestParts::new(request);
+    let client = request.blocking_client_builder()?.build()?;
+
+    let mut builder = client.request(client::method(&parts), &parts.url);
+    for (name, value) in client::headers(&parts) {
+        builder = builder.header(name, value);
+    }
+    if let Some(timeout) = parts.timeout {
+        builder = builder.timeout(Duration::from_secs_f64(timeout));
+    }
+    builder = match &parts.body {
+        Some(Body::Raw(raw) | Body::Json(raw)) => builder.body(raw.clone()),
+        Some(Body::File(path)) => builder.body(std::fs::read(path)?),
+        Some(Body::Form(fields)) => {
+            let mut form = multipart::Form::new();
+            for field in fields {
+                form = match field {
+                    FormField::Text(name, value) => form.text(name.clone(), value.clone()),
+                    FormField::File(name, path) => form.file(name.clone(), path)?,
+                    FormField::Content(name, path) => {
+                        form.part(name.clone(), multipart::Part::bytes(std::fs::read(path)?))
+                    }
+                };
+            }
+            builder.multipart(form)
+        }
+        None => builder,
+    };
+
+    let start = Instant::now();
+    let response = builder.send()?;
+    let status = response.status();
+    let version = format!("{:?}", response.version());
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            (name.to_string(), value)
+        })
+        .collect();
+    let body = response.bytes()?.to_vec();
+
+    Ok(Response {
+        status: status.as_u16(),
+        reason: status.canonical_reason().unwrap_or_default().into(),
+        version,
+        headers,
+        body,
+        elapsed: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    /// Serve a single request on a local port, the raw request is sent back as the body.
+    fn echo_server() -> (String, std::thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            // Read the head, then the body of its Content-Length
+            loop {
+                let len = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..len]);
+                let text = String::from_utf8_lossy(&request).to_lowercase();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let content_length = head
+                        .split("content-length: ")
+                        .nth(1)
+                        .and_then(|l| l.lines().next()?.parse().ok())
+                        .unwrap_or(0);
+                    if len == 0 || body.len() >= content_length {
+                        break;
+                    }
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 201 Created\r\nX-Test: 1\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                request.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            stream.write_all(&request).unwrap();
+        });
+        (address, handle)
+    }
+
+    #[test]
+    fn test_send() {
+        let (address, handle) = echo_server();
+        let input = format!(
+            "curl 'http://{}/a?b=1' -X 'PUT' -H 'X-A: 1' -d 'name=nomcurl' -u 'user:pwd'",
+            address
+        );
+        let response = send(&parse_curl_command(&input).unwrap()).unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(
+            (201, "Created"),
+            (response.status, response.reason.as_str())
+        );
+        assert_eq!("HTTP/1.1", response.version);
+        assert!(response.headers.contains(&("x-test".into(), "1".into())));
+
+        let echo = String::from_utf8(response.body).unwrap().to_lowercase();
+        assert!(echo.starts_with("put /a?b=1 http/1.1\r\n"), "{}", echo);
+        assert!(echo.contains("\r\nx-a: 1\r\n"));
+        assert!(echo.contains("\r\nauthorization: basic dxnlcjpwd2q=\r\n"));
+        assert!(echo.ends_with("\r\n\r\nname=nomcurl"));
+    }
+}
@@ -0,0 +1,260 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use super::raw_http::COMPUTED_HEADERS;
+use crate::curl::{
+    error::ParseError, method::Method, request::ParsedRequest, url_parser::CurlURL, Curl, CurlStru,
+};
+
+#[derive(Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Deserialize)]
+struct Log {
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    request: Request,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    #[serde(default)]
+    cookies: Vec<NameValue>,
+    post_data: Option<PostData>,
+}
+
+#[derive(Deserialize)]
+struct NameValue {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostData {
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    params: Vec<Param>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Param {
+    name: String,
+    #[serde(default)]
+    value: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+}
+
+/// Build the curl command of every entry of a HAR file, e.g. saved from the network panel of a
+/// browser or exported by a proxy.
+///
+/// Only a malformed HAR document is an error, an entry which can't be turned into a command,
+/// e.g. a `data:` url, is an `Err` of its own. Multipart bodies are written with `-F`, the
+/// others verbatim with `--data-raw`. HTTP/2 pseudo-headers, `Host` and `Content-Length` are
+/// dropped, curl computes them.
+pub fn parse_har(
+    input: &str,
+) -> Result<Vec<Result<ParsedRequest<'static>, ParseError>>, ParseError> {
+    let har: Har =
+        serde_json::from_str(input).map_err(|e| ParseError::InvalidHar(e.to_string()))?;
+    Ok(har
+        .log
+        .entries
+        .into_iter()
+        .map(|e| entry_request(e.request))
+        .collect())
+}
+
+fn entry_request(request: Request) -> Result<ParsedRequest<'static>, ParseError> {
+    let method = Method::from_str(&request.method)?;
+    let mut curls = vec![Curl::URL(
+        CurlURL::try_from(request.url.as_str())?.into_owned(),
+    )];
+
+    let post_data = request
+        .post_data
+        .filter(|p| !p.text.is_empty() || !p.params.is_empty());
+    let is_form = post_data
+        .as_ref()
+        .is_some_and(|p| p.mime_type.starts_with("multipart/form-data") && !p.params.is_empty());
+    let implied = match post_data {
+        Some(_) => Method::POST,
+        None => Method::GET,
+    };
+    if method != implied {
+        curls.push(Curl::Method(CurlStru::new_with_data(
+            "-X",
+            method.to_string(),
+        )));
+    }
+
+    let mut has_cookie = false;
+    for NameValue { name, value } in request.headers {
+        has_cookie |= name.eq_ignore_ascii_case("Cookie");
+        // The boundary of the multipart body is curl's own
+        let computed = COMPUTED_HEADERS
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(&name))
+            || (is_form && name.eq_ignore_ascii_case("Content-Type"));
+        if !name.starts_with(':') && !computed {
+            let header = format!("{}: {}", name, value);
+            curls.push(Curl::Header(CurlStru::new_with_data("-H", header)));
+        }
+    }
+    if !has_cookie && !request.cookies.is_empty() {
+        let cookies: Vec<String> = request
+            .cookies
+            .iter()
+            .map(|c| format!("{}={}", c.name, c.value))
+            .collect();
+        curls.push(Curl::Flag(CurlStru::new_with_data(
+            "-b",
+            cookies.join("; "),
+        )));
+    }
+
+    match post_data {
+        Some(post_data) if is_form => {
+            for param in post_data.params {
+                curls.push(Curl::Flag(form_field(param)));
+            }
+        }
+        Some(post_data) if !post_data.text.is_empty() => {
+            curls.push(Curl::Flag(CurlStru::new_with_data(
+                "--data-raw",
+                post_data.text,
+            )));
+        }
+        Some(post_data) => {
+            let pairs: Vec<String> = post_data
+                .params
+                .iter()
+                .map(|p| format!("{}={}", p.name, p.value))
+                .collect();
+            curls.push(Curl::Flag(CurlStru::new_with_data(
+                "--data-raw",
+                pairs.join("&"),
+            )));
+        }
+        None => {}
+    }
+
+    match request.http_version.to_ascii_uppercase().as_str() {
+        "HTTP/1.0" => curls.push(Curl::Flag(CurlStru::new("--http1.0"))),
+        "HTTP/2" | "HTTP/2.0" | "H2" => curls.push(Curl::Flag(CurlStru::new("--http2"))),
+        _ => {}
+    }
+
+    ParsedRequest::new(curls)
+}
+
+/// The `-F` field of a multipart param, `--form-string` keeps a text starting with `@` or `<`.
+fn form_field(param: Param) -> CurlStru<'static> {
+    let Param {
+        name,
+        value,
+        file_name,
+        content_type,
+    } = param;
+    match file_name {
+        Some(file_name) => {
+            let field = match content_type {
+                Some(content_type) => format!("{}=@{};type={}", name, file_name, content_type),
+                None => format!("{}=@{}", name, file_name),
+            };
+            CurlStru::new_with_data("-F", field)
+        }
+        None if value.starts_with(['@', '<']) => {
+            CurlStru::new_with_data("--form-string", format!("{}={}", name, value))
+        }
+        None => CurlStru::new_with_data("-F", format!("{}={}", name, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curl::request::parse_curl_command;
+
+    fn to_curls(input: &str) -> Vec<Result<String, ParseError>> {
+        parse_har(input)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.map(|r| r.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_har() {
+        let input = r#"{"log": {"version": "1.2", "entries": [
+            {"request": {"method": "GET", "url": "https://example.com/a?b=1", "httpVersion": "http/2.0",
+                "headers": [{"name": ":authority", "value": "example.com"}, {"name": "Accept", "value": "*/*"}],
+                "cookies": [{"name": "a", "value": "1"}, {"name": "b", "value": "2"}], "queryString": []}},
+            {"request": {"method": "PUT", "url": "http://example.com/api", "httpVersion": "HTTP/1.1",
+                "headers": [{"name": "Content-Type", "value": "application/json"}, {"name": "Content-Length", "value": "7"}],
+                "postData": {"mimeType": "application/json", "text": "{\"a\":1}"}}},
+            {"request": {"method": "POST", "url": "http://example.com/form",
+                "postData": {"mimeType": "application/x-www-form-urlencoded", "params": [{"name": "a", "value": "1"}, {"name": "b", "value": "2"}]}}}
+        ]}}"#;
+        let expect = [
+            "curl 'https://example.com/a?b=1' -H 'Accept: */*' -b 'a=1; b=2' --http2",
+            "curl 'http://example.com/api' -X 'PUT' -H 'Content-Type: application/json' --data-raw '{\"a\":1}'",
+            "curl 'http://example.com/form' --data-raw 'a=1&b=2'",
+        ];
+        let curls = to_curls(input);
+        assert_eq!(expect.map(|e| Ok(e.to_string())).to_vec(), curls);
+        for curl in curls {
+            assert!(parse_curl_command(&curl.unwrap()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_parse_har_form() {
+        let input = r#"{"log": {"entries": [
+            {"request": {"method": "POST", "url": "https://example.com/upload",
+                "headers": [{"name": "Content-Type", "value": "multipart/form-data; boundary=----x"}, {"name": "Cookie", "value": "s=1"}],
+                "cookies": [{"name": "s", "value": "1"}],
+                "postData": {"mimeType": "multipart/form-data; boundary=----x", "text": "------x...",
+                    "params": [{"name": "a", "value": "1"}, {"name": "at", "value": "@me"},
+                        {"name": "f", "fileName": "a.png", "contentType": "image/png"}]}}}
+        ]}}"#;
+        let expect = "curl 'https://example.com/upload' -H 'Cookie: s=1' -F 'a=1' --form-string 'at=@me' -F 'f=@a.png;type=image/png'";
+        assert_eq!(vec![Ok(expect.to_string())], to_curls(input));
+    }
+
+    #[test]
+    fn test_parse_har_errors() {
+        let input = r#"{"log": {"entries": [
+            {"request": {"method": "GET", "url": "data:text/plain,a"}},
+            {"request": {"method": "GET", "url": "https://example.com"}}
+        ]}}"#;
+        let curls = to_curls(input);
+        assert!(curls[0].is_err());
+        assert_eq!(Ok("curl 'https://example.com'".to_string()), curls[1]);
+
+        assert!(matches!(
+            parse_har(r#"{"entries": []}"#),
+            Err(ParseError::InvalidHar(_))
+        ));
+        assert!(matches!(parse_har("curl"), Err(ParseError::InvalidHar(_))));
+    }
+}
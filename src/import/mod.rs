@@ -0,0 +1,2 @@
+pub mod har;
+pub mod raw_http;
@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use crate::curl::{
+    error::ParseError, method::Method, request::ParsedRequest, url_parser::CurlURL, Curl, CurlStru,
+};
+
+/// The headers curl computes itself, they are dropped from the command.
+pub(super) const COMPUTED_HEADERS: [&str; 2] = ["Host", "Content-Length"];
+
+/// Build the curl command of a raw HTTP/1.x request, e.g. copied from Burp or a pcap dump.
+///
+/// Origin-form targets (`GET /a HTTP/1.1`) are resolved against the `Host` header, over
+/// `http` for port 80 and `https` otherwise. The body is kept verbatim with `--data-raw`.
+pub fn parse_raw_http(input: &str) -> Result<ParsedRequest<'_>, ParseError> {
+    let input = input.trim_start();
+    let (head, body) = match input.split_once("\r\n\r\n") {
+        Some(split) => split,
+        None => input.split_once("\n\n").unwrap_or((input, "")),
+    };
+    let mut lines = head.lines().map(|l| l.trim_end_matches('\r'));
+
+    let request_line = lines.next().unwrap_or_default();
+    let mut request_line = request_line.split_whitespace();
+    let (method, target, version) = match (
+        request_line.next(),
+        request_line.next(),
+        request_line.next(),
+    ) {
+        (Some(method), Some(target), version) => (Method::from_str(method)?, target, version),
+        _ => return Err(ParseError::InvalidHttp("missing request line".into())),
+    };
+
+    let mut headers = Vec::new();
+    for line in lines {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::InvalidHttp(format!("malformed header line: {}", line)))?;
+        headers.push((name.trim(), value.trim()));
+    }
+
+    let url = if target.contains("://") {
+        target.to_string()
+    } else {
+        let host = headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("Host"))
+            .map(|(_, v)| *v)
+            .ok_or(ParseError::MissingUrl)?;
+        let scheme = match host.ends_with(":80") {
+            true => "http",
+            false => "https",
+        };
+        format!("{}://{}{}", scheme, host, target)
+    };
+
+    let mut curls = vec![Curl::URL(CurlURL::try_from(url.as_str())?.into_owned())];
+    let implied = match body.is_empty() {
+        true => Method::GET,
+        false => Method::POST,
+    };
+    if method != implied {
+        curls.push(Curl::Method(CurlStru::new_with_data(
+            "-X",
+            method.to_string(),
+        )));
+    }
+    for (name, value) in headers {
+        if !COMPUTED_HEADERS
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name))
+        {
+            let header = format!("{}: {}", name, value);
+            curls.push(Curl::Header(CurlStru::new_with_data("-H", header)));
+        }
+    }
+    if !body.is_empty() {
+        curls.push(Curl::Flag(CurlStru::new_with_data("--data-raw", body)));
+    }
+    match version {
+        Some("HTTP/1.0") => curls.push(Curl::Flag(CurlStru::new("--http1.0"))),
+        Some("HTTP/2" | "HTTP/2.0") => curls.push(Curl::Flag(CurlStru::new("--http2"))),
+        _ => {}
+    }
+
+    ParsedRequest::new(curls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curl::request::parse_curl_command, test_util::generic_parse};
+
+    #[test]
+    fn test_parse_raw_http() {
+        let input = "POST /api/login?next=home HTTP/1.1\r\n\
+            Host: example.com\r\n\
+            Content-Type: application/json\r\n\
+            Content-Length: 13\r\n\
+            \r\n\
+            {\"user\":\"a\"}";
+        let expect = "curl 'https://example.com/api/login?next=home' -H 'Content-Type: application/json' --data-raw '{\"user\":\"a\"}'";
+        let request = parse_raw_http(input).unwrap();
+        assert_eq!(expect, request.to_string());
+        assert_eq!(parse_curl_command(expect), Ok(request));
+
+        let input = "DELETE http://example.com:8080/a HTTP/1.0\nAccept: */*\n";
+        let expect = "curl 'http://example.com:8080/a' -X 'DELETE' -H 'Accept: */*' --http1.0";
+        let to_curl = |input| parse_raw_http(input).map(|r| r.to_string());
+        generic_parse(to_curl, input, Ok(expect.to_string()));
+
+        let expect = "curl 'http://example.com:80/' -X 'HEAD'";
+        generic_parse(
+            to_curl,
+            "HEAD / HTTP/1.1\nHost: example.com:80",
+            Ok(expect.into()),
+        );
+    }
+
+    #[test]
+    fn test_parse_raw_http_errors() {
+        generic_parse(
+            parse_raw_http,
+            "GET / HTTP/1.1",
+            Err(ParseError::MissingUrl),
+        );
+        generic_parse(
+            parse_raw_http,
+            "",
+            Err(ParseError::InvalidHttp("missing request line".into())),
+        );
+        generic_parse(
+            parse_raw_http,
+            "GET / HTTP/1.1\nHost example.com",
+            Err(ParseError::InvalidHttp(
+                "malformed header line: Host example.com".into(),
+            )),
+        );
+    }
+}
@@ -1,2 +1,32 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "reqwest")]
+pub mod client;
+#[cfg(feature = "std")]
+pub mod codegen;
 pub mod curl;
+#[cfg(feature = "http")]
+pub mod curlify;
+#[cfg(feature = "curl-sys")]
+pub mod easy;
+#[cfg(feature = "exec")]
+pub mod exec;
+#[cfg(feature = "std")]
+pub mod import;
+pub mod shell_quote;
 mod test_util;
+
+/// The `alloc` items of the std prelude, for the `no_std` build of the parser core.
+#[allow(unused_imports)]
+mod prelude {
+    pub use alloc::{
+        borrow::ToOwned,
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+}
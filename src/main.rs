@@ -1,8 +1,45 @@
-use clap::{Arg, Command};
-use curl::{curl_parsers::curl_cmd_parse, Curl};
+use clap::{
+    builder::PossibleValuesParser, builder::TypedValueParser, Arg, ArgAction, ArgMatches, Command,
+};
+use clap_complete::Shell;
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io::{IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-pub mod curl;
-mod test_util;
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use nomcurl::codegen::Target;
+#[cfg(feature = "fancy-errors")]
+use nomcurl::curl::diagnostic::LocatedFinding;
+use nomcurl::curl::{
+    batch, cookies,
+    curl_parsers::curl_cmd_parse,
+    dialect,
+    document::RequestDocument,
+    error::{ErrorCode as ParseErrorCode, ParseError},
+    explain::{explain_at, Explanation},
+    filter::{CommandStream, RequestFilter},
+    format::FormatOptions,
+    highlight::{self, Style},
+    lint::{self, Finding, LintPolicy, Severity},
+    merge,
+    method::{Method, MethodPolicy},
+    options::{self, Category, CurlOption},
+    recover::parse_curl_command_lenient,
+    redact::Redactor,
+    request::{find_curl_commands, is_curl_invocation, parse_curl_command, ParsedRequest},
+    span::{curl_cmd_spans, error_span, Span},
+    stats::CorpusStats,
+    url_parser::{CurlURL, ProtocolPolicy},
+    variables, Curl,
+};
+use nomcurl::import::har;
+use nomcurl::shell_quote;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum CurlCommand {
@@ -13,22 +50,380 @@ pub enum CurlCommand {
     Url,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Yaml,
+    Toml,
+    /// One JSON object per curl command found in the input.
+    Ndjson,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BatchFormat {
+    Ndjson,
+    /// A single JSON array of every command.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CookieFormat {
+    /// Aligned `NAME VALUE SOURCE` columns.
+    Table,
+    /// A cookie-jar file for `curl -b`.
+    Netscape,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Colors when stdout is a terminal and NO_COLOR isn't set.
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiagnosticFormat {
+    /// One `severity[code]: message` line per diagnostic.
+    Text,
+    Json,
+    /// An annotated excerpt of the command per diagnostic.
+    #[cfg(feature = "fancy-errors")]
+    Fancy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    Text,
+    /// A `{"code", "message", "exit_code", "span"}` object.
+    Json,
+    /// An annotated excerpt of the command for a parse error, text otherwise.
+    #[cfg(feature = "fancy-errors")]
+    Fancy,
+}
+
+/// The failure classes of the CLI, each with its own exit code, see `EXIT_CODES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum ErrorCode {
+    /// The input isn't a valid request document.
+    InvalidDocument,
+    /// A file or stdin can't be read, or the output can't be written.
+    IoError,
+    /// An option value or the input is otherwise unusable, e.g. an unresolved variable.
+    InvalidInput,
+    /// `exec` couldn't send the request.
+    #[cfg_attr(not(feature = "exec"), allow(dead_code))]
+    RequestError,
+    /// The output can't be serialized.
+    SerializeError,
+    /// `convert` can't write the request for the target.
+    CodegenError,
+    /// The input isn't a valid curl command, serialized as the code of the parse error.
+    #[serde(untagged)]
+    Parse(ParseErrorCode),
+}
+
+impl ErrorCode {
+    fn exit_code(self) -> i32 {
+        match self {
+            ErrorCode::Parse(_) | ErrorCode::InvalidDocument => 1,
+            ErrorCode::IoError => 3,
+            ErrorCode::InvalidInput => 4,
+            ErrorCode::RequestError => 5,
+            ErrorCode::SerializeError => 6,
+            ErrorCode::CodegenError => 7,
+        }
+    }
+}
+
+const EXIT_CODES: &str = "Exit codes:
+  0  Success
+  1  The input isn't a valid curl command, or lint/validate found errors
+  2  Warnings with --fail-on-warning or --strict, or invalid arguments
+  3  A file or stdin can't be read, or the output can't be written
+  4  An option value or the input is otherwise unusable
+  5  exec couldn't send the request
+  6  The output can't be serialized
+  7  convert can't write the request for the target";
+
+/// A failure of a subcommand, printed as text or, with `--error-format json`, as JSON.
+#[derive(Debug, Serialize)]
+struct CliError {
+    code: ErrorCode,
+    message: String,
+    exit_code: i32,
+    /// Where the parser stopped in the input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span: Option<ErrorSpan>,
+    /// The parse error with the command it's about, for `--error-format fancy`.
+    #[cfg(feature = "fancy-errors")]
+    #[serde(skip)]
+    report: Option<Box<(ParseError, String)>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorSpan {
+    start: usize,
+    end: usize,
+    line: usize,
+    column: usize,
+}
+
+impl CliError {
+    fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            exit_code: code.exit_code(),
+            span: None,
+            #[cfg(feature = "fancy-errors")]
+            report: None,
+        }
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(span) = &self.span {
+            write!(f, " (at line {}, column {})", span.line, span.column)?;
+        }
+        Ok(())
+    }
+}
+
+/// A parse error of the command, a slice of the input, spanning where the parser stopped: the
+/// token of a syntax error, or the one `error_span` finds.
+fn parse_error(input: &str, command: &str, context: &str, err: &ParseError) -> CliError {
+    let offset = command.as_ptr() as usize - input.as_ptr() as usize;
+    // The span tells the line and column
+    let mut error = CliError::new(
+        ErrorCode::Parse(err.code()),
+        format!("{}: {:#}", context, err),
+    );
+    let span = match err.root() {
+        ParseError::Syntax(e) => Some(Span {
+            start: e.offset,
+            end: e.offset + e.found.len(),
+        }),
+        ParseError::UnsupportedDialect(hint) => Some(Span {
+            start: hint.offset,
+            end: hint.offset + hint.len,
+        }),
+        _ => error_span(command),
+    };
+    error.span = span.map(|span| {
+        let span = Span {
+            start: span.start + offset,
+            end: span.end + offset,
+        };
+        let (line, column) = span.line_col(input);
+        ErrorSpan {
+            start: span.start,
+            end: span.end,
+            line,
+            column,
+        }
+    });
+    #[cfg(feature = "fancy-errors")]
+    {
+        error.report = Some(Box::new((err.clone(), command.to_string())));
+    }
+    error
+}
+
+/// A problem reported by `validate`: a parse error or a lint finding.
+#[derive(Debug, Serialize)]
+struct Diagnostic {
+    code: &'static str,
+    severity: Severity,
+    message: String,
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(err: &ParseError) -> Self {
+        Self {
+            code: err.code().as_str(),
+            severity: Severity::Error,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<Finding> for Diagnostic {
+    fn from(finding: Finding) -> Self {
+        Self {
+            code: finding.lint.code(),
+            severity: finding.severity,
+            message: finding.message,
+        }
+    }
+}
+
 // TODO: Build more funcs
 fn main() {
-    let matches = Command::new("nomcurl")
+    let matches = cli().get_matches();
+    init_tracing(matches.get_count("verbose"));
+
+    let result = match matches.subcommand() {
+        Some(("parse", sub_matches)) => parse(sub_matches),
+        Some(("batch", sub_matches)) => batch(sub_matches),
+        Some(("filter", sub_matches)) => filter(sub_matches),
+        Some(("stats", sub_matches)) => stats(sub_matches),
+        Some(("validate", sub_matches)) => validate(sub_matches),
+        Some(("lint", sub_matches)) => lint(sub_matches),
+        Some(("diff", sub_matches)) => diff(sub_matches),
+        Some(("merge", sub_matches)) => merge(sub_matches),
+        Some(("convert", sub_matches)) => convert(sub_matches),
+        Some(("explain", sub_matches)) => explain(sub_matches),
+        Some(("options", sub_matches)) => options(sub_matches),
+        Some(("cookies", sub_matches)) => cookies(sub_matches),
+        Some(("format", sub_matches)) => format(sub_matches),
+        Some(("minify", sub_matches)) => minify(sub_matches),
+        Some(("edit", sub_matches)) => edit(sub_matches),
+        Some(("build", sub_matches)) => build(sub_matches),
+        Some(("import", sub_matches)) => import(sub_matches),
+        Some(("template", sub_matches)) => template(sub_matches),
+        Some(("interactive", sub_matches)) => interactive(use_color(sub_matches)),
+        Some(("completions", sub_matches)) => {
+            let shell = *sub_matches.get_one::<Shell>("shell").unwrap();
+            clap_complete::generate(shell, &mut cli(), "nomcurl", &mut std::io::stdout());
+            Ok(())
+        }
+        #[cfg(feature = "exec")]
+        Some(("exec", sub_matches)) => exec(sub_matches),
+        #[cfg(feature = "schemars")]
+        Some(("schema", _)) => {
+            println!("{}", nomcurl::curl::document::json_schema());
+            Ok(())
+        }
+        _ => {
+            cli().print_help().unwrap();
+            println!();
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        match matches.get_one::<ErrorFormat>("error-format").unwrap() {
+            ErrorFormat::Text => eprintln!("{}", e),
+            ErrorFormat::Json => eprintln!("{}", serde_json::to_string(&e).unwrap()),
+            #[cfg(feature = "fancy-errors")]
+            ErrorFormat::Fancy => match &e.report {
+                Some(report) => {
+                    let (err, command) = report.as_ref();
+                    eprintln!("{:?}", fancy_report(err.clone(), command));
+                }
+                None => eprintln!("{}", e),
+            },
+        }
+        std::process::exit(e.exit_code);
+    }
+    if WARNED.load(Ordering::Relaxed) {
+        std::process::exit(2);
+    }
+}
+
+fn cli() -> Command {
+    let command = Command::new("nomcurl")
         .version("0.1.0")
         .about("A CLI tool to parse and manipulate curl commands")
         .subcommand_required(true)
         .arg_required_else_help(true)
+        .after_help(EXIT_CODES)
+        .arg(
+            Arg::new("error-format")
+                .long("error-format")
+                .value_name("FORMAT")
+                .help("Prints the errors to stderr as text or as JSON objects with a code and a span")
+                .default_value("text")
+                .value_parser(clap::value_parser!(ErrorFormat))
+                .global(true),
+        )
+        .arg(
+            Arg::new("unknown-protocol")
+                .long("unknown-protocol")
+                .value_name("POLICY")
+                .help("Accepts a url scheme other than http, https, ftp and smb, warns about it or rejects it")
+                .default_value("warn")
+                .value_parser(
+                    PossibleValuesParser::new(ProtocolPolicy::ALL.iter().map(|p| p.name())).map(
+                        |name| *ProtocolPolicy::ALL.iter().find(|p| p.name() == name).unwrap(),
+                    ),
+                )
+                .global(true),
+        )
+        .arg(
+            Arg::new("custom-method")
+                .long("custom-method")
+                .value_name("POLICY")
+                .help("Accepts a -X method other than the standard HTTP ones, warns about it or rejects it")
+                .default_value("warn")
+                .value_parser(
+                    PossibleValuesParser::new(MethodPolicy::ALL.iter().map(|p| p.name())).map(
+                        |name| *MethodPolicy::ALL.iter().find(|p| p.name() == name).unwrap(),
+                    ),
+                )
+                .global(true),
+        )
+        .arg(
+            Arg::new("fail-on-warning")
+                .long("fail-on-warning")
+                .help("Exits with 2 when a parsed command has lint warnings, which are printed to stderr")
+                .action(ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Traces the parse pipeline to stderr, -vv also traces every param")
+                .action(ArgAction::Count)
+                .global(true),
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .value_name("WHEN")
+                .help("Colors the format, minify and interactive output")
+                .default_value("auto")
+                .value_parser(clap::value_parser!(ColorChoice))
+                .global(true),
+        )
+        .arg(
+            Arg::new("env")
+                .long("env")
+                .value_name("KEY=VALUE")
+                .help("Replaces the $KEY, ${KEY} and {{KEY}} placeholders of the input before parsing, can be repeated")
+                .action(ArgAction::Append)
+                .global(true),
+        )
+        .arg(
+            Arg::new("env-file")
+                .long("env-file")
+                .value_name("FILE")
+                .help("Reads the KEY=VALUE variables of a .env file, --env takes precedence")
+                .global(true),
+        )
+        .arg(
+            Arg::new("require-env")
+                .long("require-env")
+                .help("Fails when a placeholder of the input has no variable")
+                .action(ArgAction::SetTrue)
+                .global(true),
+        )
         .subcommand(
             Command::new("parse")
                 .about("Parses a curl command")
-                .arg(
-                    Arg::new("command")
-                        .help("The input curl command string")
-                        .required(true)
-                        .index(1),
-                )
+                .args(input_args())
                 .arg(
                     Arg::new("part")
                         .short('p')
@@ -37,57 +432,1659 @@ fn main() {
                         .help("Specifies which part of the curl command to parse (method, header, data, flag, url)")
                         .required(false)
                         .value_parser(clap::value_parser!(CurlCommand)),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("The output format of the parsed command")
+                        .default_value("text")
+                        .conflicts_with("part")
+                        .value_parser(clap::value_parser!(OutputFormat)),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Alias of --format json")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with_all(["part", "format"]),
+                )
+                .arg(
+                    Arg::new("redact")
+                        .long("redact")
+                        .help("Replaces the credentials, tokens, cookies and other secrets by REDACTED")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("redact-pattern")
+                        .long("redact-pattern")
+                        .value_name("NAME")
+                        .help("Also redacts the headers, query params and form fields whose name contains NAME")
+                        .action(ArgAction::Append)
+                        .requires("redact"),
+                )
+                .arg(
+                    Arg::new("raw-headers")
+                        .long("raw-headers")
+                        .help("Keeps every header as written, e.g. Name:value, in the raw field of the JSON, YAML or TOML output")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("part"),
+                )
+                .arg(
+                    Arg::new("query")
+                        .short('q')
+                        .long("query")
+                        .value_name("EXPR")
+                        .help("Prints a single value of the JSON output, e.g. .url.domain or .headers[\"Authorization\"]")
+                        .conflicts_with_all(["part", "format", "json"]),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("batch")
+                .about("Parses every curl command found in a file, e.g. a shell script or a log extract")
+                .arg(
+                    Arg::new("input")
+                        .help("The input file, `-` reads it from stdin")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("The output format of the parsed commands")
+                        .default_value("ndjson")
+                        .value_parser(clap::value_parser!(BatchFormat)),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("filter")
+                .about("Reads curl commands from stdin as they come, printing the ones matching the predicates")
+                .arg(
+                    Arg::new("host")
+                        .long("host")
+                        .value_name("HOST")
+                        .help("Keeps the requests to the host, can be repeated")
+                        .action(ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("method")
+                        .short('X')
+                        .long("method")
+                        .value_name("METHOD")
+                        .help("Keeps the requests sent with the method, can be repeated")
+                        .action(ArgAction::Append)
+                        .value_parser(|m: &str| m.parse::<Method>()),
+                )
+                .arg(
+                    Arg::new("header")
+                        .short('H')
+                        .long("header")
+                        .value_name("NAME[:VALUE]")
+                        .help("Keeps the requests sending the header, whose value contains VALUE if given, can be repeated")
+                        .action(ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Prints one JSON document per line instead of the commands")
+                        .action(ArgAction::SetTrue),
                 ),
         )
-        .get_matches();
-
-    match matches.subcommand() {
-        Some(("parse", sub_matches)) => {
-            let command = sub_matches.get_one::<String>("command").unwrap();
-            let part = sub_matches.get_one::<CurlCommand>("part");
-
-            match curl_cmd_parse(command) {
-                Ok((_remaining, curls)) => {
-                    if let Some(part) = part {
-                        match part {
-                            CurlCommand::Method => {
-                                for curl in curls.iter().filter(|c| matches!(c, Curl::Method(_))) {
-                                    println!("{:?}", curl);
-                                }
-                            }
-                            CurlCommand::Header => {
-                                for curl in curls.iter().filter(|c| matches!(c, Curl::Header(_))) {
-                                    println!("{:?}", curl);
-                                }
-                            }
-                            CurlCommand::Data => {
-                                for curl in curls.iter().filter(|c| matches!(c, Curl::Data(_))) {
-                                    println!("{:?}", curl);
-                                }
-                            }
-                            CurlCommand::Flag => {
-                                for curl in curls.iter().filter(|c| matches!(c, Curl::Flag(_))) {
-                                    println!("{:?}", curl);
-                                }
-                            }
-                            CurlCommand::Url => {
-                                for curl in curls.iter().filter(|c| matches!(c, Curl::URL(_))) {
-                                    println!("{:?}", curl);
-                                }
-                            }
-                        }
-                    } else {
-                        for curl in curls {
-                            println!("{:?}", curl);
-                        }
-                    }
+        .subcommand(
+            Command::new("stats")
+                .about("Reports the hosts, methods, headers and flags of every curl command found in a file")
+                .arg(
+                    Arg::new("input")
+                        .help("The input file, `-` reads it from stdin")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Prints the statistics as JSON")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("validate")
+                .about("Checks a curl command, exits with 0 if it's valid, 1 on errors and 2 on warnings with --strict")
+                .args(input_args())
+                .arg(
+                    Arg::new("strict")
+                        .long("strict")
+                        .help("Fails with the exit code 2 on warnings")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("The output format of the diagnostics")
+                        .default_value("text")
+                        .value_parser(clap::value_parser!(DiagnosticFormat)),
+                ),
+        )
+        .subcommand(
+            Command::new("lint")
+                .about("Checks a curl command for likely mistakes")
+                .args(input_args())
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("The output format of the findings")
+                        .default_value("text")
+                        .value_parser(clap::value_parser!(DiagnosticFormat)),
+                )
+                .arg(
+                    Arg::new("deny")
+                        .short('D')
+                        .long("deny")
+                        .value_name("LEVEL")
+                        .help("Fails on the findings of the level, errors always fail")
+                        .value_parser(["warnings"]),
+                ),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Compares two curl commands: url, method, headers, queries, body and flags")
+                .arg(
+                    Arg::new("before")
+                        .help("The first curl command, or a file holding it, `-` reads it from stdin")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("after")
+                        .help("The second curl command, or a file holding it")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Prints the changes as JSON")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("merge")
+                .about("Applies the headers, flags, data and url of an overlay command on top of every base command")
+                .arg(
+                    Arg::new("base")
+                        .help("The base curl commands, or a file holding them, `-` reads them from stdin")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("overlay")
+                        .help("The overlay curl command, its url may be left out, e.g. `curl -H 'Authorization: Bearer x'`")
+                        .required(true)
+                        .index(2),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("convert")
+                .about("Converts a curl command to code or another format, see --list-targets")
+                .args(input_args().map(|arg| match arg.get_id() == "command" {
+                    true => arg.required_unless_present_any(["file", "list-targets"]),
+                    false => arg,
+                }))
+                .arg(
+                    Arg::new("to")
+                        .short('t')
+                        .long("to")
+                        .value_name("TARGET")
+                        .help("The target format")
+                        .required_unless_present("list-targets")
+                        .value_parser(
+                            PossibleValuesParser::new(Target::ALL.iter().map(|t| t.name()))
+                                .map(|name| name.parse::<Target>().unwrap()),
+                        ),
+                )
+                .arg(
+                    Arg::new("list-targets")
+                        .long("list-targets")
+                        .help("Lists the target formats")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .help("Converts every curl command of the input, http-file joins them with ### and har lists them in one log")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("explain")
+                .about("Explains what every option of a curl command does")
+                .args(input_args())
+                .arg(
+                    Arg::new("at")
+                        .long("at")
+                        .value_name("OFFSET")
+                        .help("Explains only the option at the byte offset of the input, which may be a script holding curl commands")
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Prints the explanations as JSON")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("options")
+                .about("Lists the curl options nomcurl knows about, or looks one up")
+                .arg(
+                    Arg::new("name")
+                        .value_name("OPTION")
+                        .help("The long name or short alias of the option, e.g. --retry or -k")
+                        .allow_hyphen_values(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("category")
+                        .long("category")
+                        .value_name("CATEGORY")
+                        .help("Lists the options of the category only")
+                        .conflicts_with("name")
+                        .value_parser(
+                            PossibleValuesParser::new(Category::ALL.iter().map(|c| c.name())).map(
+                                |name| *Category::ALL.iter().find(|c| c.name() == name).unwrap(),
+                            ),
+                        ),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Prints the options with their aliases, arity, category and curl version as JSON")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("cookies")
+                .about("Lists the cookies of the -b strings and Cookie headers of a curl command")
+                .args(input_args())
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("The output format of the cookies")
+                        .default_value("table")
+                        .value_parser(clap::value_parser!(CookieFormat)),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("format")
+                .about("Pretty-prints a curl command with one option per line")
+                .args(input_args())
+                .arg(
+                    Arg::new("sort")
+                        .long("sort")
+                        .help("Orders the options: method, headers by name, data, flags")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("indent")
+                        .long("indent")
+                        .value_name("SPACES")
+                        .help("The indentation of the continuation lines")
+                        .default_value("2")
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .args(shell_args())
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("minify")
+                .about("Collapses a curl command into a single line")
+                .args(input_args())
+                .arg(
+                    Arg::new("strip-noop")
+                        .long("strip-noop")
+                        .help("Leaves out the options which don't change the request, e.g. -s or a redundant -X")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(shell_args())
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("edit")
+                .about("Opens the formatted command in $VISUAL or $EDITOR, then prints the edited one on a single line")
+                .args(input_args())
+                .arg(
+                    Arg::new("yaml")
+                        .long("yaml")
+                        .help("Edits the YAML of the parsed request instead of the command")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("build")
+                .about("Builds a curl command from the JSON of `parse --json`, or a spec with a url string")
+                .arg(
+                    Arg::new("spec")
+                        .help("The JSON file, `-` reads it from stdin")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("pretty")
+                        .long("pretty")
+                        .help("Prints one option per line")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Builds the curl command of every request of a HAR file")
+                .arg(
+                    Arg::new("har")
+                        .help("The HAR file, `-` reads it from stdin")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("pretty")
+                        .long("pretty")
+                        .help("Prints one option per line")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(shell_args())
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("template")
+                .about("Renders the {{VAR}} and $VAR placeholders of a curl command")
+                .args(input_args())
+                .arg(
+                    Arg::new("var")
+                        .long("var")
+                        .value_name("KEY=VALUE")
+                        .help("The value of a placeholder, can be repeated, takes precedence over --env")
+                        .action(ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Prints the parsed request of the rendered command as JSON")
+                        .action(ArgAction::SetTrue),
+                )
+                .args(output_args()),
+        )
+        .subcommand(
+            Command::new("interactive")
+                .about("Starts a REPL to inspect and edit pasted curl commands, type `help` in it"),
+        )
+        .subcommand(
+            Command::new("completions")
+                .about("Prints the completion script of a shell")
+                .arg(
+                    Arg::new("shell")
+                        .help("The shell to complete in")
+                        .required(true)
+                        .index(1)
+                        .value_parser(clap::value_parser!(Shell)),
+                ),
+        );
+    #[cfg(feature = "exec")]
+    let command = command.subcommand(
+        Command::new("exec")
+            .about("Sends the request and prints the status, headers and body of the response")
+            .args(input_args())
+            .args(output_args()),
+    );
+    #[cfg(feature = "schemars")]
+    let command = command.subcommand(
+        Command::new("schema").about("Prints the JSON Schema of the --format json output"),
+    );
+    command
+}
+
+/// The input args of the subcommands taking a single curl command, see `read_input`.
+fn input_args() -> [Arg; 2] {
+    [
+        Arg::new("command")
+            .help("The input curl command string, `-` reads it from stdin")
+            .required_unless_present("file")
+            .conflicts_with("file")
+            .index(1),
+        Arg::new("file")
+            .long("file")
+            .value_name("FILE")
+            .help("Reads the curl command from a file, e.g. a saved multi-line snippet"),
+    ]
+}
+
+/// The `--shell` and `--minimal-quotes` args of the subcommands writing a curl command.
+fn shell_args() -> [Arg; 2] {
+    [
+        Arg::new("shell")
+            .long("shell")
+            .value_name("SHELL")
+            .help("Quotes the command and continues its lines for a POSIX shell, cmd.exe or PowerShell")
+            .default_value("posix")
+            .value_parser(
+                PossibleValuesParser::new(shell_quote::Shell::ALL.iter().map(|s| s.name())).map(
+                    |name| *shell_quote::Shell::ALL.iter().find(|s| s.name() == name).unwrap(),
+                ),
+            ),
+        Arg::new("minimal-quotes")
+            .long("minimal-quotes")
+            .help("Quotes only the values which need it, e.g. -X POST")
+            .action(ArgAction::SetTrue),
+    ]
+}
+
+/// The `--output` and `--force` args of the subcommands writing a result.
+fn output_args() -> [Arg; 2] {
+    [
+        Arg::new("output")
+            .short('o')
+            .long("output")
+            .value_name("FILE")
+            .help("Writes the output to a file instead of stdout"),
+        Arg::new("force")
+            .long("force")
+            .help("Overwrites the --output file if it already exists")
+            .action(ArgAction::SetTrue)
+            .requires("output"),
+    ]
+}
+
+fn parse(matches: &ArgMatches) -> Result<(), CliError> {
+    let input = read_input(matches)?;
+    let part = matches.get_one::<CurlCommand>("part");
+    let format = match matches.get_flag("json") {
+        true => OutputFormat::Json,
+        false => *matches.get_one::<OutputFormat>("format").unwrap(),
+    };
+
+    let redactor = matches.get_flag("redact").then(|| {
+        let patterns = matches.get_many::<String>("redact-pattern");
+        patterns
+            .into_iter()
+            .flatten()
+            .fold(Redactor::new(), |redactor, p| redactor.with_pattern(p))
+    });
+    let redact = |request| match &redactor {
+        Some(redactor) => redactor.redact(&request),
+        None => request,
+    };
+
+    if let Some(expr) = matches.get_one::<String>("query") {
+        let value = redact(parse_request(matches, &input)?)
+            .query(expr)
+            .map_err(|e| format!("Error: {}", e))?;
+        // Strings are printed raw, as `jq -r` does
+        let output = match value {
+            serde_json::Value::String(s) => s,
+            value => value.to_string(),
+        };
+        return write_output(matches, format!("{}\n", output));
+    }
+
+    if format == OutputFormat::Ndjson {
+        let mut entries = batch::parse_batch(&input);
+        check_batch_warnings(matches, &entries);
+        if let Some(redactor) = &redactor {
+            for entry in &mut entries {
+                if let Ok(request) = parse_curl_command(&entry.command) {
+                    let request = redactor.redact(&request);
+                    let command = request.to_string();
+                    entry.request = Some(request.into());
+                    entry.command = command;
                 }
-                Err(e) => eprintln!("Error parsing curl command: {:?}", e),
             }
         }
-        _ => {
-            Command::new("nomcurl").print_help().unwrap();
-            println!();
+        let output = batch::to_ndjson(&entries).map_err(|e| {
+            CliError::new(
+                ErrorCode::SerializeError,
+                format!("Error serializing curl commands: {}", e),
+            )
+        })?;
+        return write_output(matches, &output);
+    }
+
+    let command = first_command(&input);
+    if format != OutputFormat::Text {
+        let request = redact(parse_request(matches, &input)?);
+        let document = match matches.get_flag("raw-headers") {
+            true => RequestDocument::with_raw_headers(&request),
+            false => RequestDocument::from(&request),
+        };
+        let output = match format {
+            OutputFormat::Yaml => serde_yaml::to_string(&document).map_err(|e| e.to_string()),
+            OutputFormat::Toml => toml::to_string(&document).map_err(|e| e.to_string()),
+            _ => serde_json::to_string_pretty(&document).map_err(|e| e.to_string()),
+        }
+        .map_err(|e| {
+            CliError::new(
+                ErrorCode::SerializeError,
+                format!("Error serializing curl command: {}", e),
+            )
+        })?;
+        return write_output(matches, format!("{}\n", output.trim_end()));
+    }
+
+    let (_remaining, curls) = curl_cmd_parse(command).map_err(|_| {
+        // parse_curl_command tells why, e.g. a url given to -X or another shell's quoting
+        let e = parse_curl_command(command).expect_err("the params failed to parse");
+        parse_error(&input, command, "Error parsing curl command", &e)
+    })?;
+    let curls = match ParsedRequest::new(curls.clone()) {
+        Ok(request) => {
+            request
+                .check_protocol(lint_policy(matches).protocol)
+                .and_then(|_| request.check_method(lint_policy(matches).method))
+                .map_err(|e| parse_error(&input, command, "Error parsing curl command", &e))?;
+            check_warnings(matches, &request);
+            redact(request).into_curls()
+        }
+        Err(_) => curls,
+    };
+    let mut output = String::new();
+    for curl in curls.iter().filter(|curl| match part {
+        Some(CurlCommand::Method) => matches!(curl, Curl::Method(_)),
+        Some(CurlCommand::Header) => matches!(curl, Curl::Header(_)),
+        Some(CurlCommand::Data) => matches!(curl, Curl::Data(_)),
+        Some(CurlCommand::Flag) => matches!(curl, Curl::Flag(_)),
+        Some(CurlCommand::Url) => matches!(curl, Curl::URL(_)),
+        None => true,
+    }) {
+        writeln!(output, "{:?}", curl).unwrap();
+    }
+    write_output(matches, &output)
+}
+
+/// Print the diagnostics of the command and exit with its validation status.
+fn validate(matches: &ArgMatches) -> Result<(), CliError> {
+    let input = read_input(matches)?;
+    let command = first_command(&input);
+
+    // Every bad token is reported, the lints are only run on a valid command
+    let recovered = parse_curl_command_lenient(command);
+    let errors = recovered.errors.clone();
+    let findings = match (recovered.is_valid(), recovered.into_request()) {
+        (true, Ok(request)) => lint_request(matches, &request),
+        _ => Vec::new(),
+    };
+    let diagnostics: Vec<Diagnostic> = (errors.iter().map(Diagnostic::from))
+        .chain(findings.iter().cloned().map(Diagnostic::from))
+        .collect();
+
+    match matches.get_one::<DiagnosticFormat>("format").unwrap() {
+        DiagnosticFormat::Text => {
+            for diagnostic in &diagnostics {
+                println!(
+                    "{}[{}]: {}",
+                    diagnostic.severity, diagnostic.code, diagnostic.message
+                );
+            }
+        }
+        DiagnosticFormat::Json => {
+            let valid = diagnostics.iter().all(|d| d.severity < Severity::Error);
+            let json = serde_json::json!({ "valid": valid, "diagnostics": diagnostics });
+            println!("{}", json);
+        }
+        #[cfg(feature = "fancy-errors")]
+        DiagnosticFormat::Fancy => {
+            for err in errors {
+                println!("{:?}", fancy_report(err, command));
+            }
+            let spans = curl_cmd_spans(command).unwrap_or_default();
+            for finding in LocatedFinding::locate(findings, &spans) {
+                println!("{:?}", fancy_report(finding, command));
+            }
+        }
+    }
+
+    let severity = diagnostics.iter().map(|d| d.severity).max();
+    match severity {
+        Some(Severity::Error) => std::process::exit(1),
+        Some(Severity::Warning)
+            if matches.get_flag("strict") || matches.get_flag("fail-on-warning") =>
+        {
+            std::process::exit(2)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Print the lint findings of the command with the location of the param each one is about.
+fn lint(matches: &ArgMatches) -> Result<(), CliError> {
+    let input = read_input(matches)?;
+    let command = first_command(&input);
+    let error = |e: ParseError| parse_error(&input, command, "Error parsing curl command", &e);
+    let request = parse_curl_command(command).map_err(error)?;
+    let spans = curl_cmd_spans(command).map_err(error)?;
+    let findings = lint_request(matches, &request);
+
+    match matches.get_one::<DiagnosticFormat>("format").unwrap() {
+        DiagnosticFormat::Text => {
+            for finding in &findings {
+                println!("{}", finding);
+                if let Some(span) = finding.param.and_then(|i| spans.get(i)) {
+                    let (line, column) = span.line_col(command);
+                    println!(
+                        "  --> {}:{} {}",
+                        line,
+                        column,
+                        &command[span.start..span.end]
+                    );
+                }
+            }
+        }
+        DiagnosticFormat::Json => {
+            let findings: Vec<serde_json::Value> = findings
+                .iter()
+                .map(|finding| {
+                    let span = finding.param.and_then(|i| spans.get(i)).map(|span| {
+                        let (line, column) = span.line_col(command);
+                        serde_json::json!({
+                            "start": span.start,
+                            "end": span.end,
+                            "line": line,
+                            "column": column,
+                        })
+                    });
+                    serde_json::json!({
+                        "code": finding.lint.code(),
+                        "severity": finding.severity,
+                        "message": finding.message,
+                        "span": span,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::Value::from(findings));
+        }
+        #[cfg(feature = "fancy-errors")]
+        DiagnosticFormat::Fancy => {
+            for finding in LocatedFinding::locate(findings.clone(), &spans) {
+                println!("{:?}", fancy_report(finding, command));
+            }
+        }
+    }
+
+    let deny = match matches.get_one::<String>("deny") {
+        Some(_) => Severity::Warning,
+        None if matches.get_flag("fail-on-warning") => Severity::Warning,
+        None => Severity::Error,
+    };
+    if findings.iter().any(|f| f.severity >= deny) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// The diagnostic with an annotated excerpt of the command, debug-formatted to render it.
+#[cfg(feature = "fancy-errors")]
+fn fancy_report(
+    diagnostic: impl miette::Diagnostic + Send + Sync + 'static,
+    command: &str,
+) -> miette::Report {
+    miette::Report::new(diagnostic).with_source_code(command.to_string())
+}
+
+fn diff(matches: &ArgMatches) -> Result<(), CliError> {
+    let mut requests = Vec::new();
+    for arg in ["before", "after"] {
+        let arg = matches.get_one::<String>(arg).unwrap();
+        let input = read_command_arg(matches, arg)?;
+        let command = first_command(&input);
+        let request = parse_curl_command(command).map_err(|e| {
+            let context = format!("Error parsing curl command {}", arg);
+            parse_error(&input, command, &context, &e)
+        })?;
+        check_warnings(matches, &request);
+        requests.push(request.into_owned());
+    }
+
+    let diff = requests[0].diff(&requests[1]);
+    let output = match matches.get_flag("json") {
+        true => serde_json::to_string_pretty(&diff)
+            .map(|json| json + "\n")
+            .map_err(|e| {
+                CliError::new(
+                    ErrorCode::SerializeError,
+                    format!("Error serializing the diff: {}", e),
+                )
+            })?,
+        false => diff.to_string(),
+    };
+    write_output(matches, &output)
+}
+
+/// Apply the overlay on every command of the base input, one merged command per line.
+fn merge(matches: &ArgMatches) -> Result<(), CliError> {
+    let base = matches.get_one::<String>("base").unwrap();
+    let input = read_command_arg(matches, base)?;
+    let overlay_input = read_command_arg(matches, matches.get_one::<String>("overlay").unwrap())?;
+    let overlay_command = first_command(&overlay_input);
+    let overlay = merge::parse_overlay(overlay_command).map_err(|e| {
+        parse_error(
+            &overlay_input,
+            overlay_command,
+            "Error parsing the overlay",
+            &e,
+        )
+    })?;
+
+    let mut output = String::new();
+    for command in find_curl_commands(&input) {
+        let request = parse_curl_command(command).map_err(|e| {
+            let context = format!("Error parsing curl command {}", base);
+            parse_error(&input, command, &context, &e)
+        })?;
+        let merged = merge::merge(&request, &overlay);
+        check_warnings(matches, &merged);
+        writeln!(output, "{}", merged).unwrap();
+    }
+    if output.is_empty() {
+        return Err(format!("Error: no curl command found in {}", base).into());
+    }
+    write_output(matches, &output)
+}
+
+fn convert(matches: &ArgMatches) -> Result<(), CliError> {
+    if matches.get_flag("list-targets") {
+        let mut output = String::new();
+        for target in Target::ALL {
+            writeln!(output, "{:<12}{}", target.name(), target.description()).unwrap();
+        }
+        return write_output(matches, output);
+    }
+
+    let target = *matches.get_one::<Target>("to").unwrap();
+    let requests = match matches.get_flag("all") {
+        true => {
+            let input = read_input(matches)?;
+            find_curl_commands(&input)
+                .into_iter()
+                .map(|command| parse_request(matches, command).map(ParsedRequest::into_owned))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        false => vec![read_request(matches)?],
+    };
+
+    if target == Target::Har {
+        let har = nomcurl::codegen::har::generate_batch(&requests).map_err(|e| {
+            CliError::new(
+                ErrorCode::IoError,
+                format!("Error converting to {}: {}", target.name(), e),
+            )
+        })?;
+        return write_output(matches, har);
+    }
+    let separator: &[u8] = match target {
+        Target::HttpFile => b"\n###\n\n",
+        _ => b"\n",
+    };
+    let mut output = Vec::new();
+    for (i, request) in requests.iter().enumerate() {
+        if i > 0 {
+            output.extend_from_slice(separator);
+        }
+        let code = target.generate(request).map_err(|e| {
+            let message = format!("Error converting to {}: {}", target.name(), e);
+            // Only reading the file of a `@file` payload fails otherwise
+            let code = match e.kind() {
+                std::io::ErrorKind::InvalidData => ErrorCode::CodegenError,
+                _ => ErrorCode::IoError,
+            };
+            CliError::new(code, message)
+        })?;
+        output.extend(code);
+    }
+    write_output(matches, output)
+}
+
+fn explain(matches: &ArgMatches) -> Result<(), CliError> {
+    let explanations = match matches.get_one::<usize>("at") {
+        Some(&offset) => vec![explain_offset(&read_input(matches)?, offset)?],
+        None => read_request(matches)?.explain(),
+    };
+    let output = match matches.get_flag("json") {
+        true => serde_json::to_string_pretty(&explanations).map_err(|e| {
+            let message = format!("Error serializing the explanations: {}", e);
+            CliError::new(ErrorCode::SerializeError, message)
+        })?,
+        false => explanations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    write_output(matches, format!("{}\n", output))
+}
+
+/// The explanation of the option at the offset of the input, e.g. an editor cursor.
+fn explain_offset(input: &str, offset: usize) -> Result<Explanation, CliError> {
+    explain_at(input, offset).ok_or_else(|| {
+        let message = format!("No option of a curl command at offset {}", offset);
+        CliError::new(ErrorCode::InvalidInput, message)
+    })
+}
+
+/// Print the option registry, one option, or the options of a category.
+fn options(matches: &ArgMatches) -> Result<(), CliError> {
+    let selected: Vec<&CurlOption> = match matches.get_one::<String>("name") {
+        Some(name) => {
+            let option = options::lookup(name).ok_or_else(|| {
+                format!("Error: unknown option {:?}, see `nomcurl options`", name)
+            })?;
+            vec![option]
+        }
+        None => {
+            let category = matches.get_one::<Category>("category");
+            options::OPTIONS
+                .iter()
+                .filter(|o| category.is_none_or(|c| o.category == *c))
+                .collect()
+        }
+    };
+
+    if matches.get_flag("json") {
+        let json = match matches.contains_id("name") {
+            true => serde_json::to_string_pretty(selected[0]),
+            false => serde_json::to_string_pretty(&selected),
+        }
+        .map_err(|e| {
+            let message = format!("Error serializing the options: {}", e);
+            CliError::new(ErrorCode::SerializeError, message)
+        })?;
+        return write_output(matches, format!("{}\n", json));
+    }
+
+    let mut output = String::new();
+    for option in selected {
+        let names = match option.short {
+            Some(short) => format!("{}, {}", short, option.name),
+            None => format!("    {}", option.name),
+        };
+        let names = match option.takes_value {
+            true => format!("{} <value>", names),
+            false => names,
+        };
+        let since = match option.deprecated {
+            Some(deprecated) => format!("since {}, {}", option.since, deprecated),
+            None => format!("since {}", option.since),
+        };
+        writeln!(
+            output,
+            "{:<32} {:<10} {} ({})",
+            names,
+            option.category.name(),
+            option.description,
+            since
+        )
+        .unwrap();
+    }
+    write_output(matches, output)
+}
+
+fn cookies(matches: &ArgMatches) -> Result<(), CliError> {
+    let request = read_request(matches)?;
+    let cookies = request.cookies();
+    let output = match matches.get_one::<CookieFormat>("format").unwrap() {
+        CookieFormat::Table => cookies::to_table(&cookies),
+        CookieFormat::Netscape => cookies::to_netscape(&request, &cookies),
+        CookieFormat::Json => serde_json::to_string_pretty(&cookies)
+            .map(|json| json + "\n")
+            .map_err(|e| {
+                CliError::new(
+                    ErrorCode::SerializeError,
+                    format!("Error serializing the cookies: {}", e),
+                )
+            })?,
+    };
+    write_output(matches, &output)
+}
+
+fn format(matches: &ArgMatches) -> Result<(), CliError> {
+    let request = read_request(matches)?;
+    let options = FormatOptions {
+        sort: matches.get_flag("sort"),
+        indent: matches.get_one::<usize>("indent").copied(),
+        color: use_color(matches),
+        shell: *matches.get_one::<shell_quote::Shell>("shell").unwrap(),
+        minimal_quotes: matches.get_flag("minimal-quotes"),
+        ..FormatOptions::pretty()
+    };
+    write_output(matches, format!("{}\n", request.format(&options)))
+}
+
+fn minify(matches: &ArgMatches) -> Result<(), CliError> {
+    let request = read_request(matches)?;
+    let options = FormatOptions {
+        strip_noop: matches.get_flag("strip-noop"),
+        color: use_color(matches),
+        shell: *matches.get_one::<shell_quote::Shell>("shell").unwrap(),
+        minimal_quotes: matches.get_flag("minimal-quotes"),
+        ..Default::default()
+    };
+    write_output(matches, format!("{}\n", request.format(&options)))
+}
+
+/// Edit the request in the editor, then validate the edited one and print it on a single line.
+///
+/// The edited file is left in the temp directory when it doesn't parse, to not lose the edit.
+fn edit(matches: &ArgMatches) -> Result<(), CliError> {
+    let request = read_request(matches)?;
+    let yaml = matches.get_flag("yaml");
+    let (content, extension) = match yaml {
+        true => (
+            request.to_yaml().map_err(|e| {
+                CliError::new(
+                    ErrorCode::SerializeError,
+                    format!("Error serializing curl command: {}", e),
+                )
+            })?,
+            "yaml",
+        ),
+        false => (
+            format!("{}\n", request.format(&FormatOptions::pretty())),
+            "sh",
+        ),
+    };
+    let (path, mut file) = create_edit_file(extension).map_err(|e| {
+        CliError::new(
+            ErrorCode::IoError,
+            format!("Error creating the file to edit: {}", e),
+        )
+    })?;
+    file.write_all(content.as_bytes()).map_err(|e| {
+        CliError::new(
+            ErrorCode::IoError,
+            format!("Error writing file {}: {}", path.display(), e),
+        )
+    })?;
+    drop(file);
+
+    let edited = run_editor(&path).and_then(|()| {
+        let input = read_file_or_stdin(&path.to_string_lossy())?;
+        let request = match yaml {
+            true => ParsedRequest::from_yaml(&input).map_err(|e| {
+                CliError::new(
+                    ErrorCode::InvalidDocument,
+                    format!("Error parsing request YAML: {}", e),
+                )
+            })?,
+            false => parse_request(matches, &input)?,
+        };
+        let findings = lint_request(matches, &request);
+        for finding in findings.iter().filter(|f| f.severity >= Severity::Warning) {
+            eprintln!("{}", finding);
+        }
+        match findings.iter().any(|f| f.severity == Severity::Error) {
+            true => Err(CliError::new(
+                ErrorCode::InvalidInput,
+                "Error: the edited command has lint errors",
+            )),
+            false => Ok(request.into_owned()),
+        }
+    });
+    let request = match edited {
+        Ok(request) => {
+            let _ = std::fs::remove_file(&path);
+            request
+        }
+        Err(mut e) => {
+            e.message
+                .push_str(&format!(", the edit is kept in {}", path.display()));
+            return Err(e);
+        }
+    };
+
+    let options = FormatOptions {
+        color: use_color(matches),
+        ..Default::default()
+    };
+    write_output(matches, format!("{}\n", request.format(&options)))
+}
+
+/// Create a new file in the temp directory, readable by the user only, for `edit`.
+///
+/// An existing file is never opened, so another user can't plant one, e.g. a symlink, under the
+/// name; the next name is tried instead.
+fn create_edit_file(extension: &str) -> std::io::Result<(PathBuf, std::fs::File)> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let pid = std::process::id();
+    let mut attempt = 0;
+    loop {
+        let name = format!("nomcurl-edit-{}-{}.{}", pid, attempt, extension);
+        let path = std::env::temp_dir().join(name);
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempt < 100 => {
+                attempt += 1
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Open the file in `$VISUAL`, `$EDITOR` or the platform editor, and wait for it to exit.
+fn run_editor(path: &Path) -> Result<(), CliError> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| match cfg!(windows) {
+            true => "notepad".into(),
+            false => "vi".into(),
+        });
+    debug!(editor, path = %path.display(), "opening the editor");
+
+    // The editor may have arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let status = std::process::Command::new(words.next().unwrap_or_default())
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| {
+            CliError::new(
+                ErrorCode::IoError,
+                format!("Error running {}: {}", editor, e),
+            )
+        })?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(CliError::new(
+            ErrorCode::IoError,
+            format!("Error: {} exited with {}", editor, status),
+        )),
+    }
+}
+
+fn build(matches: &ArgMatches) -> Result<(), CliError> {
+    let path = matches.get_one::<String>("spec").unwrap();
+    let spec = substitute_env(matches, read_file_or_stdin(path)?)?;
+    let request = ParsedRequest::from_json(&spec)
+        .map_err(|e| format!("Error reading request spec {}: {}", path, e))?;
+    check_warnings(matches, &request);
+
+    let options = match matches.get_flag("pretty") {
+        true => FormatOptions::pretty(),
+        false => FormatOptions::default(),
+    };
+    write_output(matches, format!("{}\n", request.format(&options)))
+}
+
+/// Print the command of every HAR entry, the ones which can't be built are reported on stderr.
+fn import(matches: &ArgMatches) -> Result<(), CliError> {
+    let path = matches.get_one::<String>("har").unwrap();
+    let har = read_file_or_stdin(path)?;
+    let entries = har::parse_har(&har).map_err(|e| {
+        CliError::new(
+            ErrorCode::Parse(e.code()),
+            format!("Error reading {}: {}", path, e),
+        )
+    })?;
+
+    let options = FormatOptions {
+        shell: *matches.get_one::<shell_quote::Shell>("shell").unwrap(),
+        minimal_quotes: matches.get_flag("minimal-quotes"),
+        ..match matches.get_flag("pretty") {
+            true => FormatOptions::pretty(),
+            false => FormatOptions::default(),
+        }
+    };
+    let mut output = String::new();
+    for (i, entry) in entries.iter().enumerate() {
+        match entry {
+            Ok(request) => output.push_str(&format!("{}\n", request.format(&options))),
+            Err(e) => eprintln!("warning: entry {}: {}", i + 1, e),
+        }
+    }
+    write_output(matches, output)
+}
+
+/// Print the command with its placeholders replaced, or its parsed JSON with `--json`.
+fn template(matches: &ArgMatches) -> Result<(), CliError> {
+    let input = read_input(matches)?;
+    let command = first_command(&input);
+    let (_, unresolved) = variables::substitute(command, &HashMap::new());
+    if !unresolved.is_empty() {
+        eprintln!("warning: unresolved variables: {}", unresolved.join(", "));
+    }
+
+    if !matches.get_flag("json") {
+        return write_output(matches, format!("{}\n", command.trim()));
+    }
+    let json = parse_request(matches, &input)?.to_json().map_err(|e| {
+        CliError::new(
+            ErrorCode::SerializeError,
+            format!("Error serializing curl command: {}", e),
+        )
+    })?;
+    write_output(matches, format!("{}\n", json))
+}
+
+/// Print the response head, then write the body to stdout or the `--output` file.
+#[cfg(feature = "exec")]
+fn exec(matches: &ArgMatches) -> Result<(), CliError> {
+    let request = read_request(matches)?;
+    let response = nomcurl::exec::send(&request)
+        .map_err(|e| CliError::new(ErrorCode::RequestError, format!("Error: {}", e)))?;
+
+    println!(
+        "{} {} {}",
+        response.version, response.status, response.reason
+    );
+    for (name, value) in &response.headers {
+        println!("{}: {}", name, value);
+    }
+    println!();
+    write_output(matches, &response.body)?;
+    eprintln!("Completed in {} ms", response.elapsed.as_millis());
+    Ok(())
+}
+
+const INTERACTIVE_HELP: &str = "\
+curl ...            parse a command, continued lines ending with `\\` are joined
+show                print the parts of the command
+header NAME: VALUE  set a header
+unheader NAME       remove a header
+method [METHOD]     set the -X method, or leave it implied
+host HOST           change the host of the url
+url URL             replace the url
+format              print the command with one option per line
+help                print this help
+quit                leave";
+
+/// A REPL over stdin: paste a command, inspect it, edit it and get the updated command back.
+fn interactive(color: bool) -> Result<(), CliError> {
+    println!("nomcurl interactive, type `help` for the commands");
+    let mut request: Option<ParsedRequest<'static>> = None;
+    let mut pending = String::new();
+    let stdin = std::io::stdin();
+
+    loop {
+        print!("{}", if pending.is_empty() { "> " } else { "... " });
+        std::io::stdout().flush().map_err(|e| e.to_string())?;
+        let mut line = String::new();
+        if stdin.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            return Ok(());
+        }
+
+        if !pending.is_empty() || line.trim_start().starts_with("curl") {
+            pending.push_str(&line);
+            if line.trim_end().ends_with('\\') {
+                continue;
+            }
+            match parse_curl_command(pending.trim()) {
+                Ok(parsed) => {
+                    print!("{}", show_request(&parsed, color));
+                    request = Some(parsed.into_owned());
+                }
+                Err(e) => println!("Error parsing curl command: {}", e),
+            }
+            pending.clear();
+            continue;
+        }
+
+        let (command, arg) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let arg = arg.trim();
+        match command {
+            "" => continue,
+            "help" => {
+                println!("{}", INTERACTIVE_HELP);
+                continue;
+            }
+            "quit" | "exit" => return Ok(()),
+            _ => {}
+        }
+        let Some(request) = request.as_mut() else {
+            println!("Paste a curl command first");
+            continue;
+        };
+
+        let result = match (command, arg) {
+            ("show", _) => {
+                print!("{}", show_request(request, color));
+                continue;
+            }
+            ("format", _) => {
+                let options = FormatOptions {
+                    color,
+                    ..FormatOptions::pretty()
+                };
+                println!("{}", request.format(&options));
+                continue;
+            }
+            ("header", arg) => match arg.split_once(':') {
+                Some((name, value)) => {
+                    request.set_header(name.trim(), value.trim());
+                    Ok(())
+                }
+                None => Err("Usage: header NAME: VALUE".to_string()),
+            },
+            ("unheader", name) => match request.remove_header(name) {
+                true => Ok(()),
+                false => Err(format!("No header {}", name)),
+            },
+            ("method", "") => {
+                request.set_method(None);
+                Ok(())
+            }
+            ("method", method) => method
+                .to_uppercase()
+                .parse()
+                .map(|method| request.set_method(Some(method)))
+                .map_err(|e: ParseError| e.to_string()),
+            ("host", "") => Err("Usage: host HOST".to_string()),
+            ("host", host) => {
+                request.url_mut().domain = host.to_string().into();
+                Ok(())
+            }
+            ("url", url) => CurlURL::try_from(url.trim_matches(|c| c == '\'' || c == '"'))
+                .map(|url| *request.url_mut() = url.into_owned())
+                .map_err(|e| e.to_string()),
+            (command, _) => Err(format!(
+                "Unknown command {}, type `help` for the commands",
+                command
+            )),
+        };
+        match result {
+            Ok(()) => println!("{}", request),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+/// The parts of the request, one section per kind of param.
+fn show_request(request: &ParsedRequest, color: bool) -> String {
+    let paint = |text: &str, style: Style| match color {
+        true => highlight::paint(text, style),
+        false => text.to_string(),
+    };
+    let url = request.url();
+    let mut output = String::new();
+    writeln!(output, "url      {}", paint(&url.to_string(), Style::Url)).unwrap();
+    writeln!(output, "  host   {}", url.domain).unwrap();
+    if let Some(uri) = url.uri.as_deref().filter(|u| !u.is_empty()) {
+        writeln!(output, "  path   {}", uri).unwrap();
+    }
+    for (name, value) in url.query_pairs() {
+        writeln!(output, "  query  {} = {}", name, paint(value, Style::Value)).unwrap();
+    }
+    let method = request.effective_method().to_string();
+    writeln!(output, "method   {}", paint(&method, Style::Method)).unwrap();
+
+    let headers: Vec<String> = request
+        .headers()
+        .map(|h| match h.split_once(':') {
+            Some((name, value)) => format!(
+                "{}:{}",
+                paint(name, Style::HeaderName),
+                paint(value, Style::Value)
+            ),
+            None => h.to_string(),
+        })
+        .collect();
+    let data: Vec<String> = request.data().into_iter().map(String::from).collect();
+    let flags: Vec<String> = request
+        .flags()
+        .filter(|f| !options::is_data_option(&f.identifier))
+        .map(|f| match &f.data {
+            Some(data) => format!(
+                "{} {}",
+                paint(&f.identifier, Style::Flag),
+                paint(data, Style::Value)
+            ),
+            None => paint(&f.identifier, Style::Flag),
+        })
+        .collect();
+    for (title, items) in [("headers", headers), ("data", data), ("flags", flags)] {
+        if !items.is_empty() {
+            writeln!(output, "{}", title).unwrap();
+        }
+        for item in items {
+            writeln!(output, "  {}", item).unwrap();
+        }
+    }
+    output
+}
+
+fn batch(matches: &ArgMatches) -> Result<(), CliError> {
+    let input = read_file_or_stdin(matches.get_one::<String>("input").unwrap())?;
+    let input = substitute_env(matches, input)?;
+    let entries = batch::parse_batch(&input);
+    check_batch_warnings(matches, &entries);
+    let output = match matches.get_one::<BatchFormat>("format").unwrap() {
+        BatchFormat::Ndjson => batch::to_ndjson(&entries),
+        BatchFormat::Json => batch::to_json(&entries).map(|json| json + "\n"),
+    }
+    .map_err(|e| {
+        CliError::new(
+            ErrorCode::SerializeError,
+            format!("Error serializing curl commands: {}", e),
+        )
+    })?;
+    write_output(matches, &output)?;
+
+    let (parsed, failed) = batch::counts(&entries);
+    eprintln!(
+        "{} curl commands: {} parsed, {} failed",
+        entries.len(),
+        parsed,
+        failed
+    );
+    Ok(())
+}
+
+/// Print the matching commands of stdin as soon as they're complete, unparsable ones are skipped.
+fn filter(matches: &ArgMatches) -> Result<(), CliError> {
+    let values = |id: &str| -> Vec<String> {
+        matches
+            .get_many::<String>(id)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    };
+    let filter = RequestFilter {
+        hosts: values("host"),
+        methods: matches
+            .get_many::<Method>("method")
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect(),
+        headers: values("header"),
+    };
+
+    let mut stdout = std::io::stdout();
+    let io_error = |e: std::io::Error| CliError::new(ErrorCode::IoError, format!("Error: {}", e));
+    let mut print = |command: String| -> Result<(), CliError> {
+        let command = substitute_env(matches, command)?;
+        let request = match parse_curl_command(&command) {
+            Ok(request) if filter.matches(&request) => request,
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                debug!(error = %e, "skipped an unparsable command");
+                return Ok(());
+            }
+        };
+        check_warnings(matches, &request);
+        let output = match matches.get_flag("json") {
+            true => serde_json::to_string(&request).map_err(|e| {
+                let message = format!("Error serializing curl command: {}", e);
+                CliError::new(ErrorCode::SerializeError, message)
+            })?,
+            false => command,
+        };
+        writeln!(stdout, "{}", output).map_err(io_error)?;
+        stdout.flush().map_err(io_error)
+    };
+
+    let mut stream = CommandStream::new();
+    for line in std::io::stdin().lines() {
+        if let Some(command) = stream.push_line(&line.map_err(io_error)?) {
+            print(command)?;
+        }
+    }
+    match stream.finish() {
+        Some(command) => print(command),
+        None => Ok(()),
+    }
+}
+
+fn stats(matches: &ArgMatches) -> Result<(), CliError> {
+    let input = read_file_or_stdin(matches.get_one::<String>("input").unwrap())?;
+    let input = substitute_env(matches, input)?;
+    let stats = CorpusStats::new(&input);
+    let output = match matches.get_flag("json") {
+        true => serde_json::to_string_pretty(&stats).map_err(|e| {
+            CliError::new(
+                ErrorCode::SerializeError,
+                format!("Error serializing the statistics: {}", e),
+            )
+        })?,
+        false => stats.to_string(),
+    };
+    write_output(matches, format!("{}\n", output))
+}
+
+/// The command text of the `parse` input: the positional argument, stdin for `-`, or `--file`.
+fn read_input(matches: &ArgMatches) -> Result<String, CliError> {
+    if let Some(path) = matches.get_one::<String>("file") {
+        return substitute_env(matches, read_file_or_stdin(path)?);
+    }
+
+    let command = matches.get_one::<String>("command").unwrap();
+    let input = match command.as_str() {
+        "-" => read_file_or_stdin(command)?,
+        _ => command.clone(),
+    };
+    debug!(bytes = input.len(), "read the command");
+    substitute_env(matches, input)
+}
+
+/// A curl command given inline, or the content of the file holding it, `-` reads stdin.
+fn read_command_arg(matches: &ArgMatches, arg: &str) -> Result<String, CliError> {
+    let input = match is_curl_invocation(arg.trim_start()) {
+        true => arg.to_string(),
+        false => read_file_or_stdin(arg)?,
+    };
+    substitute_env(matches, input)
+}
+
+/// Resolve the placeholders of the input with the `--env-file`, `--env` and, for `template`,
+/// `--var` variables, an unresolved one is an error with `--require-env`.
+fn substitute_env(matches: &ArgMatches, input: String) -> Result<String, CliError> {
+    let mut vars = HashMap::new();
+    if let Some(path) = matches.get_one::<String>("env-file") {
+        let content = read_file_or_stdin(path)?;
+        let file_vars = variables::parse_env_file(&content)
+            .map_err(|e| format!("Error reading env file {}: {}", path, e))?;
+        vars.extend(file_vars);
+    }
+    let definitions = matches.get_many::<String>("env").into_iter().flatten();
+    let template_vars = matches.try_get_many::<String>("var").ok().flatten();
+    for definition in definitions.chain(template_vars.into_iter().flatten()) {
+        let (name, value) =
+            variables::parse_variable(definition).map_err(|e| format!("Error: {}", e))?;
+        vars.insert(name, value);
+    }
+
+    let (output, unresolved) = variables::substitute(&input, &vars);
+    if !vars.is_empty() || !unresolved.is_empty() {
+        debug!(
+            variables = vars.len(),
+            ?unresolved,
+            "substituted the placeholders"
+        );
+    }
+    if matches.get_flag("require-env") && !unresolved.is_empty() {
+        return Err(format!("Error: unresolved variables: {}", unresolved.join(", ")).into());
+    }
+    Ok(output)
+}
+
+/// The request of the first curl command of the input, see `read_input`.
+fn read_request(matches: &ArgMatches) -> Result<ParsedRequest<'static>, CliError> {
+    let input = read_input(matches)?;
+    parse_request(matches, &input).map(ParsedRequest::into_owned)
+}
+
+/// The first curl command of the input, a file may hold comments or a shebang around it.
+fn first_command(input: &str) -> &str {
+    let commands = find_curl_commands(input);
+    debug!(
+        commands = commands.len(),
+        dialect = dialect(input),
+        "found the curl commands of the input, using the first"
+    );
+    commands.into_iter().next().unwrap_or(input)
+}
+
+/// The shell the command was likely copied from, see `dialect::detect`.
+fn dialect(input: &str) -> &'static str {
+    dialect::detect(input)
+        .map(|hint| hint.sign.shell())
+        .unwrap_or_default()
+        .name()
+}
+
+/// Parse the first command of the input and report its lint warnings with `--fail-on-warning`.
+fn parse_request<'a>(matches: &ArgMatches, input: &'a str) -> Result<ParsedRequest<'a>, CliError> {
+    let command = first_command(input);
+    let request = parse_curl_command(command).map_err(|e| {
+        debug!(error = ?e, "failed to parse the command");
+        parse_error(input, command, "Error parsing curl command", &e)
+    })?;
+    request
+        .check_protocol(lint_policy(matches).protocol)
+        .and_then(|_| request.check_method(lint_policy(matches).method))
+        .map_err(|e| parse_error(input, command, "Error parsing curl command", &e))?;
+    for curl in request.curls() {
+        trace!(param = %curl, "parsed a param");
+    }
+    debug!(
+        url = %request.url(),
+        method = %request.effective_method(),
+        params = request.curls().len(),
+        "parsed the request"
+    );
+    for finding in lint_request(matches, &request) {
+        debug!(code = finding.lint.code(), severity = %finding.severity, "{}", finding.message);
+    }
+    check_warnings(matches, &request);
+    Ok(request)
+}
+
+/// The `--unknown-protocol` and `--custom-method` policies.
+fn lint_policy(matches: &ArgMatches) -> LintPolicy {
+    LintPolicy {
+        protocol: *matches
+            .get_one::<ProtocolPolicy>("unknown-protocol")
+            .unwrap(),
+        method: *matches.get_one::<MethodPolicy>("custom-method").unwrap(),
+    }
+}
+
+/// The lint findings of the request, an unknown url scheme or a custom method reported as
+/// `--unknown-protocol` and `--custom-method` tell.
+fn lint_request(matches: &ArgMatches, request: &ParsedRequest) -> Vec<Finding> {
+    lint::lint_with_policy(request, lint_policy(matches))
+}
+
+/// Set by `check_warnings`, the run then exits with 2 once its output is written.
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// With `--fail-on-warning`, print the lint warnings and errors of the request to stderr.
+fn check_warnings(matches: &ArgMatches, request: &ParsedRequest) {
+    if !matches.get_flag("fail-on-warning") {
+        return;
+    }
+    for finding in lint_request(matches, request) {
+        if finding.severity >= Severity::Warning {
+            eprintln!("{}", finding);
+            WARNED.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+fn check_batch_warnings(matches: &ArgMatches, entries: &[batch::BatchEntry]) {
+    for entry in entries.iter().filter(|e| e.request.is_some()) {
+        if let Ok(request) = parse_curl_command(&entry.command) {
+            check_warnings(matches, &request);
         }
     }
 }
+
+/// The content of the file at the path, or of stdin for `-`.
+fn read_file_or_stdin(path: &str) -> Result<String, CliError> {
+    debug!(path, "reading the input");
+    if path != "-" {
+        return std::fs::read_to_string(path).map_err(|e| {
+            CliError::new(
+                ErrorCode::IoError,
+                format!("Error reading file {}: {}", path, e),
+            )
+        });
+    }
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| CliError::new(ErrorCode::IoError, format!("Error reading stdin: {}", e)))?;
+    Ok(input)
+}
+
+/// Log to stderr with `-v`, at the debug level, or the trace level from `-vv`.
+fn init_tracing(verbose: u8) {
+    let level = match verbose {
+        0 => return,
+        1 => tracing::Level::DEBUG,
+        _ => tracing::Level::TRACE,
+    };
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .without_time()
+        .with_target(false)
+        .init();
+}
+
+/// Whether to color the output, `auto` colors a terminal stdout unless NO_COLOR is set.
+fn use_color(matches: &ArgMatches) -> bool {
+    match matches.get_one::<ColorChoice>("color").unwrap() {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+            let to_file = matches
+                .try_get_one::<String>("output")
+                .is_ok_and(|o| o.is_some());
+            !no_color && !to_file && std::io::stdout().is_terminal()
+        }
+    }
+}
+
+/// Print the output, or write it to the `--output` file which is only overwritten with `--force`.
+fn write_output(matches: &ArgMatches, output: impl AsRef<[u8]>) -> Result<(), CliError> {
+    let Some(path) = matches.get_one::<String>("output") else {
+        return std::io::stdout().write_all(output.as_ref()).map_err(|e| {
+            CliError::new(ErrorCode::IoError, format!("Error writing stdout: {}", e))
+        });
+    };
+
+    if Path::new(path).exists() && !matches.get_flag("force") {
+        let message = format!(
+            "Error writing file {}: it already exists, use --force to overwrite it",
+            path
+        );
+        return Err(CliError::new(ErrorCode::IoError, message));
+    }
+    std::fs::write(path, output).map_err(|e| {
+        CliError::new(
+            ErrorCode::IoError,
+            format!("Error writing file {}: {}", path, e),
+        )
+    })
+}
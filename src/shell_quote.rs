@@ -0,0 +1,194 @@
+//! Quoting of command arguments, e.g. to write a curl command for a shell.
+
+use crate::prelude::*;
+
+/// The shells an argument can be quoted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shell {
+    /// `sh`, `bash`, `zsh` and the other POSIX shells.
+    #[default]
+    Posix,
+    /// `cmd.exe`, with the argument split by the C runtime of the program, e.g. `curl.exe`.
+    Cmd,
+    PowerShell,
+}
+
+impl Shell {
+    pub const ALL: [Shell; 3] = [Shell::Posix, Shell::Cmd, Shell::PowerShell];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Posix => "posix",
+            Shell::Cmd => "cmd",
+            Shell::PowerShell => "powershell",
+        }
+    }
+
+    /// The char ending a line continued on the next one, e.g. `\` for a POSIX shell.
+    pub fn continuation(self) -> char {
+        match self {
+            Shell::Posix => '\\',
+            Shell::Cmd => '^',
+            Shell::PowerShell => '`',
+        }
+    }
+
+    /// Quote the argument for the shell, arguments without special characters are kept as is.
+    pub fn quote(self, arg: &str) -> String {
+        match self {
+            Shell::Posix => posix(arg),
+            Shell::Cmd => cmd(arg),
+            Shell::PowerShell => powershell(arg),
+        }
+    }
+}
+
+/// Whether the argument means the same unquoted in the shell.
+fn is_safe(shell: Shell, arg: &str) -> bool {
+    let safe = match shell {
+        Shell::Posix => "-_./:=@,+%^",
+        // `%VAR%` is expanded and `^` is an escape
+        Shell::Cmd => "-_./:=@,+",
+        // `@var` splats a variable and `,` makes an array
+        Shell::PowerShell => "-_./:=+%",
+    };
+    !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || safe.contains(c))
+}
+
+/// Quote the argument for a POSIX shell, arguments without special characters are kept as is.
+pub fn posix(arg: &str) -> String {
+    if is_safe(Shell::Posix, arg) {
+        return arg.into();
+    }
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// Always quote the argument for a POSIX shell, in single quotes unless it contains one, as
+/// written by the `Display` of a curl param, e.g. `-H 'Accept: */*'`.
+pub fn posix_quoted(arg: &str) -> String {
+    if !arg.contains('\'') {
+        format!("'{}'", arg)
+    } else if !arg.contains(['"', '$', '`', '\\', '!']) {
+        format!("\"{}\"", arg)
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Quote the argument for `cmd.exe`, in double quotes escaped for the C runtime argument
+/// splitting, arguments without special characters are kept as is.
+///
+/// `%VAR%` is still expanded inside double quotes by an interactive `cmd.exe`.
+pub fn cmd(arg: &str) -> String {
+    if is_safe(Shell::Cmd, arg) {
+        return arg.into();
+    }
+
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                // Backslashes before a quote are escapes
+                quoted.extend(core::iter::repeat_n('\\', backslashes * 2 + 1));
+                backslashes = 0;
+            }
+            _ => {
+                quoted.extend(core::iter::repeat_n('\\', backslashes));
+                backslashes = 0;
+            }
+        }
+        if c != '\\' {
+            quoted.push(c);
+        }
+    }
+    // The closing quote follows them too
+    quoted.extend(core::iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// Quote the argument for PowerShell in single quotes, arguments without special characters
+/// are kept as is.
+pub fn powershell(arg: &str) -> String {
+    if is_safe(Shell::PowerShell, arg) {
+        return arg.into();
+    }
+    powershell_quoted(arg)
+}
+
+/// Always quote the argument in PowerShell single quotes, e.g. a string literal of a script.
+pub fn powershell_quoted(arg: &str) -> String {
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('\'');
+    for c in arg.chars() {
+        // PowerShell also ends a string at the typographic single quotes
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::generic_parse;
+
+    #[test]
+    fn test_posix() {
+        generic_parse(posix, "https://a.com/b", "https://a.com/b".to_string());
+        generic_parse(posix, "a^b", "a^b".to_string());
+        generic_parse(posix, "@body.json", "@body.json".to_string());
+        generic_parse(posix, "a,b%20", "a,b%20".to_string());
+        generic_parse(posix, "", "''".to_string());
+        generic_parse(posix, "a b", "'a b'".to_string());
+        generic_parse(posix, "it's", r"'it'\''s'".to_string());
+    }
+
+    #[test]
+    fn test_posix_quoted() {
+        generic_parse(posix_quoted, "a", "'a'".to_string());
+        generic_parse(posix_quoted, "it's", "\"it's\"".to_string());
+        generic_parse(posix_quoted, "it's $HOME", r"'it'\''s $HOME'".to_string());
+        generic_parse(posix_quoted, "it's \"a\"", r#"'it'\''s "a"'"#.to_string());
+    }
+
+    #[test]
+    fn test_cmd() {
+        generic_parse(cmd, "https://a.com/b", "https://a.com/b".to_string());
+        generic_parse(cmd, "", "\"\"".to_string());
+        generic_parse(cmd, "@body.json", "@body.json".to_string());
+        generic_parse(cmd, "%PATH%", "\"%PATH%\"".to_string());
+        generic_parse(cmd, "a^b", "\"a^b\"".to_string());
+        generic_parse(cmd, "a b", "\"a b\"".to_string());
+        generic_parse(cmd, r#"{"a":1}"#, r#""{\"a\":1}""#.to_string());
+        generic_parse(cmd, r"C:\a b\", r#""C:\a b\\""#.to_string());
+        generic_parse(cmd, r#"a\"b"#, r#""a\\\"b""#.to_string());
+    }
+
+    #[test]
+    fn test_powershell() {
+        generic_parse(powershell, "https://a.com/b", "https://a.com/b".to_string());
+        generic_parse(powershell, "", "''".to_string());
+        generic_parse(powershell, "@body.json", "'@body.json'".to_string());
+        generic_parse(powershell, "a,b", "'a,b'".to_string());
+        generic_parse(powershell, "a%20", "a%20".to_string());
+        generic_parse(powershell, "$a b", "'$a b'".to_string());
+        generic_parse(powershell, "it's", "'it''s'".to_string());
+        generic_parse(
+            powershell,
+            "it\u{2019}s",
+            "'it\u{2019}\u{2019}s'".to_string(),
+        );
+        assert_eq!("'a b'", Shell::PowerShell.quote("a b"));
+        generic_parse(powershell_quoted, "a", "'a'".to_string());
+    }
+}
@@ -3,9 +3,9 @@
 pub fn generic_command_parse<F, I, T, E>(parser: F, input: I, expect: T)
 where
     F: Fn(I) -> Result<(I, T), E>,
-    T: PartialEq + std::fmt::Debug,
-    I: std::fmt::Debug,
-    E: std::fmt::Debug,
+    T: PartialEq + core::fmt::Debug,
+    I: core::fmt::Debug,
+    E: core::fmt::Debug,
 {
     let result = parser(input);
     assert!(result.is_ok(), "The result:\r\n{:#?}", result);
@@ -21,8 +21,8 @@ where
 pub fn generic_parse<F, I, T>(parser: F, input: I, expect: T)
 where
     F: Fn(I) -> T,
-    T: PartialEq + std::fmt::Debug,
-    I: std::fmt::Debug,
+    T: PartialEq + core::fmt::Debug,
+    I: core::fmt::Debug,
 {
     let result = parser(input);
     assert_eq!(
@@ -31,3 +31,171 @@ where
         expect, result
     );
 }
+
+/// proptest generators of the urls and params the parsers accept, for the round-trip tests.
+#[cfg(test)]
+pub mod strategies {
+    use proptest::{collection::vec, option, prelude::*, sample::select};
+
+    use crate::curl::{options, Curl, CurlStru};
+
+    /// A url written the way `CurlURL` displays it: `userinfo@`, a path, `key=value` queries
+    /// without empty values and an alphanumeric fragment.
+    pub fn url() -> impl Strategy<Value = String> {
+        let query = ("[a-z_]{1,6}", option::of("[a-zA-Z0-9%._~-]{1,8}"));
+        (
+            select(&["http", "https", "ftp"][..]),
+            option::of("[a-z][a-z0-9]{0,7}:[a-zA-Z0-9]{1,8}"),
+            vec("[a-z0-9][a-z0-9-]{0,10}", 1..4),
+            option::of(1u16..),
+            vec("[a-zA-Z0-9._~%-]{1,10}", 1..4),
+            option::of(vec(query, 1..4)),
+            option::of("[a-zA-Z0-9]{1,8}"),
+        )
+            .prop_map(
+                |(protocol, userinfo, labels, port, path, queries, fragment)| {
+                    let mut url = format!("{}://", protocol);
+                    if let Some(userinfo) = userinfo {
+                        url.push_str(&format!("{}@", userinfo));
+                    }
+                    url.push_str(&labels.join("."));
+                    if let Some(port) = port {
+                        url.push_str(&format!(":{}", port));
+                    }
+                    url.push_str(&format!("/{}", path.join("/")));
+                    if let Some(queries) = queries {
+                        let queries: Vec<String> = queries
+                            .into_iter()
+                            .map(|(k, v)| v.map_or(k.clone(), |v| format!("{}={}", k, v)))
+                            .collect();
+                        url.push_str(&format!("?{}", queries.join("&")));
+                    }
+                    if let Some(fragment) = fragment {
+                        url.push_str(&format!("#{}", fragment));
+                    }
+                    url
+                },
+            )
+    }
+
+    /// A param value of printable characters, quotes and backslashes included, maybe empty.
+    pub fn value() -> impl Strategy<Value = String> {
+        "[^\\x00-\\x1f\\x7f]{0,20}"
+    }
+
+    /// A `-X`, `-H`, `-d` or flag param, under the long name or the short alias of the option.
+    pub fn param() -> impl Strategy<Value = Curl<'static>> {
+        let identifier = |option: &options::CurlOption, short: bool| match option.short {
+            Some(alias) if short => alias,
+            _ => option.name,
+        };
+        // The parsers of -X, -H and -d take them apart from the flags
+        let flags: Vec<&options::CurlOption> = options::OPTIONS
+            .iter()
+            .filter(|o| !matches!(o.short, Some("-X" | "-H" | "-d")))
+            .collect();
+        let flag =
+            (select(flags), any::<bool>(), value()).prop_map(move |(option, short, value)| {
+                let identifier = identifier(option, short);
+                match option.takes_value {
+                    true => Curl::Flag(CurlStru::new_with_data(identifier, value)),
+                    false => Curl::Flag(CurlStru::new(identifier)),
+                }
+            });
+
+        prop_oneof![
+            "[A-Z]{1,7}".prop_map(|m| Curl::Method(CurlStru::new_with_data("-X", m))),
+            ("[A-Za-z][A-Za-z0-9-]{0,15}", value()).prop_map(|(name, value)| {
+                Curl::Header(CurlStru::new_with_data(
+                    "-H",
+                    format!("{}: {}", name, value),
+                ))
+            }),
+            value().prop_map(|data| Curl::Data(CurlStru::new_with_data("-d", data))),
+            flag,
+        ]
+    }
+}
+
+/// The golden corpus: real-world curl commands, each `NAME.sh` fixture of a directory paired
+/// with its expected `NAME.json` snapshot. Run with `NOMCURL_UPDATE_GOLDEN=1` to write the
+/// snapshots anew, then review their diff.
+///
+/// A fixture the parser can't read yet is a known failure: it has no snapshot, and the check
+/// fails once it's read, to move it to the snapshots.
+#[cfg(all(test, feature = "std"))]
+pub mod golden {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    pub const UPDATE_VAR: &str = "NOMCURL_UPDATE_GOLDEN";
+
+    /// The `.sh` fixtures of the directory, sorted by name.
+    pub fn fixtures(dir: &Path) -> Vec<PathBuf> {
+        let entries = fs::read_dir(dir)
+            .unwrap_or_else(|e| panic!("The golden corpus {}: {}", dir.display(), e));
+        let mut fixtures: Vec<PathBuf> = entries
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "sh"))
+            .collect();
+        fixtures.sort();
+        fixtures
+    }
+
+    /// Render every fixture of the directory and compare it with its snapshot, every mismatch is
+    /// reported at once. `render` fails on a fixture which isn't read, only the fixtures named
+    /// in `known_failures` (without their extension) may fail.
+    pub fn check(
+        dir: &Path,
+        known_failures: &[&str],
+        render: impl Fn(&str) -> Result<String, String>,
+    ) {
+        let update = std::env::var_os(UPDATE_VAR).is_some();
+        let fixtures = fixtures(dir);
+        assert!(!fixtures.is_empty(), "No fixture in {}", dir.display());
+
+        let mut failures = Vec::new();
+        for fixture in fixtures {
+            let input = fs::read_to_string(&fixture).unwrap();
+            let name = fixture.file_stem().unwrap().to_string_lossy();
+            let known_failure = known_failures.contains(&name.as_ref());
+            let actual = match (render(&input), known_failure) {
+                (Ok(actual), false) => actual,
+                (Err(_), true) => continue,
+                (Ok(_), true) => {
+                    failures.push(format!(
+                        "{} is read now, remove it from the known failures",
+                        fixture.display()
+                    ));
+                    continue;
+                }
+                (Err(e), false) => {
+                    failures.push(format!("{} fails: {}", fixture.display(), e));
+                    continue;
+                }
+            };
+            let snapshot = fixture.with_extension("json");
+            if update {
+                fs::write(&snapshot, &actual).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&snapshot) {
+                Ok(expect) if expect == actual => {}
+                Ok(expect) => failures.push(format!(
+                    "{} differs from its snapshot:\r\n--- expected\r\n{}\r\n+++ actual\r\n{}",
+                    fixture.display(),
+                    expect,
+                    actual
+                )),
+                Err(_) => failures.push(format!(
+                    "{} has no snapshot, run with {}=1 to write it",
+                    fixture.display(),
+                    UPDATE_VAR
+                )),
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\r\n\r\n"));
+    }
+}
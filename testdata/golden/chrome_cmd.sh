@@ -0,0 +1,4 @@
+curl "https://example.com/api/items?page=2" ^
+  -H "accept: */*" ^
+  -H "user-agent: Mozilla/5.0 (Windows NT 10.0; Win64; x64)" ^
+  --compressed
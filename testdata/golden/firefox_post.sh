@@ -0,0 +1 @@
+curl 'https://httpbin.org/post' -X POST -H 'User-Agent: Mozilla/5.0 (X11; Linux x86_64; rv:130.0) Gecko/20100101 Firefox/130.0' -H 'Accept: application/json' -H 'Content-Type: application/x-www-form-urlencoded' --data-raw 'name=nomcurl&tags=nom%2Ccurl'
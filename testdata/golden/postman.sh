@@ -0,0 +1,10 @@
+curl --location 'https://postman-echo.com/post?source=postman' \
+--header 'Content-Type: application/json' \
+--header 'Authorization: Basic dXNlcjpwYXNzd2Q=' \
+--data '{
+    "name": "nomcurl",
+    "count": -1,
+    "nested": {
+        "quote": "it'\''s"
+    }
+}'
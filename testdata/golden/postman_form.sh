@@ -0,0 +1,3 @@
+curl --location --request PUT 'https://postman-echo.com/put' \
+--form 'file=@"/home/user/report.pdf"' \
+--form 'note="quarterly report"'
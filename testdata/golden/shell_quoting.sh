@@ -0,0 +1,7 @@
+curl -sS -X 'PATCH' "https://api.example.com/users/42?fields=name,email" \
+  -H "Content-Type: application/json" \
+  -H 'X-Empty;' \
+  -d "{\"name\": \"O'Brien\", \"path\": \"C:\\\\temp\"}" \
+  -o - \
+  --max-time '-1' \
+  --retry '3'
@@ -0,0 +1,6 @@
+curl 'https://api.stripe.com/v1/customers' \
+  -u 'sk_test_4eC39HqLyjWDarjtT1zdp7dc:' \
+  -d 'description=Customer for jenny.rosen@example.com' \
+  -d 'metadata[order_id]=6735' \
+  -d 'expand[]=default_source' \
+  -G